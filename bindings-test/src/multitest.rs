@@ -30,8 +30,8 @@ use elys_bindings::{
         QueryVestingInfoResponse, StableStakeParamsData, StableStakeParamsResp,
     },
     types::{
-        BalanceAvailable, Mtp, OracleAssetInfo, PageResponse, Price, SwapAmountInRoute,
-        SwapAmountOutRoute,
+        BalanceAvailable, Mtp, OracleAssetInfo, PageResponse, PerpetualPosition, Price,
+        SwapAmountInRoute, SwapAmountOutRoute,
     },
     ElysMsg, ElysQuery,
 };
@@ -43,6 +43,7 @@ pub const BLOCK_TIME: u64 = 5;
 pub const PERPETUAL_OPENED_POSITION: Item<Vec<Mtp>> = Item::new("perpetual_opened_position");
 pub const LAST_MODULE_USED: Item<Option<String>> = Item::new("last_module_used");
 pub const ACCOUNT: Item<Vec<String>> = Item::new("account");
+pub const SWAP_SLIPPAGE: Item<Decimal> = Item::new("swap_slippage");
 
 pub struct ElysModule {}
 
@@ -84,6 +85,9 @@ impl ElysModule {
     ) -> StdResult<()> {
         ASSET_INFO.save(store, infos)
     }
+    pub fn set_swap_slippage(&self, store: &mut dyn Storage, slippage: Decimal) -> StdResult<()> {
+        SWAP_SLIPPAGE.save(store, &slippage)
+    }
     pub fn set_mtp(&self, store: &mut dyn Storage, mtps: &Vec<Mtp>) -> StdResult<()> {
         PERPETUAL_OPENED_POSITION.save(store, mtps)
     }
@@ -297,7 +301,7 @@ impl Module for ElysModule {
                     available_liquidity: coin(999999, denom_out),
                     weight_balance_ratio: SignedDecimal::zero(),
                     price_impact: SignedDecimal::zero(),
-                    slippage: Decimal::zero(),
+                    slippage: SWAP_SLIPPAGE.may_load(storage)?.unwrap_or_default(),
                 };
 
                 Ok(to_json_binary(&resp)?)
@@ -513,20 +517,20 @@ impl Module for ElysModule {
                     return Ok(to_json_binary(&resp)?);
                 }
 
-                let prices = PRICES.load(storage).unwrap();
-                let info = ASSET_INFO.load(storage).unwrap();
+                let prices = PRICES.load(storage)?;
+                let info = ASSET_INFO.load(storage)?;
 
                 let asset_info = info
                     .iter()
                     .find(|i| i.band_ticker == asset)
                     .cloned()
-                    .unwrap();
+                    .ok_or_else(|| StdError::not_found("asset info"))?;
 
                 let price = prices
                     .iter()
                     .find(|price| price.asset == asset_info.denom)
                     .cloned()
-                    .unwrap()
+                    .ok_or_else(|| StdError::not_found("price"))?
                     .price;
 
                 let resp = QueryGetPriceResponse {
@@ -560,11 +564,11 @@ impl Module for ElysModule {
                 let price_in = prices
                     .iter()
                     .find(|price| price.asset == token_in.denom)
-                    .unwrap();
+                    .ok_or_else(|| StdError::not_found("price"))?;
                 let price_out = prices
                     .iter()
                     .find(|price| price.asset == "ibc/2180E84E20F5679FCC760D8C165B60F42065DEF7F46A72B447CFF1B7DC6C0A65".to_string())
-                    .unwrap();
+                    .ok_or_else(|| StdError::not_found("price"))?;
                 let spot_price = price_in.price / price_out.price;
 
                 let resp = spot_price;
@@ -711,6 +715,14 @@ impl Module for ElysModule {
                     .unwrap())
                 .floor()
                 .atomics();
+                // a long position holds the trading asset and borrows the collateral asset,
+                // a short position holds the collateral asset and borrows the trading asset
+                let (custody_asset, liabilities_asset) =
+                    if position == PerpetualPosition::Short as i32 {
+                        (collateral.denom.clone(), trading_asset.clone())
+                    } else {
+                        (trading_asset.clone(), collateral.denom.clone())
+                    };
                 let mtp: Mtp = Mtp {
                     address: owner,
                     liabilities: Int128::zero(),
@@ -728,13 +740,13 @@ impl Module for ElysModule {
                     collateral_asset: collateral.denom,
                     collateral: Int128::new((collateral.amount.u128()) as i128),
                     custody,
-                    custody_asset: "".to_string(),
+                    custody_asset,
                     funding_fee_paid_collateral: Int128::zero(),
                     funding_fee_paid_custody: Int128::zero(),
                     funding_fee_received_collateral: Int128::zero(),
                     funding_fee_received_custody: Int128::zero(),
                     leverage: SignedDecimal::try_from(leverage)?,
-                    liabilities_asset: "".to_string(),
+                    liabilities_asset,
                     open_price: SignedDecimal::zero(),
                     take_profit_borrow_rate: SignedDecimal::zero(),
                     take_profit_custody: Int128::zero(),
@@ -807,6 +819,68 @@ impl Module for ElysModule {
                 PERPETUAL_OPENED_POSITION.save(storage, &mtps)?;
                 Ok(resp)
             }
+            ElysMsg::PerpetualAddCollateral {
+                creator,
+                id,
+                amount,
+                owner,
+            } => {
+                LAST_MODULE_USED.save(storage, &Some("PerpetualAddCollateral".to_string()))?;
+                let mut mtps: Vec<Mtp> = PERPETUAL_OPENED_POSITION.load(storage)?;
+
+                let mtp = match mtps
+                    .iter_mut()
+                    .find(|mtp| mtp.address.as_str() == owner.as_str() && mtp.id == id)
+                {
+                    Some(mtp) => mtp,
+                    None => return Err(StdError::not_found("mtp").into()),
+                };
+
+                // the position size stays the same, the leverage goes down with the new collateral
+                let collateral = mtp.collateral.checked_add(amount)?;
+                mtp.leverage *= SignedDecimal::from_ratio(mtp.collateral, collateral);
+                mtp.collateral = collateral;
+
+                let burn_msg = BankMsg::Burn {
+                    amount: coins(amount.i128() as u128, &mtp.collateral_asset),
+                };
+                PERPETUAL_OPENED_POSITION.save(storage, &mtps)?;
+
+                router.execute(
+                    api,
+                    storage,
+                    block,
+                    Addr::unchecked(creator),
+                    burn_msg.into(),
+                )?;
+
+                Ok(AppResponse {
+                    events: vec![],
+                    data: None,
+                })
+            }
+            ElysMsg::PerpetualUpdateTakeProfitPrice {
+                id, price, owner, ..
+            } => {
+                LAST_MODULE_USED
+                    .save(storage, &Some("PerpetualUpdateTakeProfitPrice".to_string()))?;
+                let mut mtps: Vec<Mtp> = PERPETUAL_OPENED_POSITION.load(storage)?;
+
+                let mtp = match mtps
+                    .iter_mut()
+                    .find(|mtp| mtp.address.as_str() == owner.as_str() && mtp.id == id)
+                {
+                    Some(mtp) => mtp,
+                    None => return Err(StdError::not_found("mtp").into()),
+                };
+                mtp.take_profit_price = price;
+
+                PERPETUAL_OPENED_POSITION.save(storage, &mtps)?;
+                Ok(AppResponse {
+                    events: vec![],
+                    data: None,
+                })
+            }
             ElysMsg::AmmSwapByDenom {
                 sender,
                 amount,
@@ -468,6 +468,75 @@ fn perpetual_perpetual_close_position() {
 
     assert_eq!(last_module_used, "PerpetualClose");
 }
+
+#[test]
+fn perpetual_add_collateral_and_update_take_profit_price() {
+    let wallets: Vec<(&str, Vec<Coin>)> = vec![("contract_addr", coins(10, "btc"))];
+    let mut app = ElysApp::new_with_wallets(wallets);
+
+    let open_msg = ElysMsg::perpetual_open_position(
+        "contract_addr",
+        coin(5, "btc"),
+        "uusdc",
+        PerpetualPosition::Short,
+        SignedDecimal::from_atomics(Int64::new(4), 0).unwrap(),
+        None,
+        "user",
+    );
+
+    app.execute(Addr::unchecked("user"), open_msg.into())
+        .unwrap();
+
+    let add_collateral_msg = ElysMsg::perpetual_add_collateral("contract_addr", 0, 5, "user");
+
+    app.execute(Addr::unchecked("user"), add_collateral_msg.into())
+        .unwrap();
+
+    assert_eq!(
+        app.wrap()
+            .query_balance("contract_addr", "btc")
+            .unwrap()
+            .amount
+            .u128(),
+        0
+    );
+
+    let last_module_used = app
+        .init_modules(|router, _, store| router.custom.get_last_module(store))
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(last_module_used, "PerpetualAddCollateral");
+
+    let take_profit_price = SignedDecimal256::from_atomics(Uint128::new(11), 1).unwrap();
+    let update_msg =
+        ElysMsg::perpetual_update_take_profit_price("contract_addr", 0, take_profit_price, "user");
+
+    app.execute(Addr::unchecked("user"), update_msg.into())
+        .unwrap();
+
+    let last_module_used = app
+        .init_modules(|router, _, store| router.custom.get_last_module(store))
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(last_module_used, "PerpetualUpdateTakeProfitPrice");
+
+    let req = ElysQuery::mtp("user", 0).into();
+    let mtp = app
+        .wrap()
+        .query::<PerpetualMtpResponse>(&req)
+        .unwrap()
+        .mtp
+        .unwrap();
+
+    assert_eq!(mtp.collateral, Int128::new(10));
+    assert_eq!(
+        mtp.leverage,
+        SignedDecimal::from_atomics(Int64::new(2), 0).unwrap()
+    );
+    assert_eq!(mtp.take_profit_price, take_profit_price);
+}
 #[test]
 fn auth_account() {
     let wallets: Vec<(&str, Vec<Coin>)> =
@@ -14,12 +14,13 @@ pub struct PerpetualAssets {
 pub struct PerpetualAsset {
     pub denom: String,
     pub position: PerpetualPosition,
-    pub pnl: SignedDecimal,
+    // unrealized pnl in USD, None when the price of an asset of the position is missing
+    pub pnl: Option<SignedDecimal>,
     pub collateral: DecCoin,
     pub leverage: SignedDecimal,
     pub size: DecCoin,
     pub order_price: SignedDecimal,
-    pub liquidation: SignedDecimal,
+    pub liquidation: Option<SignedDecimal>,
     pub health: SignedDecimal,
     pub profit_price: DecCoin,
     pub stop_loss: Option<DecCoin>,
@@ -38,7 +39,7 @@ impl PerpetualAsset {
         Ok(PerpetualAsset {
             denom: mtp.mtp.collateral_asset.clone(),
             position: PerpetualPosition::try_from_i32(mtp.mtp.position).unwrap(),
-            pnl: mtp.unrealized_pnl,
+            pnl: mtp.pnl.as_ref().map(|pnl| pnl.unrealized_pnl_usd),
             collateral: DecCoin {
                 denom: mtp.mtp.collateral_asset.clone(),
                 amount: Decimal256::from(
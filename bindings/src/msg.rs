@@ -25,6 +25,18 @@ pub enum ElysMsg {
         amount: Int128,
         owner: String,
     },
+    PerpetualAddCollateral {
+        creator: String,
+        id: u64,
+        amount: Int128,
+        owner: String,
+    },
+    PerpetualUpdateTakeProfitPrice {
+        creator: String,
+        id: u64,
+        price: SignedDecimal256,
+        owner: String,
+    },
     AmmSwapExactAmountIn {
         sender: String,
         routes: Vec<SwapAmountInRoute>,
@@ -171,6 +183,34 @@ impl ElysMsg {
             owner: owner.into(),
         }
     }
+
+    pub fn perpetual_add_collateral(
+        creator: impl Into<String>,
+        id: u64,
+        amount: i128,
+        owner: impl Into<String>,
+    ) -> Self {
+        Self::PerpetualAddCollateral {
+            creator: creator.into(),
+            id,
+            amount: Int128::new(amount),
+            owner: owner.into(),
+        }
+    }
+
+    pub fn perpetual_update_take_profit_price(
+        creator: impl Into<String>,
+        id: u64,
+        price: SignedDecimal256,
+        owner: impl Into<String>,
+    ) -> Self {
+        Self::PerpetualUpdateTakeProfitPrice {
+            creator: creator.into(),
+            id,
+            price,
+            owner: owner.into(),
+        }
+    }
     pub fn swap_by_denom(
         sender: impl Into<String>,
         amount: Coin,
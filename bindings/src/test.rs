@@ -1,4 +1,5 @@
 use crate::types::PageRequest;
+use cosmwasm_std::to_json_binary;
 
 #[test]
 fn pagination_filter_test() {
@@ -30,3 +31,32 @@ fn pagination_filter_test() {
     assert_eq!(res, last);
     assert!(p_res.next_key.is_none());
 }
+
+#[test]
+fn pagination_page_zero_limit_test() {
+    let pagination = PageRequest::new(0);
+
+    let entries = vec![(0u64, 1), (1, 2)];
+
+    assert!(pagination.page(entries.into_iter(), None).is_err());
+}
+
+#[test]
+fn pagination_page_offset_with_key_test() {
+    let entries = vec![(0u64, 1), (1, 2), (2, 3), (3, 4), (4, 5)];
+
+    let mut pagination = PageRequest::new(2);
+    pagination.offset = Some(1);
+
+    let (res, p_res) = pagination.page(entries.clone().into_iter(), None).unwrap();
+
+    assert_eq!(res, vec![2, 3]);
+    assert_eq!(p_res.next_key, Some(to_json_binary(&3u64).unwrap()));
+
+    // the entries are ranged from the key, the offset is not applied a second time
+    pagination.update(p_res.next_key);
+    let (res, p_res) = pagination.page(entries.into_iter().skip(3), None).unwrap();
+
+    assert_eq!(res, vec![4, 5]);
+    assert!(p_res.next_key.is_none());
+}
@@ -1,9 +1,11 @@
 use crate::trade_shield::types::{
-    OrderPrice, PerpetualOrderType, PerpetualPosition, SpotOrderType, SwapAmountInRoute,
+    CloseSize, OrderPrice, PauseFlags, PerpetualOrderType, PerpetualPosition, SpotOrderType,
+    SwapAmountInRoute, TradingPair, TrailingDistance,
 };
 use crate::types::EarnType;
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Coin, Int128, SignedDecimal, SignedDecimal256, Uint128};
+use cosmwasm_std::{Coin, Decimal, Int128, SignedDecimal, SignedDecimal256, Uint128};
+use cw_utils::{Duration, Expiration};
 
 #[cw_serde]
 pub enum ExecuteMsg {
@@ -11,7 +13,27 @@ pub enum ExecuteMsg {
         order_type: SpotOrderType,
         order_source_denom: String,
         order_target_denom: String,
-        order_price: Option<OrderPrice>,
+        order_price: Option<OrderPrice>, // Can be null if it's a MarketBuy type, not used by TrailingStop types
+        trailing_distance: Option<TrailingDistance>, // Can be null if it's not a TrailingStop type
+        linked_order_id: Option<u64>, // Pending spot order canceled when this one executes, and the other way around
+        expiration: Option<Expiration>, // The order is canceled and refunded once expired
+        max_slippage: Option<Decimal>, // Can be null if it's a MarketBuy type, 1% when not set
+        min_out: Option<Uint128>,     // Can be null if it's not a MarketBuy type
+    },
+    CreateTwapOrder {
+        order_source_denom: String,
+        order_target_denom: String,
+        slices: u64,        // Number of swaps the deposited amount is split into
+        interval: Duration, // Blocks or seconds between two swaps
+        max_slippage: Option<Decimal>, // Tolerated shortfall of each swap from its estimation, 1% when not set
+        expiration: Option<Expiration>, // The remaining amount is refunded once expired
+    },
+    UpdateSpotOrder {
+        order_id: u64,
+        order_price: Option<OrderPrice>, // Can be null if it's not updated, not used by TrailingStop and Twap types
+        order_amount: Option<Uint128>, // New total amount, the difference is sent with the message or refunded
+        max_slippage: Option<Decimal>, // Can be null if it's not updated
+        expiration: Option<Expiration>, // Can be null if it's not updated
     },
     CancelSpotOrder {
         order_id: u64,
@@ -27,8 +49,32 @@ pub enum ExecuteMsg {
         trading_asset: Option<String>,   // Can be null if it's not a LimitOpen or MarketOpen type
         take_profit_price: Option<SignedDecimal256>, // Can be null if it's not a LimitOpen or MarketOpen type
         order_type: PerpetualOrderType,
-        trigger_price: Option<OrderPrice>, // Can be null if it's a MarketOpen or MarketClose type
-        position_id: Option<u64>, // Can be null if it's not a LimitClose, MarketClose or StopLoss type
+        trigger_price: Option<OrderPrice>, // Can be null if it's a MarketOpen, MarketClose or TrailingStop type
+        position_id: Option<u64>, // Can be null if it's not a LimitClose, MarketClose, StopLoss or TrailingStop type
+        trailing_distance: Option<TrailingDistance>, // Can be null if it's not a TrailingStop type
+        linked_order_id: Option<u64>, // Pending perpetual order canceled when this one executes, and the other way around
+        expiration: Option<Expiration>, // The order is canceled and refunded once expired
+        close_size: Option<CloseSize>, // Can be null to close the whole position, only used by LimitClose, MarketClose, StopLoss and HealthMonitor types
+        health_threshold: Option<SignedDecimal>, // Can be null if it's not a HealthMonitor type
+    },
+    CreatePerpetualBracketOrder {
+        position: PerpetualPosition,
+        leverage: SignedDecimal,
+        trading_asset: String,
+        take_profit_price: Option<SignedDecimal256>,
+        order_type: PerpetualOrderType,    // LimitOpen or MarketOpen
+        trigger_price: Option<OrderPrice>, // Can be null if it's a MarketOpen type
+        take_profit_trigger_price: OrderPrice, // Trigger price of the LimitClose child order
+        stop_loss_trigger_price: OrderPrice, // Trigger price of the StopLoss child order
+        expiration: Option<Expiration>,    // Only applies to the open order
+    },
+    UpdatePerpetualOrder {
+        order_id: u64,
+        trigger_price: Option<OrderPrice>, // Can be null if it's not updated, not used by TrailingStop type
+        collateral: Option<Uint128>, // New collateral amount of a LimitOpen order, the difference is sent with the message or refunded
+        leverage: Option<SignedDecimal>, // Can be null if it's not updated, only used by LimitOpen type
+        take_profit_price: Option<SignedDecimal256>, // Can be null if it's not updated, only used by LimitOpen type
+        expiration: Option<Expiration>,              // Can be null if it's not updated
     },
     CancelPerpetualOrder {
         order_id: u64,
@@ -42,6 +88,13 @@ pub enum ExecuteMsg {
         id: u64,
         amount: Int128,
     },
+    AddPerpetualPositionCollateral {
+        id: u64, // The collateral is sent with the message in the collateral denom of the position
+    },
+    UpdatePerpetualPositionTakeProfitPrice {
+        id: u64,
+        take_profit_price: SignedDecimal256,
+    },
     StakeRequest {
         amount: u64,
         asset: String,
@@ -103,4 +156,19 @@ pub enum ExecuteMsg {
         position_id: u64,
         amount: Int128,
     },
+    UpdateConfig {
+        admin: Option<String>,                   // Can be null if it's not updated
+        account_history_address: Option<String>, // Can be null if it's not updated
+        allowed_pairs: Option<Vec<TradingPair>>, // Can be null if it's not updated, any pair is allowed when empty
+        min_order_amounts: Option<Vec<Coin>>,    // Can be null if it's not updated
+        max_order_amounts: Option<Vec<Coin>>,    // Can be null if it's not updated
+        max_leverage: Option<SignedDecimal>,     // Can be null if it's not updated
+        pause: Option<PauseFlags>,               // Can be null if it's not updated
+        fee_bps: Option<u64>,                    // Can be null if it's not updated
+        fee_collector: Option<String>,           // Can be null if it's not updated
+        allowed_denoms: Option<Vec<String>>, // Can be null if it's not updated, any denom is allowed when empty
+        validate_assets: Option<bool>,       // Can be null if it's not updated
+        process_order_limit: Option<u64>,    // Can be null if it's not updated
+    },
+    WithdrawProtocolFees {},
 }
@@ -2,5 +2,7 @@ use cosmwasm_schema::cw_serde;
 
 #[cw_serde]
 pub struct InstantiateMsg {
+    pub admin: Option<String>, // Can update the config, the sender when not set
     pub account_history_address: Option<String>,
+    pub process_order_limit: Option<u64>, // max number of spot and perpetual orders processed per block
 }
@@ -2,5 +2,7 @@ use cosmwasm_schema::cw_serde;
 
 #[cw_serde]
 pub struct MigrateMsg {
+    pub admin: Option<String>, // Admin of the config created by the migration, the contract admin when not set
     pub account_history_address: Option<String>,
+    pub process_order_limit: Option<u64>, // max number of spot and perpetual orders processed per block
 }
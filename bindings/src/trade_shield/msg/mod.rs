@@ -13,21 +13,27 @@ pub use reply_type::ReplyType;
 pub use sudo_msg::SudoMsg;
 
 pub mod query_resp {
+    mod config_resp;
     mod get_all_prices_resp;
+    mod get_order_history_resp;
     mod get_perpetual_order_resp;
     mod get_perpetual_orders_resp;
     mod get_perpetual_position_resp;
     mod get_perpetual_positions_for_address_resp;
     mod get_perpetual_positions_resp;
+    mod get_protocol_fees_resp;
     mod get_spot_order_resp;
     mod get_spot_orders_resp;
 
+    pub use config_resp::ConfigResp;
     pub use get_all_prices_resp::GetAllPricesResponse;
+    pub use get_order_history_resp::GetOrderHistoryResp;
     pub use get_perpetual_order_resp::GetPerpetualOrderResp;
     pub use get_perpetual_orders_resp::GetPerpetualOrdersResp;
     pub use get_perpetual_position_resp::GetPerpetualPositionResp;
     pub use get_perpetual_positions_for_address_resp::GetPerpetualPositionsForAddressResp;
     pub use get_perpetual_positions_resp::GetPerpetualPositionsResp;
+    pub use get_protocol_fees_resp::GetProtocolFeesResp;
     pub use get_spot_order_resp::GetSpotOrderResp;
     pub use get_spot_orders_resp::GetSpotOrdersResp;
 }
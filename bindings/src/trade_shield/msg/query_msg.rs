@@ -2,7 +2,7 @@
 use super::query_resp::*;
 #[allow(unused_imports)]
 use crate::query_resp::*;
-use crate::trade_shield::types::{PerpetualOrderType, SpotOrderType, Status};
+use crate::trade_shield::types::{OrderKind, PerpetualOrderType, SpotOrderType, Status};
 use crate::types::{PageRequest, PerpetualPosition};
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Coin, SignedDecimal, SignedDecimal256};
@@ -57,4 +57,14 @@ pub enum QueryMsg {
         address: String,
         pagination: Option<PageRequest>,
     },
+    #[returns(GetOrderHistoryResp)]
+    GetOrderHistory {
+        order_id: u64,
+        order_kind: OrderKind,
+        pagination: Option<PageRequest>,
+    },
+    #[returns(ConfigResp)]
+    Config {},
+    #[returns(GetProtocolFeesResp)]
+    GetProtocolFees {},
 }
@@ -0,0 +1,8 @@
+use crate::trade_shield::types::Config;
+
+use cosmwasm_schema::cw_serde;
+
+#[cw_serde]
+pub struct ConfigResp {
+    pub config: Config,
+}
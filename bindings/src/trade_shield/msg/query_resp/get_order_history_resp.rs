@@ -0,0 +1,9 @@
+use crate::trade_shield::types::{OrderHistoryEntry, PageResponse};
+
+use cosmwasm_schema::cw_serde;
+
+#[cw_serde]
+pub struct GetOrderHistoryResp {
+    pub page_response: Option<PageResponse>,
+    pub history: Vec<OrderHistoryEntry>,
+}
@@ -8,4 +8,5 @@ pub enum ReplyType {
     PerpetualBrokerMarketOpen,
     PerpetualBrokerMarketClose,
     SpotOrderMarketBuy,
+    PerpetualBrokerAddCollateral,
 }
@@ -1,3 +1,4 @@
 use cw_storage_plus::Item;
 
+// replaced by the config, only read to migrate older contracts
 pub const ACCOUNT_HISTORY_ADDRESS: Item<Option<String>> = Item::new("account_history_address");
@@ -0,0 +1,4 @@
+use crate::trade_shield::types::Config;
+use cw_storage_plus::Item;
+
+pub const CONFIG: Item<Config> = Item::new("config");
@@ -1,9 +1,29 @@
 mod account_history_address;
+mod config;
+mod order_book;
+mod order_history;
+mod order_index_version;
 mod perpetual_order;
+mod process_order_pagination;
+mod protocol_fees;
 mod reply_info;
 mod spot_order;
 
 pub use account_history_address::ACCOUNT_HISTORY_ADDRESS;
-pub use perpetual_order::{PENDING_PERPETUAL_ORDER, PERPETUAL_ORDER};
+pub use config::CONFIG;
+pub use order_book::{book_orders, crossed_orders, order_books, OrderBookKey};
+pub use order_history::{PERPETUAL_ORDER_HISTORY, SPOT_ORDER_HISTORY};
+pub use order_index_version::{LATEST_ORDER_INDEX_VERSION, ORDER_INDEX_VERSION};
+pub use perpetual_order::{
+    perpetual_order_book_key, PendingPerpetualOrderIndexes, PerpetualOrderIndexes,
+    PENDING_PERPETUAL_ORDER, PERPETUAL_ORDER, PERPETUAL_ORDER_MAX_ID,
+};
+pub use process_order_pagination::{
+    PROCESS_PERPETUAL_ORDER_PAGINATION, PROCESS_SPOT_ORDER_PAGINATION,
+};
+pub use protocol_fees::PROTOCOL_FEES;
 pub use reply_info::{MAX_REPLY_ID, REPLY_INFO};
-pub use spot_order::{PENDING_SPOT_ORDER, SPOT_ORDER, SPOT_ORDER_MAX_ID};
+pub use spot_order::{
+    spot_order_book_key, PendingSpotOrderIndexes, SpotOrderIndexes, PENDING_SPOT_ORDER, SPOT_ORDER,
+    SPOT_ORDER_MAX_ID,
+};
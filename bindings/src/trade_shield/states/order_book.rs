@@ -0,0 +1,96 @@
+use cosmwasm_schema::serde::{de::DeserializeOwned, Serialize};
+use cosmwasm_std::{Decimal, Order, StdResult, Storage};
+use cw_storage_plus::{Bound, MultiIndex};
+
+// (pair, book, trigger rate atomics), a book groups the orders of a pair triggered the same way
+pub type OrderBookKey = (String, String, u128);
+
+pub fn order_book_key(
+    denom_in: &str,
+    denom_out: &str,
+    book: impl Into<String>,
+    rate: Decimal,
+) -> OrderBookKey {
+    // a space can't be part of a denom
+    (
+        format!("{} {}", denom_in, denom_out),
+        book.into(),
+        rate.atomics().u128(),
+    )
+}
+
+// first order of each book, used to get the pair and the book of the orders to scan
+pub fn order_books<T>(
+    storage: &dyn Storage,
+    index: &MultiIndex<'static, OrderBookKey, T, u64>,
+    key_fn: fn(&[u8], &T) -> OrderBookKey,
+) -> StdResult<Vec<T>>
+where
+    T: Serialize + DeserializeOwned + Clone,
+{
+    let mut books: Vec<T> = vec![];
+    let mut start: Option<Bound<(OrderBookKey, u64)>> = None;
+
+    while let Some(res) = index.range(storage, start, None, Order::Ascending).next() {
+        let (_, order) = res?;
+        let (pair, book, _) = key_fn(&[], &order);
+        start = Some(Bound::exclusive(((pair, book, u128::MAX), u64::MAX)));
+        books.push(order);
+    }
+
+    Ok(books)
+}
+
+// every order of a book, used to move the trailing stops along with the market
+pub fn book_orders<T>(
+    storage: &dyn Storage,
+    index: &MultiIndex<'static, OrderBookKey, T, u64>,
+    (pair, book, _): OrderBookKey,
+) -> StdResult<Vec<T>>
+where
+    T: Serialize + DeserializeOwned + Clone,
+{
+    index
+        .sub_prefix((pair, book))
+        .range(storage, None, None, Order::Ascending)
+        .map(|res| res.map(|(_, order)| order))
+        .collect()
+}
+
+// orders of a book whose trigger rate has been crossed by the market rate,
+// `rate_below` selects the orders with a rate lower or equal to the market rate
+pub fn crossed_orders<T>(
+    storage: &dyn Storage,
+    index: &MultiIndex<'static, OrderBookKey, T, u64>,
+    (pair, book, _): OrderBookKey,
+    market_rate: Decimal,
+    rate_below: bool,
+    limit: usize,
+) -> StdResult<Vec<T>>
+where
+    T: Serialize + DeserializeOwned + Clone,
+{
+    let market_rate = market_rate.atomics().u128();
+    let prefix = index.sub_prefix((pair, book));
+
+    let orders = if rate_below {
+        prefix.range(
+            storage,
+            None,
+            Some(Bound::inclusive((market_rate, u64::MAX))),
+            Order::Ascending,
+        )
+    } else {
+        prefix.range(
+            storage,
+            Some(Bound::inclusive((market_rate, 0))),
+            None,
+            Order::Descending,
+        )
+    };
+
+    orders
+        .take(limit)
+        .map(|res| res.map(|(_, order)| order))
+        .collect()
+}
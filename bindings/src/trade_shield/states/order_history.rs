@@ -0,0 +1,8 @@
+use crate::trade_shield::types::OrderHistoryEntry;
+use cw_storage_plus::Map;
+
+// the entries of an order are keyed by its id and their position in its history
+pub const SPOT_ORDER_HISTORY: Map<(u64, u64), OrderHistoryEntry> = Map::new("spot order history");
+
+pub const PERPETUAL_ORDER_HISTORY: Map<(u64, u64), OrderHistoryEntry> =
+    Map::new("perpetual order history");
@@ -0,0 +1,7 @@
+use cw_storage_plus::Item;
+
+// version of the order indexes stored, the migration rebuilds them only when it is behind
+pub const ORDER_INDEX_VERSION: Item<u64> = Item::new("order index version");
+
+// bumped whenever an index is added to the orders
+pub const LATEST_ORDER_INDEX_VERSION: u64 = 3;
@@ -1,6 +1,85 @@
 use crate::trade_shield::types::PerpetualOrder;
-use cw_storage_plus::Map;
+use cosmwasm_std::Decimal;
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, MultiIndex};
 
-pub const PERPETUAL_ORDER: Map<u64, PerpetualOrder> = Map::new("perpetual order2");
+use super::order_book::{order_book_key, OrderBookKey};
 
-pub const PENDING_PERPETUAL_ORDER: Map<u64, PerpetualOrder> = Map::new("unprocess perpetual order");
+pub struct PerpetualOrderIndexes<'a> {
+    pub owner: MultiIndex<'a, String, PerpetualOrder, u64>,
+    pub status: MultiIndex<'a, String, PerpetualOrder, u64>,
+    pub parent: MultiIndex<'a, u64, PerpetualOrder, u64>,
+}
+
+impl<'a> IndexList<PerpetualOrder> for PerpetualOrderIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<PerpetualOrder>> + '_> {
+        let v: Vec<&dyn Index<PerpetualOrder>> = vec![&self.owner, &self.status, &self.parent];
+        Box::new(v.into_iter())
+    }
+}
+
+pub const PERPETUAL_ORDER: IndexedMap<u64, PerpetualOrder, PerpetualOrderIndexes> = IndexedMap::new(
+    "perpetual order2",
+    PerpetualOrderIndexes {
+        owner: MultiIndex::new(
+            |_pk, order| order.owner.to_owned(),
+            "perpetual order2",
+            "perpetual order2__owner",
+        ),
+        status: MultiIndex::new(
+            |_pk, order| order.status.key(),
+            "perpetual order2",
+            "perpetual order2__status",
+        ),
+        // an order without parent is indexed under its own id, its children are the other
+        // orders under that id
+        parent: MultiIndex::new(
+            |_pk, order| order.parent_order_id.unwrap_or(order.order_id),
+            "perpetual order2",
+            "perpetual order2__parent",
+        ),
+    },
+);
+
+pub const PERPETUAL_ORDER_MAX_ID: Item<u64> = Item::new("perpetual order max id");
+
+pub struct PendingPerpetualOrderIndexes<'a> {
+    pub order_book: MultiIndex<'a, OrderBookKey, PerpetualOrder, u64>,
+    pub group: MultiIndex<'a, u64, PerpetualOrder, u64>,
+}
+
+impl<'a> IndexList<PerpetualOrder> for PendingPerpetualOrderIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<PerpetualOrder>> + '_> {
+        let v: Vec<&dyn Index<PerpetualOrder>> = vec![&self.order_book, &self.group];
+        Box::new(v.into_iter())
+    }
+}
+
+pub fn perpetual_order_book_key(_pk: &[u8], order: &PerpetualOrder) -> OrderBookKey {
+    order_book_key(
+        &order.collateral.denom,
+        &order.trading_asset,
+        format!("{:?} {:?}", order.order_type, order.position),
+        match &order.trigger_price {
+            Some(trigger_price) => trigger_price.rate,
+            None => Decimal::zero(),
+        },
+    )
+}
+
+pub const PENDING_PERPETUAL_ORDER: IndexedMap<u64, PerpetualOrder, PendingPerpetualOrderIndexes> =
+    IndexedMap::new(
+        "unprocess perpetual order",
+        PendingPerpetualOrderIndexes {
+            order_book: MultiIndex::new(
+                perpetual_order_book_key,
+                "unprocess perpetual order",
+                "unprocess perpetual order__order_book",
+            ),
+            // an order without group is indexed under its own id
+            group: MultiIndex::new(
+                |_pk, order| order.group_id.unwrap_or(order.order_id),
+                "unprocess perpetual order",
+                "unprocess perpetual order__group",
+            ),
+        },
+    );
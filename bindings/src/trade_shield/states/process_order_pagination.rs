@@ -0,0 +1,9 @@
+use crate::types::PageRequest;
+use cw_storage_plus::Item;
+
+// cursors of the pending orders processed at the end of each block
+pub const PROCESS_SPOT_ORDER_PAGINATION: Item<PageRequest> =
+    Item::new("process spot order pagination");
+
+pub const PROCESS_PERPETUAL_ORDER_PAGINATION: Item<PageRequest> =
+    Item::new("process perpetual order pagination");
@@ -0,0 +1,5 @@
+use cosmwasm_std::Uint128;
+use cw_storage_plus::Map;
+
+// fees collected on the executed orders, by denom, until withdrawn by the fee collector
+pub const PROTOCOL_FEES: Map<&str, Uint128> = Map::new("protocol fees");
@@ -1,8 +1,81 @@
 use crate::trade_shield::types::SpotOrder;
-use cw_storage_plus::{Item, Map};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, MultiIndex};
 
-pub const SPOT_ORDER: Map<u64, SpotOrder> = Map::new("spot order");
+use super::order_book::{order_book_key, OrderBookKey};
+
+pub struct SpotOrderIndexes<'a> {
+    pub owner: MultiIndex<'a, String, SpotOrder, u64>,
+    pub status: MultiIndex<'a, String, SpotOrder, u64>,
+}
+
+impl<'a> IndexList<SpotOrder> for SpotOrderIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<SpotOrder>> + '_> {
+        let v: Vec<&dyn Index<SpotOrder>> = vec![&self.owner, &self.status];
+        Box::new(v.into_iter())
+    }
+}
+
+pub const SPOT_ORDER: IndexedMap<u64, SpotOrder, SpotOrderIndexes> = IndexedMap::new(
+    "spot order",
+    SpotOrderIndexes {
+        owner: MultiIndex::new(
+            |_pk, order| order.owner_address.to_string(),
+            "spot order",
+            "spot order__owner",
+        ),
+        status: MultiIndex::new(
+            |_pk, order| order.status.key(),
+            "spot order",
+            "spot order__status",
+        ),
+    },
+);
 
 pub const SPOT_ORDER_MAX_ID: Item<u64> = Item::new("spot order max id");
 
-pub const PENDING_SPOT_ORDER: Map<u64, SpotOrder> = Map::new("unprocess spot order");
+pub struct PendingSpotOrderIndexes<'a> {
+    pub order_book: MultiIndex<'a, OrderBookKey, SpotOrder, u64>,
+    pub group: MultiIndex<'a, u64, SpotOrder, u64>,
+}
+
+impl<'a> IndexList<SpotOrder> for PendingSpotOrderIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<SpotOrder>> + '_> {
+        let v: Vec<&dyn Index<SpotOrder>> = vec![&self.order_book, &self.group];
+        Box::new(v.into_iter())
+    }
+}
+
+pub fn spot_order_book_key(_pk: &[u8], order: &SpotOrder) -> OrderBookKey {
+    // a twap order waits for a block height or a time instead of a market rate
+    let (book, rate) = match &order.twap_schedule {
+        Some(twap_schedule) => (
+            format!("{:?} {:?}", order.order_type, twap_schedule.interval),
+            twap_schedule.book_rate(),
+        ),
+        None => (format!("{:?}", order.order_type), order.order_price.rate),
+    };
+
+    order_book_key(
+        &order.order_amount.denom,
+        &order.order_target_denom,
+        book,
+        rate,
+    )
+}
+
+pub const PENDING_SPOT_ORDER: IndexedMap<u64, SpotOrder, PendingSpotOrderIndexes> = IndexedMap::new(
+    "unprocess spot order",
+    PendingSpotOrderIndexes {
+        order_book: MultiIndex::new(
+            spot_order_book_key,
+            "unprocess spot order",
+            "unprocess spot order__order_book",
+        ),
+        // an order without group is indexed under its own id
+        group: MultiIndex::new(
+            |_pk, order| order.group_id.unwrap_or(order.order_id),
+            "unprocess spot order",
+            "unprocess spot order__group",
+        ),
+    },
+);
@@ -0,0 +1,25 @@
+use cosmwasm_schema::cw_serde;
+
+#[cw_serde]
+pub enum CancelReason {
+    // canceled by its owner
+    User,
+    Expired,
+    // another order of its group was executed
+    LinkedOrderExecuted { order_id: u64 },
+    // the open order of its bracket was canceled or failed
+    ParentOrderCanceled { order_id: u64 },
+    // the price denoms don't match the order denoms
+    DenomMismatch,
+    PriceUnavailable,
+    SwapEstimationFailed { error: String },
+    // the minimum amount expected from the swap doesn't fit in an amount
+    MinAmountOutFailed { error: String },
+    SwapFailed { error: String },
+    PositionNotFound,
+    // the other close orders of its type already close the whole position
+    CloseSizeExceeded,
+    PositionOpenFailed { error: String },
+    PositionCloseFailed { error: String },
+    CollateralAddFailed { error: String },
+}
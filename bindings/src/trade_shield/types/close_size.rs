@@ -0,0 +1,42 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Decimal, Int128, StdError, StdResult, Uint128};
+
+#[cw_serde]
+pub enum CloseSize {
+    // fraction of the custody of the position, e.g. 0.25 to close a quarter of it
+    Percentage(Decimal),
+    // amount of the custody, the whole position is closed when it holds less
+    Amount(Uint128),
+}
+
+impl CloseSize {
+    pub fn validate(&self) -> StdResult<()> {
+        match self {
+            CloseSize::Percentage(percentage) => {
+                if percentage.is_zero() || percentage > &Decimal::one() {
+                    return Err(StdError::generic_err(
+                        "close_size: The percentage should be between 0 and 1",
+                    ));
+                }
+            }
+            CloseSize::Amount(amount) => {
+                if amount.is_zero() {
+                    return Err(StdError::generic_err(
+                        "close_size: The amount cannot be zero",
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // part of the custody closed by the order
+    pub fn amount(&self, custody: Int128) -> Int128 {
+        let custody_amount = Uint128::new(custody.i128().max(0) as u128);
+        let amount = match self {
+            CloseSize::Percentage(percentage) => custody_amount * *percentage,
+            CloseSize::Amount(amount) => *amount.min(&custody_amount),
+        };
+        Int128::new(amount.u128() as i128)
+    }
+}
@@ -0,0 +1,88 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Coin, SignedDecimal};
+
+#[cw_serde]
+pub struct TradingPair {
+    pub base_denom: String,
+    pub quote_denom: String,
+}
+
+impl TradingPair {
+    // the pair matches the denoms in either order
+    pub fn matches(&self, denom_a: &str, denom_b: &str) -> bool {
+        (self.base_denom == denom_a && self.quote_denom == denom_b)
+            || (self.base_denom == denom_b && self.quote_denom == denom_a)
+    }
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct PauseFlags {
+    pub spot_orders: bool,      // Creation and update of spot orders
+    pub perpetual_orders: bool, // Creation and update of perpetual orders
+    pub process_orders: bool,   // Processing of the pending orders at the end of the blocks
+    pub staking: bool,          // Staking, vesting and reward claim messages
+    pub liquidity: bool,        // Amm pool, swap and leveragelp messages
+}
+
+impl PauseFlags {
+    // every feature paused at once
+    pub fn all() -> Self {
+        Self {
+            spot_orders: true,
+            perpetual_orders: true,
+            process_orders: true,
+            staking: true,
+            liquidity: true,
+        }
+    }
+}
+
+#[cw_serde]
+pub struct Config {
+    pub admin: Addr,
+    pub account_history_address: Option<String>,
+    pub allowed_pairs: Vec<TradingPair>, // Any pair is allowed when empty
+    pub min_order_amounts: Vec<Coin>,    // Smallest amount of an order, per denom
+    pub max_order_amounts: Vec<Coin>,    // Largest amount of an order, per denom
+    pub max_leverage: Option<SignedDecimal>,
+    pub pause: PauseFlags, // Cancellations and position closes are never paused
+    pub fee_bps: u64,      // Protocol fee on executed swaps and opened positions, in basis points
+    pub fee_collector: Option<Addr>, // Withdraws the protocol fees, the admin when not set
+    pub allowed_denoms: Vec<String>, // Any denom is allowed when empty
+    pub validate_assets: bool, // Order denoms must be registered assets with an oracle price
+}
+
+impl Config {
+    pub fn new(admin: Addr, account_history_address: Option<String>) -> Self {
+        Self {
+            admin,
+            account_history_address,
+            allowed_pairs: vec![],
+            min_order_amounts: vec![],
+            max_order_amounts: vec![],
+            max_leverage: None,
+            pause: PauseFlags::default(),
+            fee_bps: 0,
+            fee_collector: None,
+            allowed_denoms: vec![],
+            validate_assets: false,
+        }
+    }
+
+    pub fn fee_collector(&self) -> &Addr {
+        self.fee_collector.as_ref().unwrap_or(&self.admin)
+    }
+
+    pub fn is_denom_allowed(&self, denom: &str) -> bool {
+        self.allowed_denoms.is_empty() || self.allowed_denoms.iter().any(|allowed| allowed == denom)
+    }
+
+    pub fn is_pair_allowed(&self, denom_a: &str, denom_b: &str) -> bool {
+        self.allowed_pairs.is_empty()
+            || self
+                .allowed_pairs
+                .iter()
+                .any(|pair| pair.matches(denom_a, denom_b))
+    }
+}
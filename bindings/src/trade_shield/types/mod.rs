@@ -3,25 +3,41 @@ mod spot_order_type;
 mod spot_order {
     pub mod spot_order;
     mod impls {
+        mod fill;
         mod new;
         #[cfg(feature = "testing")]
         mod new_dummy;
+        mod trailing_stop;
     }
 }
+mod cancel_reason;
+mod close_size;
+mod config;
 mod date;
+mod order_history;
 mod perpetual_order;
 mod perpetual_order_type;
+mod perpetual_pnl;
 mod perpetual_position_plus;
 mod reply_info;
 mod status;
+mod trailing_stop;
+mod twap_schedule;
 
 pub use crate::types::*;
+pub use cancel_reason::CancelReason;
+pub use close_size::CloseSize;
+pub use config::{Config, PauseFlags, TradingPair};
 pub use date::Date;
+pub use order_history::{OrderEvent, OrderHistoryEntry, OrderKind};
 pub use perpetual_order::PerpetualOrder;
 pub use perpetual_order_type::PerpetualOrderType;
+pub use perpetual_pnl::{PerpetualPnl, UsdAssets};
 pub use perpetual_position_plus::PerpetualPositionPlus;
 pub use reply_info::ReplyInfo;
 pub use spot_order::spot_order::SpotOrder;
 pub use spot_order_price::OrderPrice;
 pub use spot_order_type::SpotOrderType;
 pub use status::Status;
+pub use trailing_stop::{TrailingDistance, TrailingStop};
+pub use twap_schedule::TwapSchedule;
@@ -0,0 +1,34 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Binary, Coin, Decimal};
+
+use super::{CancelReason, Date};
+
+#[cw_serde]
+pub enum OrderKind {
+    Spot,
+    Perpetual,
+}
+
+#[cw_serde]
+pub enum OrderEvent {
+    Created,
+    Updated,
+    // the order was sent to the module at this market price
+    Triggered {
+        market_price: Decimal,
+    },
+    Executed {
+        fill_amount: Option<Coin>,
+        // response of the module
+        data: Option<Binary>,
+    },
+    Canceled {
+        reason: CancelReason,
+    },
+}
+
+#[cw_serde]
+pub struct OrderHistoryEntry {
+    pub date: Date,
+    pub event: OrderEvent,
+}
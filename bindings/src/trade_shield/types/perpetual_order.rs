@@ -1,8 +1,10 @@
 use crate::types::PerpetualPosition;
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Coin, OverflowError, SignedDecimal, SignedDecimal256, StdError, StdResult};
+use cosmwasm_std::{Coin, Decimal, Int128, SignedDecimal, SignedDecimal256, StdResult};
 
-use super::{OrderPrice, PerpetualOrderType, Status};
+use cw_utils::Expiration;
+
+use super::{CancelReason, CloseSize, OrderPrice, PerpetualOrderType, Status, TrailingStop};
 
 #[cw_serde]
 pub struct PerpetualOrder {
@@ -17,6 +19,20 @@ pub struct PerpetualOrder {
     pub take_profit_price: Option<SignedDecimal256>,
     pub position_id: Option<u64>,
     pub status: Status,
+    pub trailing_stop: Option<TrailingStop>,
+    pub group_id: Option<u64>,
+    pub parent_order_id: Option<u64>,
+    pub expiration: Option<Expiration>,
+    // why the order ended when its status is canceled
+    pub cancel_reason: Option<CancelReason>,
+    // protocol fee taken from the collateral once the position is opened
+    pub fee: Option<Coin>,
+    // part of the position closed by a close order, the whole position when not set
+    pub close_size: Option<CloseSize>,
+    // health of the position below which a health monitor order is triggered
+    pub health_threshold: Option<SignedDecimal>,
+    // collateral held for a health monitor and added to the position instead of closing it
+    pub escrowed_collateral: Option<Coin>,
 }
 
 impl PerpetualOrder {
@@ -29,12 +45,10 @@ impl PerpetualOrder {
         leverage: &SignedDecimal,
         take_profit_price: &Option<SignedDecimal256>,
         trigger_price: &Option<OrderPrice>,
-        order_vec: &Vec<PerpetualOrder>,
+        order_id: u64,
     ) -> StdResult<Self> {
         let status = Status::Pending;
 
-        let order_id = get_new_id(&order_vec)?;
-
         let order = Self {
             order_id,
             owner: owner.into(),
@@ -47,6 +61,15 @@ impl PerpetualOrder {
             trigger_price: trigger_price.to_owned(),
             status,
             position_id: None,
+            trailing_stop: None,
+            group_id: None,
+            expiration: None,
+            parent_order_id: None,
+            cancel_reason: None,
+            fee: None,
+            close_size: None,
+            health_threshold: None,
+            escrowed_collateral: None,
         };
 
         return Ok(order);
@@ -61,10 +84,8 @@ impl PerpetualOrder {
         position_id: u64,
         trigger_price: &Option<OrderPrice>,
         take_profit_price: &Option<SignedDecimal256>,
-        order_vec: &Vec<PerpetualOrder>,
+        order_id: u64,
     ) -> StdResult<Self> {
-        let order_id: u64 = get_new_id(&order_vec)?;
-
         let status = Status::Pending;
 
         let position = PerpetualPosition::try_from_i32(position)?;
@@ -81,22 +102,109 @@ impl PerpetualOrder {
             position_id: Some(position_id),
             leverage: leverage.to_owned(),
             take_profit_price: take_profit_price.to_owned(),
+            trailing_stop: None,
+            group_id: None,
+            expiration: None,
+            parent_order_id: None,
+            cancel_reason: None,
+            fee: None,
+            close_size: None,
+            health_threshold: None,
+            escrowed_collateral: None,
         };
 
         Ok(order)
     }
-}
 
-fn get_new_id(orders: &[PerpetualOrder]) -> StdResult<u64> {
-    match orders.iter().max_by_key(|s| s.order_id) {
-        Some(order) => match order.order_id.checked_add(1) {
-            Some(id) => Ok(id),
-            None => Err(StdError::overflow(OverflowError::new(
-                cosmwasm_std::OverflowOperation::Add,
-                "perpetual_order_max_id",
-                "increment one",
-            ))),
-        },
-        None => Ok(0),
+    // close order waiting for the position of its parent open order to be opened
+    pub fn new_child(
+        parent: &PerpetualOrder,
+        order_type: &PerpetualOrderType,
+        trigger_price: &OrderPrice,
+        order_id: u64,
+    ) -> StdResult<Self> {
+        let order: PerpetualOrder = Self {
+            order_id,
+            status: Status::Pending,
+            order_type: order_type.to_owned(),
+            position: parent.position.to_owned(),
+            owner: parent.owner.to_owned(),
+            trigger_price: Some(trigger_price.to_owned()),
+            collateral: parent.collateral.to_owned(),
+            trading_asset: parent.trading_asset.to_owned(),
+            position_id: None,
+            leverage: parent.leverage.to_owned(),
+            take_profit_price: parent.take_profit_price.to_owned(),
+            trailing_stop: None,
+            group_id: None,
+            expiration: None,
+            parent_order_id: Some(parent.order_id),
+            cancel_reason: None,
+            fee: None,
+            close_size: None,
+            health_threshold: None,
+            escrowed_collateral: None,
+        };
+
+        Ok(order)
+    }
+
+    pub fn cancel(&mut self, reason: CancelReason) {
+        self.status = Status::Canceled;
+        self.cancel_reason = Some(reason);
+    }
+
+    // funds held by the contract for the order, sent back to the owner when it is canceled
+    pub fn escrow(&self) -> Option<Coin> {
+        match self.order_type {
+            PerpetualOrderType::LimitOpen => Some(self.collateral.clone()),
+            _ => self.escrowed_collateral.clone(),
+        }
+    }
+
+    // amount of the custody of the position closed by the order
+    pub fn close_amount(&self, custody: Int128) -> Int128 {
+        match &self.close_size {
+            Some(close_size) => close_size.amount(custody),
+            None => custody,
+        }
+    }
+
+    pub fn set_trailing_stop(&mut self, trailing_stop: TrailingStop) {
+        if let Some(trigger_price) = self.trigger_price.as_mut() {
+            trigger_price.rate = Self::trailing_stop_price(&self.position, &trailing_stop);
+        }
+        self.trailing_stop = Some(trailing_stop);
+    }
+
+    // move the trigger price along with the market, returns true if the order changed
+    pub fn follow_market(&mut self, market_price: Decimal) -> bool {
+        let trailing_stop = match self.trailing_stop.as_mut() {
+            Some(trailing_stop) => trailing_stop,
+            None => return false,
+        };
+
+        let moved = match self.position {
+            PerpetualPosition::Long => trailing_stop.raise(market_price),
+            PerpetualPosition::Short => trailing_stop.lower(market_price),
+            PerpetualPosition::Unspecified => false,
+        };
+
+        if !moved {
+            return false;
+        }
+
+        let rate = Self::trailing_stop_price(&self.position, trailing_stop);
+        if let Some(trigger_price) = self.trigger_price.as_mut() {
+            trigger_price.rate = rate;
+        }
+        true
+    }
+
+    fn trailing_stop_price(position: &PerpetualPosition, trailing_stop: &TrailingStop) -> Decimal {
+        match position {
+            PerpetualPosition::Short => trailing_stop.stop_price_above(),
+            _ => trailing_stop.stop_price_below(),
+        }
     }
 }
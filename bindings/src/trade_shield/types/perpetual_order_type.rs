@@ -9,4 +9,7 @@ pub enum PerpetualOrderType {
     MarketClose,
 
     StopLoss,
+    TrailingStop,
+    // closes the position once its health drops below a threshold
+    HealthMonitor,
 }
@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Int128, SignedDecimal, StdError, StdResult};
+
+use crate::{types::Mtp, ElysQuerier};
+
+// pnl of a perpetual position and the fees it settled, all valued in USD at the oracle price
+#[cw_serde]
+pub struct PerpetualPnl {
+    // value of the custody minus the liabilities, the unpaid borrow interest and the collateral
+    pub unrealized_pnl_usd: SignedDecimal,
+    // funding fees received minus the funding fees and borrow interest paid, the gains and
+    // losses of the closed parts of the position are not included
+    pub net_fees: SignedDecimal,
+    pub borrow_interest_paid: SignedDecimal,
+    pub borrow_interest_unpaid: SignedDecimal,
+    pub funding_fee_paid: SignedDecimal,
+    pub funding_fee_received: SignedDecimal,
+}
+
+impl PerpetualPnl {
+    pub fn new(mtp: &Mtp, assets: &mut UsdAssets) -> StdResult<Self> {
+        let collateral = &mtp.collateral_asset;
+        let custody = &mtp.custody_asset;
+
+        let borrow_interest_paid = assets
+            .usd_value(mtp.borrow_interest_paid_collateral, collateral)?
+            .checked_add(assets.usd_value(mtp.borrow_interest_paid_custody, custody)?)?;
+        let borrow_interest_unpaid =
+            assets.usd_value(mtp.borrow_interest_unpaid_collateral, collateral)?;
+        let funding_fee_paid = assets
+            .usd_value(mtp.funding_fee_paid_collateral, collateral)?
+            .checked_add(assets.usd_value(mtp.funding_fee_paid_custody, custody)?)?;
+        let funding_fee_received = assets
+            .usd_value(mtp.funding_fee_received_collateral, collateral)?
+            .checked_add(assets.usd_value(mtp.funding_fee_received_custody, custody)?)?;
+
+        let unrealized_pnl_usd = assets
+            .usd_value(mtp.custody, custody)?
+            .checked_sub(assets.usd_value(mtp.liabilities, &mtp.liabilities_asset)?)?
+            .checked_sub(borrow_interest_unpaid)?
+            .checked_sub(assets.usd_value(mtp.collateral, collateral)?)?;
+        let net_fees = funding_fee_received
+            .checked_sub(funding_fee_paid)?
+            .checked_sub(borrow_interest_paid)?;
+
+        Ok(Self {
+            unrealized_pnl_usd,
+            net_fees,
+            borrow_interest_paid,
+            borrow_interest_unpaid,
+            funding_fee_paid,
+            funding_fee_received,
+        })
+    }
+}
+
+// decimals and oracle price of the denoms valued during a query, each denom is queried once
+pub struct UsdAssets<'a> {
+    querier: &'a ElysQuerier<'a>,
+    decimals: HashMap<String, u32>,
+    prices: HashMap<String, SignedDecimal>,
+}
+
+impl<'a> UsdAssets<'a> {
+    pub fn new(querier: &'a ElysQuerier<'a>) -> Self {
+        Self {
+            querier,
+            decimals: HashMap::new(),
+            prices: HashMap::new(),
+        }
+    }
+
+    pub fn decimals(&mut self, denom: &str) -> StdResult<u32> {
+        if let Some(decimals) = self.decimals.get(denom) {
+            return Ok(*decimals);
+        }
+        let decimals = self
+            .querier
+            .asset_info(denom.to_string())?
+            .asset_info
+            .decimal as u32;
+        self.decimals.insert(denom.to_string(), decimals);
+        Ok(decimals)
+    }
+
+    pub fn price(&mut self, denom: &str) -> StdResult<SignedDecimal> {
+        if let Some(price) = self.prices.get(denom) {
+            return Ok(*price);
+        }
+        let price = SignedDecimal::try_from(self.querier.get_asset_price(denom)?)
+            .map_err(|e| StdError::generic_err(format!("{denom}: {e}")))?;
+        self.prices.insert(denom.to_string(), price);
+        Ok(price)
+    }
+
+    // true when the decimals and the price of every denom can be queried
+    pub fn available(&mut self, denoms: &[&str]) -> bool {
+        denoms
+            .iter()
+            .all(|denom| self.decimals(denom).is_ok() && self.price(denom).is_ok())
+    }
+
+    pub fn usd_value(&mut self, amount: Int128, denom: &str) -> StdResult<SignedDecimal> {
+        if amount.is_zero() {
+            return Ok(SignedDecimal::zero());
+        }
+
+        let amount = SignedDecimal::from_atomics(amount, self.decimals(denom)?)
+            .map_err(|e| StdError::generic_err(format!("{denom}: {e}")))?;
+
+        amount
+            .checked_mul(self.price(denom)?)
+            .map_err(|e| StdError::generic_err(format!("{denom}: {e}")))
+    }
+}
@@ -2,15 +2,22 @@ use crate::{types::Mtp, ElysQuerier};
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Order, SignedDecimal, StdError, StdResult, Storage};
 
-use crate::trade_shield::{states::PENDING_PERPETUAL_ORDER, types::PerpetualOrder};
+use crate::trade_shield::{
+    states::PERPETUAL_ORDER,
+    types::{PerpetualOrder, Status},
+};
 
-use super::{OrderPrice, PerpetualOrderType, PerpetualPosition};
+use super::{OrderPrice, PerpetualOrderType, PerpetualPnl, PerpetualPosition, UsdAssets};
 
 #[cw_serde]
 pub struct PerpetualPositionPlus {
     pub mtp: Mtp,
-    pub unrealized_pnl: SignedDecimal,
-    pub liquidation_price: SignedDecimal,
+    // unrealized pnl and fees valued in USD at the oracle price, None when the info or the price
+    // of an asset of the position is missing. The module doesn't report the gains and losses of
+    // the closed parts of a position, so no realized pnl is available besides the settled fees.
+    pub pnl: Option<PerpetualPnl>,
+    // None when the info of an asset of the position is missing
+    pub liquidation_price: Option<SignedDecimal>,
     pub stop_loss_price: Option<OrderPrice>,
 }
 
@@ -20,18 +27,13 @@ impl PerpetualPositionPlus {
         storage: &dyn Storage,
         querier: &ElysQuerier<'_>,
     ) -> StdResult<Vec<Self>> {
-        let stop_loss_prices = Self::get_stop_loss_prices(&mtps, storage);
-
-        if mtps.len() != stop_loss_prices.len() {
-            return Err(StdError::generic_err("parsing stop_loss_prices error"));
-        }
-
         let mut mtps_plus: Vec<PerpetualPositionPlus> = Vec::new();
+        let mut assets = UsdAssets::new(querier);
 
         for i in 0..mtps.len() {
             let mtp = mtps[i].clone();
 
-            let mtp_plus = Self::new(mtp, storage, querier)?;
+            let mtp_plus = Self::with_assets(mtp, storage, &mut assets)?;
 
             mtps_plus.push(mtp_plus)
         }
@@ -40,87 +42,53 @@ impl PerpetualPositionPlus {
     }
 
     pub fn new(mtp: Mtp, storage: &dyn Storage, querier: &ElysQuerier<'_>) -> StdResult<Self> {
-        let collateral_info = querier.asset_info(mtp.collateral_asset.clone())?;
-
-        let collateral_amount = SignedDecimal::from_atomics(
-            mtp.collateral.i128(),
-            collateral_info.asset_info.decimal as u32,
-        )
-        .map_err(|e| StdError::generic_err(e.to_string()))?;
-
-        let custody_amount = SignedDecimal::from_atomics(
-            mtp.custody.i128(),
-            collateral_info.asset_info.decimal as u32,
-        )
-        .map_err(|e| StdError::generic_err(e.to_string()))?;
-
-        let _liabilities_amount = SignedDecimal::from_atomics(
-            mtp.custody.i128(),
-            collateral_info.asset_info.decimal as u32,
-        )
-        .map_err(|e| StdError::generic_err(e.to_string()))?;
-
-        let unrealized_pnl = Self::calc_unrealized_pnl(
-            &mtp,
-            // &custody_amount,
-            // &collateral_amount,
-            // &liabilities_amount,
-        )
-        .map_err(|e| StdError::generic_err(format!("unrealized_pnl: {:?}", e.to_string())))?;
-        let liquidation_price =
-            Self::calc_liquidation_price(&mtp, &collateral_amount, &custody_amount).map_err(
-                |e| StdError::generic_err(format!("liquidation_price: {:?}", e.to_string())),
-            )?;
-        let stop_loss_price = Self::get_stop_loss_price(&mtp, storage);
+        Self::with_assets(mtp, storage, &mut UsdAssets::new(querier))
+    }
+
+    fn with_assets(mtp: Mtp, storage: &dyn Storage, assets: &mut UsdAssets) -> StdResult<Self> {
+        let pnl = if assets.available(&[
+            &mtp.collateral_asset,
+            &mtp.custody_asset,
+            &mtp.liabilities_asset,
+        ]) {
+            Some(
+                PerpetualPnl::new(&mtp, assets)
+                    .map_err(|e| StdError::generic_err(format!("pnl: {}", e)))?,
+            )
+        } else {
+            None
+        };
+
+        let liquidation_price = match (
+            assets.decimals(&mtp.collateral_asset),
+            assets.decimals(&mtp.custody_asset),
+        ) {
+            (Ok(collateral_decimals), Ok(custody_decimals)) => {
+                let collateral_amount =
+                    SignedDecimal::from_atomics(mtp.collateral.i128(), collateral_decimals)
+                        .map_err(|e| StdError::generic_err(e.to_string()))?;
+                let custody_amount =
+                    SignedDecimal::from_atomics(mtp.custody.i128(), custody_decimals)
+                        .map_err(|e| StdError::generic_err(e.to_string()))?;
+                Some(
+                    Self::calc_liquidation_price(&mtp, &collateral_amount, &custody_amount)
+                        .map_err(|e| {
+                            StdError::generic_err(format!("liquidation_price: {:?}", e.to_string()))
+                        })?,
+                )
+            }
+            _ => None,
+        };
+        let stop_loss_price = Self::get_stop_loss_price(&mtp, storage)?;
 
         Ok(Self {
             mtp,
-            unrealized_pnl,
+            pnl,
             liquidation_price,
             stop_loss_price,
         })
     }
 
-    fn calc_unrealized_pnl(mtp: &Mtp) -> StdResult<SignedDecimal> {
-        let custody_amount: SignedDecimal = SignedDecimal::from_atomics(mtp.custody, 0)
-            .map_err(|e| StdError::generic_err(e.to_string()))?;
-        let collateral_amount: SignedDecimal = SignedDecimal::from_atomics(mtp.collateral, 0)
-            .map_err(|e| StdError::generic_err(e.to_string()))?;
-        let liabilities_amount: SignedDecimal = SignedDecimal::from_atomics(mtp.liabilities, 0)
-            .map_err(|e| StdError::generic_err(e.to_string()))?;
-        let take_profit_price = SignedDecimal::try_from(mtp.take_profit_price.clone())
-            .map_err(|e| StdError::generic_err(e.to_string()))?;
-        let open_price = mtp.open_price.clone();
-
-        match PerpetualPosition::try_from_i32(mtp.position)? {
-            //estimated_pnl = custody_amount - (liability_amount + collateral_amount) / take_profit_price
-            PerpetualPosition::Long => custody_amount
-                .checked_sub(
-                    liabilities_amount
-                        .checked_add(collateral_amount.to_owned())?
-                        .checked_div(take_profit_price)
-                        .map_err(|e| StdError::generic_err(e.to_string()))?,
-                )
-                .map_err(|e| StdError::generic_err(e.to_string())),
-
-            // if position is short then estimated pnl is custody_amount / open_price - (liability_amount + collateral_amount) / take_profit_price
-            PerpetualPosition::Short => {
-                liabilities_amount
-                    .checked_add(collateral_amount)?
-                    .checked_div(take_profit_price)
-                    .map_err(|e| {
-                        StdError::generic_err(format!("take_profit_price: {}", e.to_string()))
-                    })?
-                    .checked_sub((custody_amount).checked_div(open_price).map_err(|e| {
-                        StdError::generic_err(format!("open_price: {}", e.to_string()))
-                    })?)
-                    .map_err(|e| StdError::generic_err(e.to_string()))
-            }
-
-            PerpetualPosition::Unspecified => Err(StdError::generic_err("Position is Unspecified")),
-        }
-    }
-
     fn calc_liquidation_price(
         mtp: &Mtp,
         collateral_amount: &SignedDecimal,
@@ -152,41 +120,39 @@ impl PerpetualPositionPlus {
         }
     }
 
-    fn get_stop_loss_price(mtp: &Mtp, storage: &dyn Storage) -> Option<OrderPrice> {
-        let perpetual_order = PENDING_PERPETUAL_ORDER
-            .prefix_range(storage, None, None, Order::Ascending)
-            .filter_map(|res| res.ok().map(|r| r.1))
-            .find(|order| {
-                order.position_id == Some(mtp.id)
-                    && order.order_type == PerpetualOrderType::StopLoss
-            });
-
-        match perpetual_order {
-            Some(order) => order.trigger_price,
-            None => None,
-        }
+    fn get_stop_loss_price(mtp: &Mtp, storage: &dyn Storage) -> StdResult<Option<OrderPrice>> {
+        let perpetual_orders = PERPETUAL_ORDER
+            .idx
+            .owner
+            .prefix(mtp.address.to_string())
+            .range(storage, None, None, Order::Ascending)
+            .map(|res| res.map(|r| r.1))
+            .filter(|res| match res {
+                Ok(order) => order.status == Status::Pending,
+                Err(_) => true,
+            })
+            .collect::<StdResult<Vec<PerpetualOrder>>>()?;
+
+        Ok(Self::find_stop_loss_price(mtp, &perpetual_orders))
     }
 
-    fn get_stop_loss_prices(mtps: &Vec<Mtp>, storage: &dyn Storage) -> Vec<Option<OrderPrice>> {
-        let perpetual_orders: Vec<PerpetualOrder> = PENDING_PERPETUAL_ORDER
-            .prefix_range(storage, None, None, Order::Ascending)
-            .filter_map(|res| res.ok().map(|r| r.1))
-            .collect();
-
-        let mut stop_loss_prices: Vec<Option<OrderPrice>> = Vec::new();
-
-        for Mtp { id, .. } in mtps {
-            let price = match perpetual_orders
-                .iter()
-                .find(|order| order.position_id == Some(*id))
-            {
-                Some(order) => order.trigger_price.to_owned(),
-                None => None,
-            };
-
-            stop_loss_prices.push(price)
+    // trigger price of the stop loss or trailing stop of the position which triggers first,
+    // the highest one for a long position and the lowest one for a short position
+    fn find_stop_loss_price(mtp: &Mtp, perpetual_orders: &[PerpetualOrder]) -> Option<OrderPrice> {
+        let stop_loss_prices = perpetual_orders
+            .iter()
+            .filter(|order| {
+                order.owner == mtp.address
+                    && order.position_id == Some(mtp.id)
+                    && (order.order_type == PerpetualOrderType::StopLoss
+                        || order.order_type == PerpetualOrderType::TrailingStop)
+            })
+            .filter_map(|order| order.trigger_price.to_owned());
+
+        if mtp.position == PerpetualPosition::Short as i32 {
+            stop_loss_prices.min_by(|a, b| a.rate.cmp(&b.rate))
+        } else {
+            stop_loss_prices.max_by(|a, b| a.rate.cmp(&b.rate))
         }
-
-        stop_loss_prices
     }
 }
@@ -0,0 +1,49 @@
+use crate::trade_shield::types::*;
+use cosmwasm_std::{Coin, Uint128};
+
+impl SpotOrder {
+    // part of the order amount still held by the contract
+    pub fn remaining_amount(&self) -> Coin {
+        Coin {
+            denom: self.order_amount.denom.clone(),
+            amount: self
+                .order_amount
+                .amount
+                .saturating_sub(self.filled_amount.unwrap_or_default()),
+        }
+    }
+
+    // record an executed slice of the order, the order is executed once nothing remains
+    pub fn fill(&mut self, amount: Uint128) {
+        self.filled_amount = Some(self.filled_amount.unwrap_or_default() + amount);
+        self.status = if self.remaining_amount().amount.is_zero() {
+            Status::Executed
+        } else {
+            Status::PartiallyFilled
+        };
+    }
+
+    // the order still waits to be executed, partially filled orders keep their remaining amount pending
+    pub fn is_pending(&self) -> bool {
+        self.status == Status::Pending || self.status == Status::PartiallyFilled
+    }
+
+    // the fee of every executed slice adds up
+    pub fn pay_fee(&mut self, fee: &Coin) {
+        if fee.amount.is_zero() {
+            return;
+        }
+        self.fee = Some(match &self.fee {
+            Some(paid) => Coin {
+                denom: fee.denom.clone(),
+                amount: paid.amount + fee.amount,
+            },
+            None => fee.clone(),
+        });
+    }
+
+    pub fn cancel(&mut self, reason: CancelReason) {
+        self.status = Status::Canceled;
+        self.cancel_reason = Some(reason);
+    }
+}
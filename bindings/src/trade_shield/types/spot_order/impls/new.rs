@@ -31,6 +31,14 @@ impl SpotOrder {
             order_target_denom,
             status,
             date: Date::from(block_info),
+            trailing_stop: None,
+            group_id: None,
+            expiration: None,
+            max_slippage: None,
+            filled_amount: None,
+            twap_schedule: None,
+            cancel_reason: None,
+            fee: None,
         }
     }
 }
@@ -19,6 +19,14 @@ impl SpotOrder {
                 height: 12,
                 time: Timestamp::from_nanos(500000),
             },
+            trailing_stop: None,
+            group_id: None,
+            expiration: None,
+            max_slippage: None,
+            filled_amount: None,
+            twap_schedule: None,
+            cancel_reason: None,
+            fee: None,
         }
     }
 }
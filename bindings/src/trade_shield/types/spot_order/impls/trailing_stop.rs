@@ -0,0 +1,24 @@
+use crate::trade_shield::types::*;
+use cosmwasm_std::Decimal;
+
+impl SpotOrder {
+    pub fn set_trailing_stop(&mut self, trailing_stop: TrailingStop) {
+        self.order_price.rate = trailing_stop.stop_price_below();
+        self.trailing_stop = Some(trailing_stop);
+    }
+
+    // move the trigger price along with the market, returns true if the order changed
+    pub fn follow_market(&mut self, market_price: Decimal) -> bool {
+        let trailing_stop = match self.trailing_stop.as_mut() {
+            Some(trailing_stop) => trailing_stop,
+            None => return false,
+        };
+
+        if !trailing_stop.raise(market_price) {
+            return false;
+        }
+
+        self.order_price.rate = trailing_stop.stop_price_below();
+        true
+    }
+}
@@ -1,6 +1,10 @@
-use crate::trade_shield::types::{spot_order_type::SpotOrderType, Date, OrderPrice, Status};
+use crate::trade_shield::types::{
+    spot_order_type::SpotOrderType, CancelReason, Date, OrderPrice, Status, TrailingStop,
+    TwapSchedule,
+};
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Coin};
+use cosmwasm_std::{Addr, Coin, Decimal, Uint128};
+use cw_utils::Expiration;
 
 #[cw_serde]
 pub struct SpotOrder {
@@ -12,4 +16,16 @@ pub struct SpotOrder {
     pub order_target_denom: String,
     pub status: Status,
     pub date: Date,
+    pub trailing_stop: Option<TrailingStop>,
+    pub group_id: Option<u64>,
+    pub expiration: Option<Expiration>,
+    // tolerated shortfall from the order rate when the order is executed
+    pub max_slippage: Option<Decimal>,
+    // part of the order amount already swapped
+    pub filled_amount: Option<Uint128>,
+    pub twap_schedule: Option<TwapSchedule>,
+    // why the order ended when its status is canceled
+    pub cancel_reason: Option<CancelReason>,
+    // protocol fee taken from the executed amount
+    pub fee: Option<Coin>,
 }
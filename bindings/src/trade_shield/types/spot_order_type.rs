@@ -6,4 +6,6 @@ pub enum SpotOrderType {
     LimitSell,
     LimitBuy,
     MarketBuy,
+    TrailingStop,
+    Twap,
 }
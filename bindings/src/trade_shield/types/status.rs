@@ -3,6 +3,14 @@ use cosmwasm_schema::cw_serde;
 #[cw_serde]
 pub enum Status {
     Pending,
+    PartiallyFilled,
     Executed,
     Canceled,
 }
+
+impl Status {
+    // key of the status in the order indexes
+    pub fn key(&self) -> String {
+        format!("{:?}", self)
+    }
+}
@@ -0,0 +1,85 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Decimal, StdError, StdResult};
+
+#[cw_serde]
+pub enum TrailingDistance {
+    // fraction of the best price, e.g. 0.05 for a stop trailing 5% behind the market
+    Percentage(Decimal),
+    // fixed offset expressed in the same unit as the order price rate
+    Absolute(Decimal),
+}
+
+impl TrailingDistance {
+    pub fn validate(&self) -> StdResult<()> {
+        match self {
+            TrailingDistance::Percentage(percentage) => {
+                if percentage.is_zero() || percentage >= &Decimal::one() {
+                    return Err(StdError::generic_err(
+                        "trailing_distance: The percentage should be between 0 and 1",
+                    ));
+                }
+            }
+            TrailingDistance::Absolute(offset) => {
+                if offset.is_zero() {
+                    return Err(StdError::generic_err(
+                        "trailing_distance: The offset cannot be zero",
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn offset(&self, price: Decimal) -> Decimal {
+        match self {
+            TrailingDistance::Percentage(percentage) => price * percentage,
+            TrailingDistance::Absolute(offset) => offset.to_owned(),
+        }
+    }
+}
+
+#[cw_serde]
+pub struct TrailingStop {
+    pub distance: TrailingDistance,
+    // best market price reached since the order was created
+    pub best_price: Decimal,
+}
+
+impl TrailingStop {
+    pub fn new(distance: TrailingDistance, market_price: Decimal) -> Self {
+        Self {
+            distance,
+            best_price: market_price,
+        }
+    }
+
+    // stop price for an order protecting against a fall of the market price
+    pub fn stop_price_below(&self) -> Decimal {
+        self.best_price
+            .saturating_sub(self.distance.offset(self.best_price))
+    }
+
+    // stop price for an order protecting against a rise of the market price
+    pub fn stop_price_above(&self) -> Decimal {
+        self.best_price
+            .saturating_add(self.distance.offset(self.best_price))
+    }
+
+    // move the high-water mark up, returns true if the stop price changed
+    pub fn raise(&mut self, market_price: Decimal) -> bool {
+        if market_price <= self.best_price {
+            return false;
+        }
+        self.best_price = market_price;
+        true
+    }
+
+    // move the low-water mark down, returns true if the stop price changed
+    pub fn lower(&mut self, market_price: Decimal) -> bool {
+        if market_price >= self.best_price {
+            return false;
+        }
+        self.best_price = market_price;
+        true
+    }
+}
@@ -0,0 +1,87 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{BlockInfo, Decimal, StdError, StdResult, Uint128};
+use cw_utils::{Duration, Expiration};
+
+#[cw_serde]
+pub struct TwapSchedule {
+    // number of swaps the order amount is split into
+    pub slices: u64,
+    // blocks or time between two swaps
+    pub interval: Duration,
+    pub executed_slices: u64,
+    pub next_execution: Expiration,
+    // target tokens received by the executed slices
+    pub received_amount: Uint128,
+    // target tokens received per source token swapped
+    pub average_price: Option<Decimal>,
+}
+
+impl TwapSchedule {
+    // the first slice is executed at the end of the block the order is created in
+    pub fn new(slices: u64, interval: Duration, block: &BlockInfo) -> StdResult<Self> {
+        if slices == 0 {
+            return Err(StdError::generic_err(
+                "slices: The order should be split into at least one slice",
+            ));
+        }
+
+        let next_execution = match interval {
+            Duration::Height(0) | Duration::Time(0) => {
+                return Err(StdError::generic_err(
+                    "interval: The interval cannot be zero",
+                ))
+            }
+            Duration::Height(_) => Expiration::AtHeight(block.height),
+            Duration::Time(_) => Expiration::AtTime(block.time),
+        };
+
+        Ok(Self {
+            slices,
+            interval,
+            executed_slices: 0,
+            next_execution,
+            received_amount: Uint128::zero(),
+            average_price: None,
+        })
+    }
+
+    pub fn is_due(&self, block: &BlockInfo) -> bool {
+        self.next_execution.is_expired(block)
+    }
+
+    // the orders are sorted by their next execution in the order book, as a height or a time in seconds
+    pub fn book_rate(&self) -> Decimal {
+        match self.next_execution {
+            Expiration::AtHeight(height) => Decimal::from_ratio(height, 1u64),
+            Expiration::AtTime(time) => Decimal::from_ratio(time.seconds(), 1u64),
+            Expiration::Never {} => Decimal::MAX,
+        }
+    }
+
+    // rate of the current block in the order book, the orders with a lower or equal rate are due
+    pub fn block_rate(&self, block: &BlockInfo) -> Decimal {
+        match self.interval {
+            Duration::Height(_) => Decimal::from_ratio(block.height, 1u64),
+            Duration::Time(_) => Decimal::from_ratio(block.time.seconds(), 1u64),
+        }
+    }
+
+    // the remaining amount is shared between the remaining slices, the last one swaps everything left
+    pub fn slice_amount(&self, remaining_amount: Uint128) -> Uint128 {
+        let remaining_slices = self.slices.saturating_sub(self.executed_slices).max(1);
+        remaining_amount / Uint128::from(remaining_slices)
+    }
+
+    // record an executed slice and schedule the next one
+    pub fn record_slice(
+        &mut self,
+        filled_amount: Uint128,
+        received_amount: Uint128,
+        block: &BlockInfo,
+    ) {
+        self.executed_slices += 1;
+        self.received_amount += received_amount;
+        self.average_price = Decimal::checked_from_ratio(self.received_amount, filled_amount).ok();
+        self.next_execution = self.interval.after(block);
+    }
+}
@@ -1,6 +1,7 @@
 use std::str::FromStr;
 
 use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::serde::{de::DeserializeOwned, Serialize};
 use cosmwasm_std::from_json;
 use cosmwasm_std::to_json_binary;
 use cosmwasm_std::Binary;
@@ -8,11 +9,14 @@ use cosmwasm_std::Coin;
 use cosmwasm_std::Decimal;
 use cosmwasm_std::Int128;
 use cosmwasm_std::Int256;
+use cosmwasm_std::Order;
 use cosmwasm_std::SignedDecimal;
 use cosmwasm_std::SignedDecimal256;
 use cosmwasm_std::StdError;
 use cosmwasm_std::StdResult;
+use cosmwasm_std::Storage;
 use cosmwasm_std::Uint128;
+use cw_storage_plus::{Bound, IndexList, IndexedMap};
 
 #[cw_serde]
 pub struct OracleAssetInfo {
@@ -70,6 +74,13 @@ impl PageResponse {
     }
 }
 
+// lower bound, upper bound and order of a range over the ids
+pub type PageBounds = (
+    Option<Bound<'static, u64>>,
+    Option<Bound<'static, u64>>,
+    Order,
+);
+
 #[cw_serde]
 pub struct PageRequest {
     pub key: Option<Binary>,
@@ -158,6 +169,94 @@ impl PageRequest {
     pub fn update(&mut self, key: Option<Binary>) -> () {
         self.key = key;
     }
+
+    // load the next page of a map and move the key to the first entry of the following page,
+    // the key goes back to the beginning of the map once its end is reached
+    pub fn load_next_page<T, I>(
+        &mut self,
+        storage: &dyn Storage,
+        map: &IndexedMap<u64, T, I>,
+    ) -> StdResult<Vec<T>>
+    where
+        T: Serialize + DeserializeOwned + Clone,
+        I: IndexList<T>,
+    {
+        let start = match &self.key {
+            Some(key) => Some(Bound::inclusive(from_json::<u64>(key)?)),
+            None => None,
+        };
+
+        let mut entries: Vec<(u64, T)> = map
+            .range(storage, start, None, Order::Ascending)
+            .take(self.limit as usize + 1)
+            .collect::<StdResult<Vec<(u64, T)>>>()?;
+
+        let next_key = if entries.len() > self.limit as usize {
+            match entries.pop() {
+                Some((id, _)) => Some(to_json_binary(&id)?),
+                None => None,
+            }
+        } else {
+            None
+        };
+        self.update(next_key);
+
+        Ok(entries.into_iter().map(|(_, value)| value).collect())
+    }
+
+    // bounds and order of a range over the ids starting at the key of the page
+    pub fn bounds(&self) -> StdResult<PageBounds> {
+        let key = match &self.key {
+            Some(key) => Some(Bound::inclusive(from_json::<u64>(key)?)),
+            None => None,
+        };
+
+        Ok(if self.reverse {
+            (None, key, Order::Descending)
+        } else {
+            (key, None, Order::Ascending)
+        })
+    }
+
+    // cut the page out of entries ranged with the bounds, the key of the next page is the id
+    // of its first entry so that entries added in between are neither skipped nor repeated
+    pub fn page<T>(
+        &self,
+        entries: impl Iterator<Item = (u64, T)>,
+        total: Option<u64>,
+    ) -> StdResult<(Vec<T>, PageResponse)> {
+        // an empty page would point at itself as the next page forever
+        if self.limit == 0 {
+            return Err(StdError::generic_err(
+                "pagination: The limit should be at least 1",
+            ));
+        }
+
+        // the key already starts the page at its first entry, the offset only moves the first page
+        let offset = match &self.key {
+            Some(_) => 0,
+            None => self.offset.unwrap_or(0),
+        };
+
+        let mut entries: Vec<(u64, T)> = entries
+            .skip(offset as usize)
+            .take(self.limit as usize + 1)
+            .collect();
+
+        let next_key = if entries.len() > self.limit as usize {
+            match entries.pop() {
+                Some((id, _)) => Some(to_json_binary(&id)?),
+                None => None,
+            }
+        } else {
+            None
+        };
+
+        Ok((
+            entries.into_iter().map(|(_, value)| value).collect(),
+            PageResponse::new(next_key, total),
+        ))
+    }
 }
 
 #[cw_serde]
@@ -66,7 +66,9 @@ fn history() {
     let trade_shield_code_id = app.store_code(Box::new(trade_shield_code));
 
     let trade_shield_init = TradeShieldInstantiateMsg {
+        admin: None,
         account_history_address: None,
+        process_order_limit: None,
     };
 
     let trade_shield_address = app
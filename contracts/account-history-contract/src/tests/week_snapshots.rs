@@ -279,7 +279,9 @@ fn get_portfolio() {
         ContractWrapper::new(trade_shield_execute, trade_shield_init, trade_shield_query);
     let trade_shield_code_id = app.store_code(Box::new(trade_shield_code));
     let trade_shield_init = TradeShieldInstantiateMsg {
+        admin: None,
         account_history_address: None,
+        process_order_limit: None,
     };
     let trade_shield_address = app
         .instantiate_contract(
@@ -423,17 +423,23 @@ impl AccountSnapshotGenerator {
             None => return Ok(vec![]),
         };
 
-        let spot_order: GetSpotOrdersResp = querier
-            .query_wasm_smart(
-                trade_shield_address,
-                &GetSpotOrders {
-                    pagination: None,
-                    order_owner: Some(owner.clone()),
-                    order_type: None,
-                    order_status: Some(Status::Pending),
-                },
-            )
-            .map_err(|e| StdError::generic_err(format!("GetSpotOrders failed {}", e)))?;
+        let mut spot_orders: Vec<SpotOrder> = vec![];
+
+        // a partially filled order still holds its remaining amount
+        for order_status in [Status::Pending, Status::PartiallyFilled] {
+            let spot_order: GetSpotOrdersResp = querier
+                .query_wasm_smart(
+                    trade_shield_address,
+                    &GetSpotOrders {
+                        pagination: None,
+                        order_owner: Some(owner.clone()),
+                        order_type: None,
+                        order_status: Some(order_status),
+                    },
+                )
+                .map_err(|e| StdError::generic_err(format!("GetSpotOrders failed {}", e)))?;
+            spot_orders.extend(spot_order.orders);
+        }
         let perpetual_order: GetPerpetualOrdersResp = querier
             .query_wasm_smart(
                 trade_shield_address,
@@ -447,7 +453,7 @@ impl AccountSnapshotGenerator {
             .map_err(|e| StdError::generic_err(format!("GetPerpetualOrders failed {}", e)))?;
         let mut map: HashMap<String, Uint128> = HashMap::new();
 
-        for SpotOrder { order_amount, .. } in spot_order.orders {
+        for order_amount in spot_orders.iter().map(SpotOrder::remaining_amount) {
             map.entry(order_amount.denom)
                 .and_modify(|e| *e += order_amount.amount)
                 .or_insert(order_amount.amount);
@@ -0,0 +1,40 @@
+use cosmwasm_std::{coin, Int128, StdError};
+
+use super::*;
+
+pub fn add_perpetual_position_collateral(
+    info: MessageInfo,
+    env: Env,
+    deps: DepsMut<ElysQuery>,
+    id: u64,
+) -> Result<Response<ElysMsg>, ContractError> {
+    let querier = ElysQuerier::new(&deps.querier);
+
+    let mtp = match querier.mtp(info.sender.to_string(), id)?.mtp {
+        Some(mtp) => mtp,
+        None => return Err(StdError::not_found("perpetual trading position").into()),
+    };
+
+    let amount = cw_utils::must_pay(&info, &mtp.collateral_asset)?;
+
+    let msg = ElysMsg::perpetual_add_collateral(
+        env.contract.address.as_str(),
+        id,
+        Int128::try_from(amount).map_err(StdError::from)?.i128(),
+        info.sender.as_str(),
+    );
+
+    let resp = Response::new()
+        .add_message(CosmosMsg::Custom(msg))
+        .add_event(
+            Event::new("add_perpetual_position_collateral")
+                .add_attribute("position_id", id.to_string())
+                .add_attribute("owner", info.sender.as_str())
+                .add_attribute(
+                    "collateral",
+                    coin(amount.u128(), &mtp.collateral_asset).to_string(),
+                ),
+        );
+
+    Ok(resp)
+}
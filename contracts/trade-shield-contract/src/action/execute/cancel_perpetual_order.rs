@@ -1,7 +1,11 @@
 use super::*;
+use crate::helper::{
+    cancel_child_perpetual_orders, cancel_event, log_perpetual_order, perpetual_order_event,
+};
 
 pub fn cancel_perpetual_order(
     info: MessageInfo,
+    env: Env,
     deps: DepsMut<ElysQuery>,
     order_id: u64,
 ) -> Result<Response<ElysMsg>, ContractError> {
@@ -10,8 +14,6 @@ pub fn cancel_perpetual_order(
         None => return Err(ContractError::OrderNotFound { order_id }),
     };
 
-    let order_type = order.order_type.clone();
-
     if order.owner != info.sender.to_string() {
         return Err(ContractError::Unauthorized {
             sender: info.sender,
@@ -25,24 +27,33 @@ pub fn cancel_perpetual_order(
         });
     }
 
-    order.status = Status::Canceled;
+    order.cancel(CancelReason::User);
 
-    let refund_msg = BankMsg::Send {
+    let refund_msg = order.escrow().map(|escrow| BankMsg::Send {
         to_address: order.owner.clone(),
-        amount: vec![order.collateral.clone()],
-    };
+        amount: vec![escrow],
+    });
 
     let resp = Response::new().add_event(
-        Event::new("cancel_perpetual_order")
-            .add_attribute("perpetual_order_id", order.order_id.to_string()),
+        cancel_event(
+            perpetual_order_event("cancel_perpetual_order", &order),
+            &CancelReason::User,
+        )?
+        .add_attribute("perpetual_order_id", order.order_id.to_string()),
     );
 
     PERPETUAL_ORDER.save(deps.storage, order_id, &order)?;
-    PENDING_PERPETUAL_ORDER.remove(deps.storage, order.order_id);
-
-    if order_type == PerpetualOrderType::LimitOpen {
-        Ok(resp.add_message(CosmosMsg::Bank(refund_msg)))
-    } else {
-        Ok(resp)
-    }
+    PENDING_PERPETUAL_ORDER.remove(deps.storage, order.order_id)?;
+    log_perpetual_order(
+        deps.storage,
+        order_id,
+        &env.block,
+        OrderEvent::Canceled {
+            reason: CancelReason::User,
+        },
+    )?;
+    let child_events = cancel_child_perpetual_orders(deps.storage, &env.block, order.order_id)?;
+    let resp = resp.add_events(child_events);
+
+    Ok(resp.add_messages(refund_msg))
 }
@@ -3,9 +3,13 @@ use std::collections::HashMap;
 use cosmwasm_std::{to_json_binary, Coin, StdError};
 
 use super::*;
+use crate::helper::{
+    cancel_child_perpetual_orders, cancel_event, log_perpetual_order, perpetual_order_event,
+};
 
 pub fn cancel_perpetual_orders(
     info: MessageInfo,
+    env: Env,
     deps: DepsMut<ElysQuery>,
     order_ids: Option<Vec<u64>>,
     order_type: Option<PerpetualOrderType>,
@@ -38,7 +42,10 @@ pub fn cancel_perpetual_orders(
         orders
     } else {
         let orders: Vec<PerpetualOrder> = PERPETUAL_ORDER
-            .prefix_range(deps.storage, None, None, Order::Ascending)
+            .idx
+            .owner
+            .prefix(info.sender.to_string())
+            .range(deps.storage, None, None, Order::Ascending)
             .filter_map(|res| {
                 if let Some(r) = res.ok() {
                     Some(r.1)
@@ -46,9 +53,7 @@ pub fn cancel_perpetual_orders(
                     None
                 }
             })
-            .filter(|order| {
-                order.owner.as_str() == info.sender.as_str() && order.status == Status::Pending
-            })
+            .filter(|order| order.status == Status::Pending)
             .collect();
 
         if orders.is_empty() {
@@ -61,11 +66,29 @@ pub fn cancel_perpetual_orders(
     };
 
     let mut orders = filter_order_by_type(orders, order_type)?;
+    let mut events: Vec<Event> = vec![];
 
     for order in orders.iter_mut() {
-        order.status = Status::Canceled;
+        order.cancel(CancelReason::User);
         PERPETUAL_ORDER.save(deps.storage, order.order_id, order)?;
-        PENDING_PERPETUAL_ORDER.remove(deps.storage, order.order_id);
+        PENDING_PERPETUAL_ORDER.remove(deps.storage, order.order_id)?;
+        events.push(cancel_event(
+            perpetual_order_event("cancel_perpetual_order", order),
+            &CancelReason::User,
+        )?);
+        log_perpetual_order(
+            deps.storage,
+            order.order_id,
+            &env.block,
+            OrderEvent::Canceled {
+                reason: CancelReason::User,
+            },
+        )?;
+        events.extend(cancel_child_perpetual_orders(
+            deps.storage,
+            &env.block,
+            order.order_id,
+        )?);
     }
 
     let order_ids: Vec<u64> = orders.iter().map(|order| order.order_id).collect();
@@ -74,6 +97,7 @@ pub fn cancel_perpetual_orders(
 
     Ok(Response::new()
         .add_message(refund_msg)
+        .add_events(events)
         .set_data(to_json_binary(&order_ids)?))
 }
 
@@ -104,13 +128,7 @@ fn filter_order_by_type(
 fn make_refund_msg(orders: Vec<PerpetualOrder>, user: String) -> BankMsg {
     let orders_amount: Vec<Coin> = orders
         .into_iter()
-        .filter_map(|order| {
-            if order.order_type == PerpetualOrderType::LimitOpen {
-                Some(order.collateral)
-            } else {
-                None
-            }
-        })
+        .filter_map(|order| order.escrow())
         .collect();
 
     let mut merged_amounts: HashMap<String, Coin> = HashMap::new();
@@ -1,7 +1,9 @@
 use super::*;
+use crate::helper::{cancel_event, log_spot_order, spot_order_event};
 
 pub fn cancel_spot_order(
     info: MessageInfo,
+    env: Env,
     deps: DepsMut<ElysQuery>,
     order_id: u64,
 ) -> Result<Response<ElysMsg>, ContractError> {
@@ -16,26 +18,37 @@ pub fn cancel_spot_order(
         });
     }
 
-    if order.status != Status::Pending {
+    if !order.is_pending() {
         return Err(ContractError::CancelStatusError {
             order_id,
             status: order.status,
         });
     }
 
-    order.status = Status::Canceled;
+    order.cancel(CancelReason::User);
 
     let refund_msg = BankMsg::Send {
         to_address: order.owner_address.to_string(),
-        amount: vec![order.order_amount.clone()],
+        amount: vec![order.remaining_amount()],
     };
 
     let resp = Response::new()
         .add_message(CosmosMsg::Bank(refund_msg))
-        .add_event(Event::new("cancel_spot_order").add_attribute("order_id", order_id.to_string()));
+        .add_event(cancel_event(
+            spot_order_event("cancel_spot_order", &order),
+            &CancelReason::User,
+        )?);
 
     SPOT_ORDER.save(deps.storage, order_id, &order)?;
-    PENDING_SPOT_ORDER.remove(deps.storage, order_id);
+    PENDING_SPOT_ORDER.remove(deps.storage, order_id)?;
+    log_spot_order(
+        deps.storage,
+        order_id,
+        &env.block,
+        OrderEvent::Canceled {
+            reason: CancelReason::User,
+        },
+    )?;
 
     Ok(resp)
 }
@@ -3,9 +3,11 @@ use std::collections::HashMap;
 use cosmwasm_std::{to_json_binary, Coin, StdError};
 
 use super::*;
+use crate::helper::{cancel_event, log_spot_order, spot_order_event};
 
 pub fn cancel_spot_orders(
     info: MessageInfo,
+    env: Env,
     deps: DepsMut<ElysQuery>,
     order_ids: Option<Vec<u64>>,
     order_type: Option<SpotOrderType>,
@@ -28,7 +30,7 @@ pub fn cancel_spot_orders(
             });
         }
 
-        if let Some(order) = orders.iter().find(|order| order.status != Status::Pending) {
+        if let Some(order) = orders.iter().find(|order| !order.is_pending()) {
             return Err(ContractError::CancelStatusError {
                 order_id: order.order_id,
                 status: order.status.clone(),
@@ -38,7 +40,10 @@ pub fn cancel_spot_orders(
         orders
     } else {
         let orders: Vec<SpotOrder> = SPOT_ORDER
-            .prefix_range(deps.storage, None, None, Order::Ascending)
+            .idx
+            .owner
+            .prefix(info.sender.to_string())
+            .range(deps.storage, None, None, Order::Ascending)
             .filter_map(|res| {
                 if let Some(r) = res.ok() {
                     Some(r.1)
@@ -46,10 +51,7 @@ pub fn cancel_spot_orders(
                     None
                 }
             })
-            .filter(|order| {
-                order.owner_address.as_str() == info.sender.as_str()
-                    && order.status == Status::Pending
-            })
+            .filter(|order| order.is_pending())
             .collect();
 
         if orders.is_empty() {
@@ -62,11 +64,24 @@ pub fn cancel_spot_orders(
     };
 
     let mut orders = filter_order_by_type(orders, order_type)?;
+    let mut events: Vec<Event> = vec![];
 
     for order in orders.iter_mut() {
-        order.status = Status::Canceled;
+        order.cancel(CancelReason::User);
         SPOT_ORDER.save(deps.storage, order.order_id, &order)?;
-        PENDING_SPOT_ORDER.remove(deps.storage, order.order_id);
+        PENDING_SPOT_ORDER.remove(deps.storage, order.order_id)?;
+        events.push(cancel_event(
+            spot_order_event("cancel_spot_order", order),
+            &CancelReason::User,
+        )?);
+        log_spot_order(
+            deps.storage,
+            order.order_id,
+            &env.block,
+            OrderEvent::Canceled {
+                reason: CancelReason::User,
+            },
+        )?;
     }
 
     let order_ids: Vec<u64> = orders.iter().map(|order| order.order_id).collect();
@@ -75,6 +90,7 @@ pub fn cancel_spot_orders(
 
     Ok(Response::new()
         .add_message(refund_msg)
+        .add_events(events)
         .set_data(to_json_binary(&order_ids)?))
 }
 
@@ -103,7 +119,10 @@ fn filter_order_by_type(
 }
 
 fn make_refund_msg(orders: Vec<SpotOrder>, user: String) -> BankMsg {
-    let orders_amount: Vec<Coin> = orders.into_iter().map(|order| order.order_amount).collect();
+    let orders_amount: Vec<Coin> = orders
+        .into_iter()
+        .map(|order| order.remaining_amount())
+        .collect();
 
     let mut merged_amounts: HashMap<String, Coin> = HashMap::new();
 
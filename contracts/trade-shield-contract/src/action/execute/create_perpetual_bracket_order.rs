@@ -0,0 +1,138 @@
+use super::*;
+use crate::action::execute::create_perpetual_bracket_open_order;
+use crate::helper::{log_perpetual_order, next_perpetual_order_id};
+use cosmwasm_std::{SignedDecimal, SignedDecimal256, StdError, StdResult};
+use cw_utils::Expiration;
+use elys_bindings::query_resp::{Entry, QueryGetEntryResponse};
+
+pub fn create_perpetual_bracket_order(
+    env: Env,
+    info: MessageInfo,
+    mut deps: DepsMut<ElysQuery>,
+    position: PerpetualPosition,
+    leverage: SignedDecimal,
+    trading_asset: String,
+    take_profit_price: Option<SignedDecimal256>,
+    order_type: PerpetualOrderType,
+    trigger_price: Option<OrderPrice>,
+    take_profit_trigger_price: OrderPrice,
+    stop_loss_trigger_price: OrderPrice,
+    expiration: Option<Expiration>,
+) -> Result<Response<ElysMsg>, ContractError> {
+    if order_type != PerpetualOrderType::LimitOpen && order_type != PerpetualOrderType::MarketOpen {
+        return Err(StdError::generic_err(
+            "order_type: A bracket order should be a LimitOpen or MarketOpen order",
+        )
+        .into());
+    }
+
+    let querier = ElysQuerier::new(&deps.querier);
+    let QueryGetEntryResponse {
+        entry: Entry {
+            denom: usdc_denom, ..
+        },
+    } = querier.get_asset_profile("uusdc".to_string())?;
+
+    check_child_trigger_price(
+        "take_profit_trigger_price",
+        &take_profit_trigger_price,
+        &usdc_denom,
+        &trading_asset,
+    )?;
+    check_child_trigger_price(
+        "stop_loss_trigger_price",
+        &stop_loss_trigger_price,
+        &usdc_denom,
+        &trading_asset,
+    )?;
+
+    let take_profit_above_stop_loss = take_profit_trigger_price.rate > stop_loss_trigger_price.rate;
+    if (position == PerpetualPosition::Long && !take_profit_above_stop_loss)
+        || (position == PerpetualPosition::Short && take_profit_above_stop_loss)
+    {
+        return Err(StdError::generic_err(
+            "take_profit_trigger_price: The take profit should be on the profitable side of the stop loss",
+        )
+        .into());
+    }
+
+    let (resp, parent_order_id) = create_perpetual_bracket_open_order(
+        env.clone(),
+        info,
+        deps.branch(),
+        position,
+        leverage,
+        trading_asset,
+        take_profit_price,
+        order_type,
+        trigger_price,
+        expiration,
+    )?;
+
+    let parent = PERPETUAL_ORDER.load(deps.storage, parent_order_id)?;
+
+    let mut take_profit = PerpetualOrder::new_child(
+        &parent,
+        &PerpetualOrderType::LimitClose,
+        &take_profit_trigger_price,
+        next_perpetual_order_id(deps.storage)?,
+    )?;
+    take_profit.group_id = Some(take_profit.order_id);
+
+    let mut stop_loss = PerpetualOrder::new_child(
+        &parent,
+        &PerpetualOrderType::StopLoss,
+        &stop_loss_trigger_price,
+        next_perpetual_order_id(deps.storage)?,
+    )?;
+    stop_loss.group_id = take_profit.group_id;
+
+    PERPETUAL_ORDER.save(deps.storage, take_profit.order_id, &take_profit)?;
+    PERPETUAL_ORDER.save(deps.storage, stop_loss.order_id, &stop_loss)?;
+    log_perpetual_order(
+        deps.storage,
+        take_profit.order_id,
+        &env.block,
+        OrderEvent::Created,
+    )?;
+    log_perpetual_order(
+        deps.storage,
+        stop_loss.order_id,
+        &env.block,
+        OrderEvent::Created,
+    )?;
+
+    Ok(resp.add_event(
+        Event::new("create_perpetual_bracket_order")
+            .add_attribute("perpetual_order_id", parent.order_id.to_string())
+            .add_attribute("take_profit_order_id", take_profit.order_id.to_string())
+            .add_attribute("stop_loss_order_id", stop_loss.order_id.to_string()),
+    ))
+}
+
+fn check_child_trigger_price(
+    field: &str,
+    price: &OrderPrice,
+    usdc_denom: &str,
+    trading_asset: &str,
+) -> StdResult<()> {
+    if price.rate.is_zero() {
+        return Err(StdError::generic_err(format!(
+            "{field}: The rate cannot be zero"
+        )));
+    }
+
+    if price.base_denom != usdc_denom {
+        return Err(StdError::generic_err(format!(
+            "{field}: The base denom should be the usdc denom"
+        )));
+    }
+
+    if price.quote_denom != trading_asset {
+        return Err(StdError::generic_err(format!(
+            "{field}: The quote denom should be the trading asset denom"
+        )));
+    }
+
+    Ok(())
+}
@@ -1,11 +1,19 @@
-use crate::{helper::get_discount, msg::ReplyType};
+use crate::{
+    helper::{
+        accrue_protocol_fee, check_assets, check_close_size, check_expiration, check_leverage,
+        check_order_amount, check_pair, get_discount, get_pending_close_orders, get_protocol_fee,
+        link_perpetual_order, log_perpetual_order, next_perpetual_order_id,
+    },
+    msg::ReplyType,
+    types::TrailingStop,
+};
 
 use super::*;
 use cosmwasm_std::{
-    coin, to_json_binary, OverflowError, OverflowOperation, SignedDecimal, SignedDecimal256,
-    StdError, StdResult, SubMsg,
+    coin, to_json_binary, BlockInfo, Decimal, OverflowError, OverflowOperation, SignedDecimal,
+    SignedDecimal256, StdError, StdResult, SubMsg,
 };
-use cw_utils;
+use cw_utils::{self, Expiration};
 use elys_bindings::query_resp::{Entry, QueryGetEntryResponse};
 use PerpetualOrderType::*;
 
@@ -20,7 +28,14 @@ pub fn create_perpetual_order(
     order_type: PerpetualOrderType,
     trigger_price: Option<OrderPrice>,
     position_id: Option<u64>,
+    trailing_distance: Option<TrailingDistance>,
+    linked_order_id: Option<u64>,
+    expiration: Option<Expiration>,
+    close_size: Option<CloseSize>,
+    health_threshold: Option<SignedDecimal>,
 ) -> Result<Response<ElysMsg>, ContractError> {
+    check_expiration(&expiration, &env.block)?;
+
     check_order_type(
         &position,
         &leverage,
@@ -28,6 +43,9 @@ pub fn create_perpetual_order(
         &order_type,
         &trigger_price,
         &position_id,
+        &trailing_distance,
+        &close_size,
+        &health_threshold,
     )?;
 
     if order_type == LimitOpen || order_type == MarketOpen {
@@ -40,8 +58,12 @@ pub fn create_perpetual_order(
             leverage.unwrap(),
             take_profit_price,
             trigger_price,
+            linked_order_id,
+            expiration,
             env.contract.address.as_str(),
+            &env.block,
         )
+        .map(|(resp, _)| resp)
     } else {
         create_perpetual_close_order(
             env.contract.address.as_str(),
@@ -50,10 +72,60 @@ pub fn create_perpetual_order(
             order_type,
             position_id.unwrap(),
             trigger_price,
+            trailing_distance,
+            linked_order_id,
+            expiration,
+            close_size,
+            health_threshold,
+            &env.block,
         )
     }
 }
 
+// creates the open order of a bracket and returns its id along with the response,
+// the take profit and stop loss orders of the bracket are attached to it
+pub fn create_perpetual_bracket_open_order(
+    env: Env,
+    info: MessageInfo,
+    deps: DepsMut<ElysQuery>,
+    position: PerpetualPosition,
+    leverage: SignedDecimal,
+    trading_asset: String,
+    take_profit_price: Option<SignedDecimal256>,
+    order_type: PerpetualOrderType,
+    trigger_price: Option<OrderPrice>,
+    expiration: Option<Expiration>,
+) -> Result<(Response<ElysMsg>, u64), ContractError> {
+    check_expiration(&expiration, &env.block)?;
+
+    check_order_type(
+        &Some(position.clone()),
+        &Some(leverage),
+        &Some(trading_asset.clone()),
+        &order_type,
+        &trigger_price,
+        &None,
+        &None,
+        &None,
+        &None,
+    )?;
+
+    create_perpetual_open_order(
+        info,
+        deps,
+        order_type,
+        position,
+        trading_asset,
+        leverage,
+        take_profit_price,
+        trigger_price,
+        None,
+        expiration,
+        env.contract.address.as_str(),
+        &env.block,
+    )
+}
+
 fn check_order_type(
     position: &Option<PerpetualPosition>,
     leverage: &Option<SignedDecimal>,
@@ -61,19 +133,75 @@ fn check_order_type(
     order_type: &PerpetualOrderType,
     trigger_price: &Option<OrderPrice>,
     position_id: &Option<u64>,
+    trailing_distance: &Option<TrailingDistance>,
+    close_size: &Option<CloseSize>,
+    health_threshold: &Option<SignedDecimal>,
 ) -> StdResult<()> {
     let mut not_found: Vec<&str> = vec![];
 
-    if order_type != &MarketOpen && order_type != &MarketClose && trigger_price.is_none() {
+    if order_type != &MarketOpen
+        && order_type != &MarketClose
+        && order_type != &PerpetualOrderType::TrailingStop
+        && order_type != &HealthMonitor
+        && trigger_price.is_none()
+    {
         not_found.push("trigger price");
     }
 
-    if (order_type == &LimitClose || order_type == &MarketClose || order_type == &StopLoss)
+    if (order_type == &LimitClose
+        || order_type == &MarketClose
+        || order_type == &StopLoss
+        || order_type == &PerpetualOrderType::TrailingStop
+        || order_type == &HealthMonitor)
         && position_id.is_none()
     {
         not_found.push("position id");
     }
 
+    if order_type == &HealthMonitor {
+        if trigger_price.is_some() {
+            return Err(StdError::generic_err(
+                "trigger_price: Not used by HealthMonitor orders",
+            ));
+        }
+        match health_threshold {
+            Some(threshold) if threshold <= &SignedDecimal::zero() => {
+                return Err(StdError::generic_err(
+                    "health_threshold: The threshold should be positive",
+                ));
+            }
+            Some(_) => {}
+            None => not_found.push("health threshold"),
+        }
+    } else if health_threshold.is_some() {
+        return Err(StdError::generic_err(
+            "health_threshold: Only used by HealthMonitor orders",
+        ));
+    }
+
+    if order_type == &PerpetualOrderType::TrailingStop && trailing_distance.is_none() {
+        not_found.push("trailing distance");
+    }
+
+    if order_type != &PerpetualOrderType::TrailingStop && trailing_distance.is_some() {
+        return Err(StdError::generic_err(
+            "trailing_distance: Only used by TrailingStop orders",
+        ));
+    }
+
+    if let Some(close_size) = close_size {
+        if order_type != &LimitClose
+            && order_type != &MarketClose
+            && order_type != &StopLoss
+            && order_type != &HealthMonitor
+        {
+            return Err(StdError::generic_err(
+                "close_size: Only used by LimitClose, MarketClose, StopLoss and HealthMonitor orders",
+            ));
+        }
+        close_size.validate()?;
+    }
+
     if order_type == &LimitOpen || order_type == &MarketOpen {
         if position.is_none() {
             not_found.push("position");
@@ -106,14 +234,22 @@ fn create_perpetual_open_order(
     leverage: SignedDecimal,
     take_profit_price: Option<SignedDecimal256>,
     trigger_price: Option<OrderPrice>,
+    linked_order_id: Option<u64>,
+    expiration: Option<Expiration>,
     creator: &str,
-) -> Result<Response<ElysMsg>, ContractError> {
+    block: &BlockInfo,
+) -> Result<(Response<ElysMsg>, u64), ContractError> {
     let collateral = cw_utils::one_coin(&info)?;
 
-    let orders: Vec<PerpetualOrder> = PERPETUAL_ORDER
-        .prefix_range(deps.storage, None, None, Order::Ascending)
-        .filter_map(|res| res.ok().map(|r| r.1))
-        .collect();
+    let config = CONFIG.load(deps.storage)?;
+    check_pair(&config, &collateral.denom, &trading_asset)?;
+    check_assets(
+        &deps.as_ref(),
+        &config,
+        &[&collateral.denom, &trading_asset],
+    )?;
+    check_order_amount(&config, &collateral)?;
+    check_leverage(&config, &leverage)?;
 
     if position == PerpetualPosition::Unspecified {
         return Err(
@@ -128,13 +264,15 @@ fn create_perpetual_open_order(
         },
     } = querier.get_asset_profile("uusdc".to_string())?;
 
+    let discount = get_discount(&deps.as_ref(), info.sender.to_string())?;
+
     let open_estimation = querier.perpetual_open_estimation(
         position.clone(),
         leverage.clone(),
         &trading_asset,
         collateral.clone(),
         take_profit_price.clone(),
-        get_discount(&deps.as_ref(), info.sender.to_string())?,
+        discount,
     )?;
 
     if !open_estimation.valid_collateral {
@@ -161,7 +299,7 @@ fn create_perpetual_open_order(
         }
     }
 
-    let order = PerpetualOrder::new_open(
+    let mut order = PerpetualOrder::new_open(
         &info.sender,
         &position,
         &order_type,
@@ -170,15 +308,29 @@ fn create_perpetual_open_order(
         &leverage,
         &take_profit_price,
         &trigger_price,
-        &orders,
+        next_perpetual_order_id(deps.storage)?,
     )?;
 
+    order.expiration = expiration;
+
+    if let Some(linked_order_id) = linked_order_id {
+        link_perpetual_order(deps.storage, &info.sender, &mut order, linked_order_id)?;
+    }
+
+    // a market order is opened right away, its fee is reverted along with it if the open fails
+    let fee = get_protocol_fee(&config, &collateral, discount);
+    if order_type == MarketOpen && !fee.amount.is_zero() {
+        accrue_protocol_fee(deps.storage, &fee)?;
+        order.fee = Some(fee.clone());
+    }
+
     let order_id = order.order_id;
 
     PERPETUAL_ORDER.save(deps.storage, order_id, &order)?;
     if order.order_type != PerpetualOrderType::MarketOpen {
         PENDING_PERPETUAL_ORDER.save(deps.storage, order_id, &order)?;
     }
+    log_perpetual_order(deps.storage, order_id, block, OrderEvent::Created)?;
 
     let resp = Response::new().add_event(
         Event::new("create_perpetual_open_order")
@@ -186,12 +338,12 @@ fn create_perpetual_open_order(
     );
 
     if order_type != MarketOpen {
-        return Ok(resp);
+        return Ok((resp, order_id));
     }
 
     let msg = ElysMsg::perpetual_open_position(
         creator,
-        collateral,
+        coin((collateral.amount - fee.amount).u128(), &collateral.denom),
         trading_asset,
         position,
         leverage,
@@ -224,7 +376,7 @@ fn create_perpetual_open_order(
 
     let sub_msg = SubMsg::reply_always(msg, reply_id);
 
-    Ok(resp.add_submessage(sub_msg))
+    Ok((resp.add_submessage(sub_msg), order_id))
 }
 
 fn create_perpetual_close_order(
@@ -234,9 +386,13 @@ fn create_perpetual_close_order(
     order_type: PerpetualOrderType,
     position_id: u64,
     trigger_price: Option<OrderPrice>,
+    trailing_distance: Option<TrailingDistance>,
+    linked_order_id: Option<u64>,
+    expiration: Option<Expiration>,
+    close_size: Option<CloseSize>,
+    health_threshold: Option<SignedDecimal>,
+    block: &BlockInfo,
 ) -> Result<Response<ElysMsg>, ContractError> {
-    cw_utils::nonpayable(&info)?;
-
     let querier = ElysQuerier::new(&deps.querier);
 
     let mtp_resp = querier.mtp(info.sender.to_string(), position_id)?;
@@ -247,10 +403,20 @@ fn create_perpetual_close_order(
         return Err(StdError::not_found("perpetual trading position").into());
     };
 
-    let orders: Vec<PerpetualOrder> = PERPETUAL_ORDER
-        .prefix_range(deps.storage, None, None, Order::Ascending)
-        .filter_map(|res| res.ok().map(|r| r.1))
-        .collect();
+    // a health monitor sent collateral adds it to the position instead of closing it
+    let escrowed_collateral = if order_type == HealthMonitor && !info.funds.is_empty() {
+        if close_size.is_some() {
+            return Err(StdError::generic_err(
+                "close_size: Not used by HealthMonitor orders adding collateral",
+            )
+            .into());
+        }
+        let amount = cw_utils::must_pay(&info, &mtp.collateral_asset)?;
+        Some(coin(amount.u128(), &mtp.collateral_asset))
+    } else {
+        cw_utils::nonpayable(&info)?;
+        None
+    };
 
     let QueryGetEntryResponse {
         entry: Entry {
@@ -278,21 +444,58 @@ fn create_perpetual_close_order(
         }
     }
 
-    if let Some(mut order) = orders
+    let trailing_stop = match trailing_distance {
+        Some(distance) => {
+            distance.validate()?;
+            let market_price = querier.get_asset_price_from_denom_in_to_denom_out(
+                &mtp.collateral_asset,
+                &mtp.trading_asset,
+            )?;
+            Some(TrailingStop::new(distance, market_price))
+        }
+        None => None,
+    };
+
+    let trigger_price = match (&trailing_stop, trigger_price) {
+        (Some(_), _) => Some(OrderPrice {
+            base_denom: usdc_denom,
+            quote_denom: mtp.trading_asset.clone(),
+            rate: Decimal::zero(),
+        }),
+        (None, trigger_price) => trigger_price,
+    };
+
+    let close_orders = get_pending_close_orders(deps.storage, info.sender.as_str(), position_id)?;
+
+    // an order closing the whole position replaces the one of its type, partial close orders
+    // can be stacked as long as they don't close more than the position holds
+    if let Some(mut order) = close_orders
         .iter()
         .find(|order| {
-            order.position_id == Some(position_id)
-                && order.status == Status::Pending
-                && order_type == order.order_type
+            order_type == order.order_type
+                && order.close_size.is_none()
+                && close_size.is_none()
+                && escrowed_collateral.is_none()
         })
         .cloned()
     {
         order.trigger_price = trigger_price;
+        order.expiration = expiration;
+        order.close_size = close_size;
+        order.health_threshold = health_threshold;
+        check_close_size(&close_orders, &order, mtp.custody)?;
+        if let Some(trailing_stop) = trailing_stop {
+            order.set_trailing_stop(trailing_stop);
+        }
+        if let Some(linked_order_id) = linked_order_id {
+            link_perpetual_order(deps.storage, &info.sender, &mut order, linked_order_id)?;
+        }
         PERPETUAL_ORDER.save(deps.storage, order.order_id, &order)?;
 
         if order.order_type != PerpetualOrderType::MarketClose {
             PENDING_PERPETUAL_ORDER.save(deps.storage, order.order_id, &order)?;
         }
+        log_perpetual_order(deps.storage, order.order_id, block, OrderEvent::Updated)?;
 
         let resp = Response::new().add_event(
             Event::new("create_perpetual_close_order")
@@ -302,7 +505,7 @@ fn create_perpetual_close_order(
         return Ok(resp);
     };
 
-    let order = PerpetualOrder::new_close(
+    let mut order = PerpetualOrder::new_close(
         &info.sender,
         mtp.position,
         &order_type,
@@ -312,15 +515,33 @@ fn create_perpetual_close_order(
         position_id,
         &trigger_price,
         &Some(mtp.take_profit_price),
-        &orders,
+        next_perpetual_order_id(deps.storage)?,
     )?;
 
+    if let Some(trailing_stop) = trailing_stop {
+        order.set_trailing_stop(trailing_stop);
+    }
+
+    order.expiration = expiration;
+    order.close_size = close_size;
+    order.health_threshold = health_threshold;
+    order.escrowed_collateral = escrowed_collateral;
+
+    if order_type != MarketClose && order.escrowed_collateral.is_none() {
+        check_close_size(&close_orders, &order, mtp.custody)?;
+    }
+
+    if let Some(linked_order_id) = linked_order_id {
+        link_perpetual_order(deps.storage, &info.sender, &mut order, linked_order_id)?;
+    }
+
     let order_id = order.order_id;
 
     PERPETUAL_ORDER.save(deps.storage, order_id, &order)?;
     if order.order_type != PerpetualOrderType::MarketClose {
         PENDING_PERPETUAL_ORDER.save(deps.storage, order_id, &order)?;
     }
+    log_perpetual_order(deps.storage, order_id, block, OrderEvent::Created)?;
 
     let resp = Response::new().add_event(
         Event::new("create_perpetual_close_order")
@@ -331,8 +552,12 @@ fn create_perpetual_close_order(
         return Ok(resp);
     }
 
-    let msg =
-        ElysMsg::perpetual_close_position(creator, position_id, mtp.custody.i128(), &info.sender);
+    let msg = ElysMsg::perpetual_close_position(
+        creator,
+        position_id,
+        order.close_amount(mtp.custody).i128(),
+        &info.sender,
+    );
 
     let reply_info_max_id = MAX_REPLY_ID.load(deps.storage)?;
 
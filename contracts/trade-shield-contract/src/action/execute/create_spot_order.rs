@@ -1,9 +1,17 @@
 use cosmwasm_std::{
-    to_json_binary, Decimal, Int128, OverflowError, StdError, StdResult, Storage, SubMsg,
+    coin, to_json_binary, Decimal, Int128, OverflowError, StdError, StdResult, Storage, SubMsg,
+    Uint128,
 };
+use cw_utils::Expiration;
 use elys_bindings::query_resp::AmmSwapEstimationByDenomResponse;
 
-use crate::{helper::get_discount, msg::ReplyType};
+use crate::{
+    helper::{
+        accrue_protocol_fee, check_assets, check_expiration, check_order_amount, check_pair,
+        get_discount, get_protocol_fee, link_spot_order, log_spot_order,
+    },
+    msg::ReplyType,
+};
 
 use super::*;
 
@@ -15,9 +23,25 @@ pub fn create_spot_order(
     order_source_denom: String,
     order_target_denom: String,
     order_price: Option<OrderPrice>,
+    trailing_distance: Option<TrailingDistance>,
+    linked_order_id: Option<u64>,
+    expiration: Option<Expiration>,
+    max_slippage: Option<Decimal>,
+    min_out: Option<Uint128>,
 ) -> Result<Response<ElysMsg>, ContractError> {
     cw_utils::one_coin(&info)?;
 
+    let config = CONFIG.load(deps.storage)?;
+
+    if order_type == SpotOrderType::Twap {
+        return Err(StdError::generic_err(
+            "order_type: Twap orders are created with CreateTwapOrder",
+        )
+        .into());
+    }
+
+    check_expiration(&expiration, &env.block)?;
+
     let querier = ElysQuerier::new(&deps.querier);
 
     if let Some(price) = &order_price {
@@ -26,6 +50,17 @@ pub fn create_spot_order(
         }
     }
 
+    // the trigger price of a trailing stop is computed from the market price and its distance
+    let order_price = if order_type == SpotOrderType::TrailingStop {
+        Some(OrderPrice {
+            base_denom: order_source_denom.clone(),
+            quote_denom: order_target_denom.clone(),
+            rate: Decimal::zero(),
+        })
+    } else {
+        order_price
+    };
+
     check_denom_error(
         &order_source_denom,
         &order_target_denom,
@@ -34,6 +69,24 @@ pub fn create_spot_order(
         &info.funds[0].denom,
     )?;
 
+    check_slippage(&order_type, &max_slippage, &min_out)?;
+
+    check_pair(&config, &order_source_denom, &order_target_denom)?;
+    check_assets(
+        &deps.as_ref(),
+        &config,
+        &[&order_source_denom, &order_target_denom],
+    )?;
+    check_order_amount(&config, &info.funds[0])?;
+
+    let trailing_stop = init_trailing_stop(
+        &querier,
+        &order_type,
+        trailing_distance,
+        &order_source_denom,
+        &order_target_denom,
+    )?;
+
     let discount = get_discount(&deps.as_ref(), info.sender.to_string())?;
 
     let AmmSwapEstimationByDenomResponse { in_route, .. } = querier.amm_swap_estimation_by_denom(
@@ -56,7 +109,7 @@ pub fn create_spot_order(
     };
     SPOT_ORDER_MAX_ID.save(deps.storage, &order_id)?;
 
-    let new_order: SpotOrder = SpotOrder::new(
+    let mut new_order: SpotOrder = SpotOrder::new(
         order_id,
         order_type.clone(),
         order_price,
@@ -66,18 +119,43 @@ pub fn create_spot_order(
         &env.block,
     );
 
+    if let Some(trailing_stop) = trailing_stop {
+        new_order.set_trailing_stop(trailing_stop);
+    }
+
+    new_order.expiration = expiration;
+    new_order.max_slippage = max_slippage;
+
+    if let Some(linked_order_id) = linked_order_id {
+        link_spot_order(deps.storage, &info.sender, &mut new_order, linked_order_id)?;
+    }
+
+    // a market order is swapped right away, its fee is reverted along with it if the swap fails
+    if new_order.order_type == SpotOrderType::MarketBuy {
+        let fee = get_protocol_fee(&config, &new_order.order_amount, discount);
+        new_order.pay_fee(&fee);
+        accrue_protocol_fee(deps.storage, &fee)?;
+    }
+
     let resp = create_resp(
         env.contract.address.as_str(),
         &new_order,
         deps.storage,
         discount,
         in_route.unwrap(),
+        min_out,
     )?;
 
     SPOT_ORDER.save(deps.storage, new_order.order_id, &new_order)?;
     if new_order.order_type != SpotOrderType::MarketBuy {
         PENDING_SPOT_ORDER.save(deps.storage, new_order.order_id, &new_order)?;
     }
+    log_spot_order(
+        deps.storage,
+        new_order.order_id,
+        &env.block,
+        OrderEvent::Created,
+    )?;
 
     Ok(resp)
 }
@@ -117,13 +195,82 @@ fn check_denom_error(
     Ok(())
 }
 
+fn check_slippage(
+    order_type: &SpotOrderType,
+    max_slippage: &Option<Decimal>,
+    min_out: &Option<Uint128>,
+) -> StdResult<()> {
+    if order_type == &SpotOrderType::MarketBuy {
+        if max_slippage.is_some() {
+            return Err(StdError::generic_err(
+                "max_slippage: Not used by MarketBuy orders, use min_out instead",
+            ));
+        }
+        return Ok(());
+    }
+
+    if min_out.is_some() {
+        return Err(StdError::generic_err(
+            "min_out: Only used by MarketBuy orders",
+        ));
+    }
+
+    if let Some(max_slippage) = max_slippage {
+        if max_slippage >= &Decimal::one() {
+            return Err(StdError::generic_err(
+                "max_slippage: The slippage should be lower than 1",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn init_trailing_stop(
+    querier: &ElysQuerier<'_>,
+    order_type: &SpotOrderType,
+    trailing_distance: Option<TrailingDistance>,
+    order_source_denom: &str,
+    order_target_denom: &str,
+) -> StdResult<Option<TrailingStop>> {
+    let distance = match (order_type, trailing_distance) {
+        (SpotOrderType::TrailingStop, Some(distance)) => distance,
+        (SpotOrderType::TrailingStop, None) => {
+            return Err(StdError::not_found("trailing distance"))
+        }
+        (_, Some(_)) => {
+            return Err(StdError::generic_err(
+                "trailing_distance: Only used by TrailingStop orders",
+            ))
+        }
+        (_, None) => return Ok(None),
+    };
+
+    distance.validate()?;
+
+    let market_price = querier
+        .get_asset_price_from_denom_in_to_denom_out(order_source_denom, order_target_denom)?;
+
+    // the stop price would be stuck at zero below the market
+    if let TrailingDistance::Absolute(offset) = &distance {
+        if offset >= &market_price {
+            return Err(StdError::generic_err(
+                "trailing_distance: The offset should be lower than the market price",
+            ));
+        }
+    }
+
+    Ok(Some(TrailingStop::new(distance, market_price)))
+}
+
 fn create_resp(
     sender: &str,
     new_order: &SpotOrder,
     storage: &mut dyn Storage,
     discount: Decimal,
     in_route: Vec<SwapAmountInRoute>,
-) -> StdResult<Response<ElysMsg>> {
+    min_out: Option<Uint128>,
+) -> Result<Response<ElysMsg>, ContractError> {
     let resp = Response::new().add_event(
         Event::new("create_spot_order").add_attribute("order_id", new_order.order_id.to_string()),
     );
@@ -147,11 +294,21 @@ fn create_resp(
 
     MAX_REPLY_ID.save(storage, &reply_id)?;
 
+    let fee = new_order
+        .fee
+        .clone()
+        .map(|fee| fee.amount)
+        .unwrap_or_default();
+    let swap_amount = coin(
+        (new_order.order_amount.amount - fee).u128(),
+        &new_order.order_amount.denom,
+    );
+
     let swap_msg = ElysMsg::amm_swap_exact_amount_in(
         sender,
-        &new_order.order_amount,
+        &swap_amount,
         &in_route,
-        Int128::zero(),
+        Int128::try_from(min_out.unwrap_or_default()).map_err(StdError::from)?,
         discount,
         &new_order.owner_address,
     );
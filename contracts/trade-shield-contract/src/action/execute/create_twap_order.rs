@@ -0,0 +1,109 @@
+use cosmwasm_std::{Decimal, OverflowError, StdError, Uint128};
+use cw_utils::{Duration, Expiration};
+
+use crate::helper::{
+    check_assets, check_expiration, check_order_amount, check_pair, log_spot_order,
+};
+
+use super::*;
+
+pub fn create_twap_order(
+    env: Env,
+    deps: DepsMut<ElysQuery>,
+    info: MessageInfo,
+    order_source_denom: String,
+    order_target_denom: String,
+    slices: u64,
+    interval: Duration,
+    max_slippage: Option<Decimal>,
+    expiration: Option<Expiration>,
+) -> Result<Response<ElysMsg>, ContractError> {
+    let order_amount = cw_utils::one_coin(&info)?;
+
+    let config = CONFIG.load(deps.storage)?;
+
+    check_expiration(&expiration, &env.block)?;
+
+    if order_source_denom != order_amount.denom {
+        return Err(ContractError::SpotOrderWrongFund);
+    }
+
+    if order_source_denom == order_target_denom {
+        return Err(ContractError::SpotOrderSameDenom);
+    }
+
+    check_pair(&config, &order_source_denom, &order_target_denom)?;
+    check_assets(
+        &deps.as_ref(),
+        &config,
+        &[&order_source_denom, &order_target_denom],
+    )?;
+    check_order_amount(&config, &order_amount)?;
+
+    if order_amount.amount < Uint128::from(slices) {
+        return Err(
+            StdError::generic_err("slices: Every slice should swap at least one token").into(),
+        );
+    }
+
+    if let Some(max_slippage) = max_slippage {
+        if max_slippage >= Decimal::one() {
+            return Err(
+                StdError::generic_err("max_slippage: The slippage should be lower than 1").into(),
+            );
+        }
+    }
+
+    let twap_schedule = TwapSchedule::new(slices, interval, &env.block)?;
+
+    // the pair is validated by the estimation, the slices are routed when they are executed
+    let querier = ElysQuerier::new(&deps.querier);
+    querier.amm_swap_estimation_by_denom(
+        &order_amount,
+        &order_source_denom,
+        &order_target_denom,
+        &Decimal::zero(),
+    )?;
+
+    let spot_order_max_id = SPOT_ORDER_MAX_ID.load(deps.storage)?;
+    let order_id = match spot_order_max_id.checked_add(1) {
+        Some(id) => id,
+        None => {
+            return Err(StdError::overflow(OverflowError::new(
+                cosmwasm_std::OverflowOperation::Add,
+                "spot_order_max_id",
+                "increment one",
+            ))
+            .into())
+        }
+    };
+    SPOT_ORDER_MAX_ID.save(deps.storage, &order_id)?;
+
+    // the price only records the pair, a twap order is executed at the market price
+    let order_price = OrderPrice {
+        base_denom: order_source_denom,
+        quote_denom: order_target_denom.clone(),
+        rate: Decimal::zero(),
+    };
+
+    let mut new_order = SpotOrder::new(
+        order_id,
+        SpotOrderType::Twap,
+        Some(order_price),
+        order_amount,
+        info.sender,
+        order_target_denom,
+        &env.block,
+    );
+
+    new_order.twap_schedule = Some(twap_schedule);
+    new_order.max_slippage = max_slippage;
+    new_order.expiration = expiration;
+
+    SPOT_ORDER.save(deps.storage, order_id, &new_order)?;
+    PENDING_SPOT_ORDER.save(deps.storage, order_id, &new_order)?;
+    log_spot_order(deps.storage, order_id, &env.block, OrderEvent::Created)?;
+
+    Ok(Response::new()
+        .add_event(Event::new("create_twap_order").add_attribute("order_id", order_id.to_string())))
+}
@@ -0,0 +1,133 @@
+use cosmwasm_std::{to_json_string, Coin, SignedDecimal, StdError};
+
+use super::*;
+use crate::helper::check_process_order_limit;
+
+// the fields of an update config message, a field left to none keeps its value
+pub struct ConfigUpdate {
+    pub admin: Option<String>,
+    pub account_history_address: Option<String>,
+    pub allowed_pairs: Option<Vec<TradingPair>>,
+    pub min_order_amounts: Option<Vec<Coin>>,
+    pub max_order_amounts: Option<Vec<Coin>>,
+    pub max_leverage: Option<SignedDecimal>,
+    pub pause: Option<PauseFlags>,
+    pub fee_bps: Option<u64>,
+    pub fee_collector: Option<String>,
+    pub allowed_denoms: Option<Vec<String>>,
+    pub validate_assets: Option<bool>,
+    pub process_order_limit: Option<u64>,
+}
+
+pub fn update_config(
+    info: MessageInfo,
+    deps: DepsMut<ElysQuery>,
+    update: ConfigUpdate,
+) -> Result<Response<ElysMsg>, ContractError> {
+    let ConfigUpdate {
+        admin,
+        account_history_address,
+        allowed_pairs,
+        min_order_amounts,
+        max_order_amounts,
+        max_leverage,
+        pause,
+        fee_bps,
+        fee_collector,
+        allowed_denoms,
+        validate_assets,
+        process_order_limit,
+    } = update;
+
+    let mut config = CONFIG.load(deps.storage)?;
+
+    if config.admin != info.sender {
+        return Err(ContractError::AdminUnauthorized {
+            sender: info.sender,
+        });
+    }
+
+    if let Some(admin) = admin {
+        config.admin = deps.api.addr_validate(&admin)?;
+    }
+    if let Some(account_history_address) = account_history_address {
+        config.account_history_address = Some(
+            deps.api
+                .addr_validate(&account_history_address)?
+                .to_string(),
+        );
+    }
+    if let Some(allowed_pairs) = allowed_pairs {
+        config.allowed_pairs = allowed_pairs;
+    }
+    if let Some(min_order_amounts) = min_order_amounts {
+        config.min_order_amounts = min_order_amounts;
+    }
+    if let Some(max_order_amounts) = max_order_amounts {
+        config.max_order_amounts = max_order_amounts;
+    }
+    // the minimum and maximum of a denom are compared once both lists are updated
+    for min in config.min_order_amounts.iter() {
+        if let Some(max) = config
+            .max_order_amounts
+            .iter()
+            .find(|max| max.denom == min.denom)
+        {
+            if min.amount > max.amount {
+                return Err(ContractError::OrderAmountRange {
+                    min: min.clone(),
+                    max: max.clone(),
+                });
+            }
+        }
+    }
+    if let Some(max_leverage) = max_leverage {
+        if max_leverage <= SignedDecimal::zero() {
+            return Err(ContractError::MaxLeverageNotPositive { max_leverage });
+        }
+        config.max_leverage = Some(max_leverage);
+    }
+    if let Some(pause) = pause {
+        config.pause = pause;
+    }
+
+    if let Some(fee_bps) = fee_bps {
+        if fee_bps > 10_000 {
+            return Err(StdError::generic_err(
+                "fee_bps: The fee cannot be larger than 10000 basis points",
+            )
+            .into());
+        }
+        config.fee_bps = fee_bps;
+    }
+    if let Some(fee_collector) = fee_collector {
+        config.fee_collector = Some(deps.api.addr_validate(&fee_collector)?);
+    }
+    if let Some(allowed_denoms) = allowed_denoms {
+        config.allowed_denoms = allowed_denoms;
+    }
+    if let Some(validate_assets) = validate_assets {
+        config.validate_assets = validate_assets;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    // the pages keep their position, only their size changes
+    if let Some(limit) = process_order_limit {
+        let limit = check_process_order_limit(limit)?;
+        for pagination in [
+            &PROCESS_SPOT_ORDER_PAGINATION,
+            &PROCESS_PERPETUAL_ORDER_PAGINATION,
+        ] {
+            let mut page = pagination.load(deps.storage)?;
+            page.limit = limit;
+            pagination.save(deps.storage, &page)?;
+        }
+    }
+
+    Ok(Response::new().add_event(
+        Event::new("update_config")
+            .add_attribute("admin", config.admin.to_string())
+            .add_attribute("pause", to_json_string(&config.pause)?),
+    ))
+}
@@ -0,0 +1,153 @@
+use cosmwasm_std::{SignedDecimal, SignedDecimal256, StdError, Uint128};
+use cw_utils::Expiration;
+use elys_bindings::query_resp::{Entry, QueryGetEntryResponse};
+
+use crate::helper::{
+    check_expiration, check_leverage, check_order_amount, get_discount, log_perpetual_order,
+    update_escrow,
+};
+
+use super::*;
+
+pub fn update_perpetual_order(
+    env: Env,
+    deps: DepsMut<ElysQuery>,
+    info: MessageInfo,
+    order_id: u64,
+    trigger_price: Option<OrderPrice>,
+    collateral: Option<Uint128>,
+    leverage: Option<SignedDecimal>,
+    take_profit_price: Option<SignedDecimal256>,
+    expiration: Option<Expiration>,
+) -> Result<Response<ElysMsg>, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let mut order: PerpetualOrder = match PERPETUAL_ORDER.may_load(deps.storage, order_id)? {
+        Some(order) => order,
+        None => return Err(ContractError::OrderNotFound { order_id }),
+    };
+
+    if order.owner != info.sender.as_str() {
+        return Err(ContractError::Unauthorized {
+            sender: info.sender,
+        });
+    }
+
+    if order.status != Status::Pending {
+        return Err(ContractError::UpdateStatusError {
+            order_id,
+            status: order.status,
+        });
+    }
+
+    check_expiration(&expiration, &env.block)?;
+
+    let querier = ElysQuerier::new(&deps.querier);
+
+    if let Some(trigger_price) = trigger_price {
+        if order.trailing_stop.is_some() {
+            return Err(
+                StdError::generic_err("trigger_price: Not used by TrailingStop orders").into(),
+            );
+        }
+
+        if order.order_type == PerpetualOrderType::HealthMonitor {
+            return Err(
+                StdError::generic_err("trigger_price: Not used by HealthMonitor orders").into(),
+            );
+        }
+
+        let QueryGetEntryResponse {
+            entry: Entry {
+                denom: usdc_denom, ..
+            },
+        } = querier.get_asset_profile("uusdc".to_string())?;
+
+        if trigger_price.rate.is_zero() {
+            return Err(StdError::generic_err("trigger_price: The rate cannot be zero").into());
+        }
+
+        if trigger_price.base_denom != usdc_denom {
+            return Err(StdError::generic_err(
+                "trigger_price: The base denom should be the usdc denom",
+            )
+            .into());
+        }
+
+        if trigger_price.quote_denom != order.trading_asset {
+            return Err(StdError::generic_err(
+                "trigger_price: The quote denom should be the trading asset denom",
+            )
+            .into());
+        }
+
+        order.trigger_price = Some(trigger_price);
+    }
+
+    let open_params_updated =
+        collateral.is_some() || leverage.is_some() || take_profit_price.is_some();
+
+    if open_params_updated && order.order_type != PerpetualOrderType::LimitOpen {
+        return Err(StdError::generic_err(
+            "collateral, leverage and take_profit_price: Only used by LimitOpen orders",
+        )
+        .into());
+    }
+
+    let refund_msg = update_escrow(&info, &order.collateral, collateral)?;
+
+    if let Some(collateral) = collateral {
+        if collateral.is_zero() {
+            return Err(StdError::generic_err("collateral: The amount cannot be zero").into());
+        }
+        order.collateral.amount = collateral;
+        check_order_amount(&config, &order.collateral)?;
+    }
+
+    if let Some(leverage) = leverage {
+        check_leverage(&config, &leverage)?;
+        order.leverage = leverage;
+    }
+
+    if take_profit_price.is_some() {
+        order.take_profit_price = take_profit_price;
+    }
+
+    if open_params_updated {
+        let open_estimation = querier.perpetual_open_estimation(
+            order.position.clone(),
+            order.leverage,
+            &order.trading_asset,
+            order.collateral.clone(),
+            order.take_profit_price,
+            get_discount(&deps.as_ref(), info.sender.to_string())?,
+        )?;
+
+        if !open_estimation.valid_collateral {
+            return Err(StdError::generic_err("not valid collateral").into());
+        }
+    }
+
+    if expiration.is_some() {
+        order.expiration = expiration;
+    }
+
+    PERPETUAL_ORDER.save(deps.storage, order_id, &order)?;
+    // the close orders of a bracket stay out of the pending orders until the position is opened
+    if PENDING_PERPETUAL_ORDER
+        .may_load(deps.storage, order_id)?
+        .is_some()
+    {
+        PENDING_PERPETUAL_ORDER.save(deps.storage, order_id, &order)?;
+    }
+    log_perpetual_order(deps.storage, order_id, &env.block, OrderEvent::Updated)?;
+
+    let resp = Response::new()
+        .add_event(
+            Event::new("update_perpetual_order")
+                .add_attribute("perpetual_order_id", order_id.to_string()),
+        )
+        .add_messages(refund_msg);
+
+    Ok(resp)
+}
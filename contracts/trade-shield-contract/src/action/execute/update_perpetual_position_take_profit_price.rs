@@ -0,0 +1,43 @@
+use cosmwasm_std::{SignedDecimal256, StdError};
+
+use super::*;
+
+pub fn update_perpetual_position_take_profit_price(
+    info: MessageInfo,
+    env: Env,
+    deps: DepsMut<ElysQuery>,
+    id: u64,
+    take_profit_price: SignedDecimal256,
+) -> Result<Response<ElysMsg>, ContractError> {
+    cw_utils::nonpayable(&info)?;
+
+    if take_profit_price <= SignedDecimal256::zero() {
+        return Err(
+            StdError::generic_err("take_profit_price: The price should be positive").into(),
+        );
+    }
+
+    let querier = ElysQuerier::new(&deps.querier);
+
+    if querier.mtp(info.sender.to_string(), id)?.mtp.is_none() {
+        return Err(StdError::not_found("perpetual trading position").into());
+    }
+
+    let msg = ElysMsg::perpetual_update_take_profit_price(
+        env.contract.address.as_str(),
+        id,
+        take_profit_price,
+        info.sender.as_str(),
+    );
+
+    let resp = Response::new()
+        .add_message(CosmosMsg::Custom(msg))
+        .add_event(
+            Event::new("update_perpetual_position_take_profit_price")
+                .add_attribute("position_id", id.to_string())
+                .add_attribute("owner", info.sender.as_str())
+                .add_attribute("take_profit_price", take_profit_price.to_string()),
+        );
+
+    Ok(resp)
+}
@@ -0,0 +1,108 @@
+use cosmwasm_std::{Decimal, StdError, Uint128};
+use cw_utils::Expiration;
+
+use crate::helper::{check_expiration, check_order_amount, log_spot_order, update_escrow};
+
+use super::*;
+
+pub fn update_spot_order(
+    env: Env,
+    deps: DepsMut<ElysQuery>,
+    info: MessageInfo,
+    order_id: u64,
+    order_price: Option<OrderPrice>,
+    order_amount: Option<Uint128>,
+    max_slippage: Option<Decimal>,
+    expiration: Option<Expiration>,
+) -> Result<Response<ElysMsg>, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let mut order: SpotOrder = match SPOT_ORDER.may_load(deps.storage, order_id)? {
+        Some(order) => order,
+        None => return Err(ContractError::OrderNotFound { order_id }),
+    };
+
+    if order.owner_address != info.sender {
+        return Err(ContractError::Unauthorized {
+            sender: info.sender,
+        });
+    }
+
+    if !order.is_pending() {
+        return Err(ContractError::UpdateStatusError {
+            order_id,
+            status: order.status,
+        });
+    }
+
+    check_expiration(&expiration, &env.block)?;
+
+    if let Some(order_price) = order_price {
+        if order.trailing_stop.is_some() || order.twap_schedule.is_some() {
+            return Err(StdError::generic_err(
+                "order_price: Not used by TrailingStop and Twap orders",
+            )
+            .into());
+        }
+
+        if order_price.rate.is_zero() {
+            return Err(StdError::generic_err("order_price: The rate cannot be zero").into());
+        }
+
+        if order_price.base_denom != order.order_amount.denom
+            || order_price.quote_denom != order.order_target_denom
+        {
+            return Err(ContractError::OrderPriceDenom);
+        }
+
+        order.order_price = order_price;
+    }
+
+    if let Some(max_slippage) = max_slippage {
+        if max_slippage >= Decimal::one() {
+            return Err(
+                StdError::generic_err("max_slippage: The slippage should be lower than 1").into(),
+            );
+        }
+        order.max_slippage = Some(max_slippage);
+    }
+
+    let refund_msg = update_escrow(&info, &order.order_amount, order_amount)?;
+
+    if let Some(order_amount) = order_amount {
+        let filled_amount = order.filled_amount.unwrap_or_default();
+        if order_amount <= filled_amount {
+            return Err(StdError::generic_err(
+                "order_amount: The amount should be greater than the filled amount",
+            )
+            .into());
+        }
+
+        if let Some(twap_schedule) = &order.twap_schedule {
+            let remaining_slices = twap_schedule.slices - twap_schedule.executed_slices;
+            if order_amount - filled_amount < Uint128::from(remaining_slices) {
+                return Err(StdError::generic_err(
+                    "order_amount: Every slice should swap at least one token",
+                )
+                .into());
+            }
+        }
+
+        order.order_amount.amount = order_amount;
+        check_order_amount(&config, &order.order_amount)?;
+    }
+
+    if expiration.is_some() {
+        order.expiration = expiration;
+    }
+
+    SPOT_ORDER.save(deps.storage, order_id, &order)?;
+    PENDING_SPOT_ORDER.save(deps.storage, order_id, &order)?;
+    log_spot_order(deps.storage, order_id, &env.block, OrderEvent::Updated)?;
+
+    let resp = Response::new()
+        .add_event(Event::new("update_spot_order").add_attribute("order_id", order_id.to_string()))
+        .add_messages(refund_msg);
+
+    Ok(resp)
+}
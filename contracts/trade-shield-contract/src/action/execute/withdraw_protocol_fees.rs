@@ -0,0 +1,46 @@
+use cosmwasm_std::{Coin, StdError, StdResult, Uint128};
+
+use super::*;
+
+pub fn withdraw_protocol_fees(
+    info: MessageInfo,
+    deps: DepsMut<ElysQuery>,
+) -> Result<Response<ElysMsg>, ContractError> {
+    cw_utils::nonpayable(&info)?;
+
+    let config = CONFIG.load(deps.storage)?;
+
+    if *config.fee_collector() != info.sender {
+        return Err(ContractError::FeeCollectorUnauthorized {
+            sender: info.sender,
+        });
+    }
+
+    let fees = PROTOCOL_FEES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|res| res.map(|(denom, amount): (String, Uint128)| Coin { denom, amount }))
+        .collect::<StdResult<Vec<Coin>>>()?;
+
+    if fees.is_empty() {
+        return Err(StdError::generic_err("no protocol fees to withdraw").into());
+    }
+
+    PROTOCOL_FEES.clear(deps.storage);
+
+    Ok(Response::new()
+        .add_event(
+            Event::new("withdraw_protocol_fees")
+                .add_attribute("fee_collector", info.sender.to_string())
+                .add_attribute(
+                    "amount",
+                    fees.iter()
+                        .map(|fee| fee.to_string())
+                        .collect::<Vec<String>>()
+                        .join(","),
+                ),
+        )
+        .add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: fees,
+        }))
+}
@@ -5,11 +5,14 @@ use elys_bindings::*;
 
 pub mod query {
     mod asset_info;
+    mod config;
     mod get_all_price;
+    mod get_order_history;
     mod get_perpetual_order;
     mod get_perpetual_orders;
     mod get_perpetual_position;
     mod get_perpetual_positions;
+    mod get_protocol_fees;
     mod get_spot_order;
     mod get_spot_orders;
     mod perpetual_get_position_for_address;
@@ -23,11 +26,14 @@ pub mod query {
     use elys_bindings::query_resp::*;
 
     pub use asset_info::asset_info;
+    pub use config::config;
     pub use get_all_price::get_all_prices;
+    pub use get_order_history::get_order_history;
     pub use get_perpetual_order::get_perpetual_order;
     pub use get_perpetual_orders::get_perpetual_orders;
     pub use get_perpetual_position::get_perpetual_position;
     pub use get_perpetual_positions::get_perpetual_positions;
+    pub use get_protocol_fees::get_protocol_fees;
     pub use get_spot_order::get_spot_order;
     pub use get_spot_orders::get_spot_orders;
     pub use perpetual_get_position_for_address::perpetual_get_position_for_address;
@@ -36,13 +42,21 @@ pub mod query {
 }
 
 pub mod execute {
+    mod add_perpetual_position_collateral;
     mod cancel_perpetual_order;
     mod cancel_perpetual_orders;
     mod cancel_spot_order;
     mod cancel_spot_orders;
     mod close_perpetual_position;
+    mod create_perpetual_bracket_order;
     mod create_perpetual_order;
     mod create_spot_order;
+    mod create_twap_order;
+    mod update_config;
+    mod update_perpetual_order;
+    mod update_perpetual_position_take_profit_price;
+    mod update_spot_order;
+    mod withdraw_protocol_fees;
 
     mod stake_request;
     mod unstake_request;
@@ -61,13 +75,21 @@ pub mod execute {
 
     use super::*;
 
+    pub use add_perpetual_position_collateral::add_perpetual_position_collateral;
     pub use cancel_perpetual_order::cancel_perpetual_order;
     pub use cancel_perpetual_orders::cancel_perpetual_orders;
     pub use cancel_spot_order::cancel_spot_order;
     pub use cancel_spot_orders::cancel_spot_orders;
     pub use close_perpetual_position::close_perpetual_position;
-    pub use create_perpetual_order::create_perpetual_order;
+    pub use create_perpetual_bracket_order::create_perpetual_bracket_order;
+    pub use create_perpetual_order::{create_perpetual_bracket_open_order, create_perpetual_order};
     pub use create_spot_order::create_spot_order;
+    pub use create_twap_order::create_twap_order;
+    pub use update_config::{update_config, ConfigUpdate};
+    pub use update_perpetual_order::update_perpetual_order;
+    pub use update_perpetual_position_take_profit_price::update_perpetual_position_take_profit_price;
+    pub use update_spot_order::update_spot_order;
+    pub use withdraw_protocol_fees::withdraw_protocol_fees;
 
     pub use claim_rewards_request::claim_rewards_request;
     pub use claim_validator_commission_request::claim_validator_commission_request;
@@ -87,6 +109,7 @@ pub mod reply {
     use super::*;
     use elys_bindings::msg_resp::*;
 
+    mod add_perpetual_position_collateral;
     mod close_perpetual_position;
     mod create_perpetual_order_market_close;
     mod create_perpetual_order_market_open;
@@ -94,6 +117,7 @@ pub mod reply {
     mod spot_order;
     mod spot_order_market;
 
+    pub use add_perpetual_position_collateral::reply_to_add_perpetual_position_collateral;
     pub use close_perpetual_position::reply_to_close_perpetual_order;
     pub use create_perpetual_order_market_close::reply_to_create_perpetual_market_close;
     pub use create_perpetual_order_market_open::reply_to_create_perpetual_market_open;
@@ -0,0 +1,7 @@
+use super::*;
+
+pub fn config(deps: Deps<ElysQuery>) -> Result<ConfigResp, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    Ok(ConfigResp { config })
+}
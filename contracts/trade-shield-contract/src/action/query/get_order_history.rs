@@ -0,0 +1,53 @@
+use cosmwasm_std::{StdResult, Storage};
+
+use super::*;
+use crate::helper::{page_entries, EntryRange};
+
+pub fn get_order_history(
+    deps: Deps<ElysQuery>,
+    order_id: u64,
+    order_kind: OrderKind,
+    pagination: Option<PageRequest>,
+) -> Result<GetOrderHistoryResp, ContractError> {
+    let order_found = match order_kind {
+        OrderKind::Spot => SPOT_ORDER.has(deps.storage, order_id),
+        OrderKind::Perpetual => PERPETUAL_ORDER.has(deps.storage, order_id),
+    };
+
+    if !order_found {
+        return Err(ContractError::OrderNotFound { order_id });
+    }
+
+    let (history, page_response) = page_entries(
+        pagination,
+        |pagination| range_history(deps.storage, order_id, &order_kind, pagination),
+        |_| true,
+    )?;
+
+    Ok(GetOrderHistoryResp {
+        page_response,
+        history,
+    })
+}
+
+// the entries of an order are ranged by their position in its history
+fn range_history<'a>(
+    storage: &'a dyn Storage,
+    order_id: u64,
+    order_kind: &OrderKind,
+    pagination: Option<&PageRequest>,
+) -> StdResult<EntryRange<'a, OrderHistoryEntry>> {
+    let (min, max, order) = match pagination {
+        Some(pagination) => pagination.bounds()?,
+        None => (None, None, Order::Ascending),
+    };
+
+    Ok(match order_kind {
+        OrderKind::Spot => SPOT_ORDER_HISTORY
+            .prefix(order_id)
+            .range(storage, min, max, order),
+        OrderKind::Perpetual => PERPETUAL_ORDER_HISTORY
+            .prefix(order_id)
+            .range(storage, min, max, order),
+    })
+}
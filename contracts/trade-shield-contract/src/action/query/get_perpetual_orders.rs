@@ -1,4 +1,7 @@
+use cosmwasm_std::{StdResult, Storage};
+
 use super::*;
+use crate::helper::{page_entries, EntryRange};
 
 pub fn get_perpetual_orders(
     deps: Deps<ElysQuery>,
@@ -7,56 +10,50 @@ pub fn get_perpetual_orders(
     order_type: Option<PerpetualOrderType>,
     order_status: Option<Status>,
 ) -> Result<GetPerpetualOrdersResp, ContractError> {
-    let orders: Vec<PerpetualOrder> = PERPETUAL_ORDER
-        .prefix_range(deps.storage, None, None, Order::Ascending)
-        .filter_map(|res| res.ok().map(|r| r.1))
-        .collect();
-
-    let (orders, page_response) = match pagination {
-        Some(pagination) => {
-            let (orders, page_resp) = pagination.filter(orders)?;
-            (orders, Some(page_resp))
-        }
-        None => (orders, None),
-    };
-
-    if orders.is_empty() {
-        return Ok(GetPerpetualOrdersResp {
-            page_response,
-            orders,
-        });
-    };
-
-    let orders: Vec<PerpetualOrder> = orders
-        .iter()
-        .filter(|order| {
-            order_owner
+    let matches = |order: &PerpetualOrder| {
+        order_type
+            .as_ref()
+            .map_or(true, |order_type| order_type == &order.order_type)
+            && order_status
                 .as_ref()
-                .map_or(true, |owner| owner == &order.owner)
-                && order_type
-                    .as_ref()
-                    .map_or(true, |order_type| order_type == &order.order_type)
-                && order_status
-                    .as_ref()
-                    .map_or(true, |status| &order.status == status)
-        })
-        .cloned()
-        .collect();
-
-    let page_response = if let Some(page_response) = page_response {
-        match page_response.total {
-            Some(_) => Some(PageResponse {
-                next_key: page_response.next_key,
-                total: Some(orders.len() as u64),
-            }),
-            None => Some(page_response),
-        }
-    } else {
-        None
+                .map_or(true, |status| &order.status == status)
     };
 
+    let (orders, page_response) = page_entries(
+        pagination,
+        |pagination| range_orders(deps.storage, &order_owner, &order_status, pagination),
+        matches,
+    )?;
+
     Ok(GetPerpetualOrdersResp {
         page_response,
         orders,
     })
 }
+
+// the orders of an owner are few, their status is checked after the owner index
+fn range_orders<'a>(
+    storage: &'a dyn Storage,
+    order_owner: &Option<String>,
+    order_status: &Option<Status>,
+    pagination: Option<&PageRequest>,
+) -> StdResult<EntryRange<'a, PerpetualOrder>> {
+    let (min, max, order) = match pagination {
+        Some(pagination) => pagination.bounds()?,
+        None => (None, None, Order::Ascending),
+    };
+
+    Ok(match (order_owner, order_status) {
+        (Some(owner), _) => PERPETUAL_ORDER
+            .idx
+            .owner
+            .prefix(owner.to_owned())
+            .range(storage, min, max, order),
+        (None, Some(status)) => PERPETUAL_ORDER
+            .idx
+            .status
+            .prefix(status.key())
+            .range(storage, min, max, order),
+        (None, None) => PERPETUAL_ORDER.range(storage, min, max, order),
+    })
+}
@@ -0,0 +1,12 @@
+use cosmwasm_std::{Coin, StdResult, Uint128};
+
+use super::*;
+
+pub fn get_protocol_fees(deps: Deps<ElysQuery>) -> Result<GetProtocolFeesResp, ContractError> {
+    let fees = PROTOCOL_FEES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|res| res.map(|(denom, amount): (String, Uint128)| Coin { denom, amount }))
+        .collect::<StdResult<Vec<Coin>>>()?;
+
+    Ok(GetProtocolFeesResp { fees })
+}
@@ -0,0 +1,78 @@
+use cosmwasm_std::{from_json, Binary, BlockInfo, SubMsgResult};
+
+use crate::helper::{
+    cancel_event, cancel_linked_perpetual_orders, log_perpetual_order, perpetual_order_event,
+};
+
+use super::*;
+
+pub fn reply_to_add_perpetual_position_collateral(
+    deps: DepsMut<ElysQuery>,
+    block: &BlockInfo,
+    data: Option<Binary>,
+    module_resp: SubMsgResult,
+) -> Result<Response<ElysMsg>, ContractError> {
+    let order_id: u64 = from_json(data.unwrap()).unwrap();
+
+    let mut order: PerpetualOrder = PERPETUAL_ORDER.load(deps.storage, order_id)?;
+
+    // the module answers without data, only the result of the message is checked
+    if let Err(error) = module_resp.into_result() {
+        let reason = CancelReason::CollateralAddFailed {
+            error: error.clone(),
+        };
+        order.cancel(reason.clone());
+        PERPETUAL_ORDER.save(deps.storage, order_id, &order)?;
+        PENDING_PERPETUAL_ORDER.remove(deps.storage, order.order_id)?;
+        let event = cancel_event(
+            perpetual_order_event("reply_to_add_perpetual_position_collateral", &order),
+            &reason,
+        )?;
+        log_perpetual_order(
+            deps.storage,
+            order_id,
+            block,
+            OrderEvent::Canceled { reason },
+        )?;
+        let refund_msgs = order.escrow().map(|escrow| BankMsg::Send {
+            to_address: order.owner.clone(),
+            amount: vec![escrow],
+        });
+        return Ok(Response::new()
+            .add_attribute("error", error)
+            .add_event(event)
+            .add_messages(refund_msgs));
+    }
+
+    order.status = Status::Executed;
+
+    PENDING_PERPETUAL_ORDER.remove(deps.storage, order.order_id)?;
+    PERPETUAL_ORDER.save(deps.storage, order_id, &order)?;
+
+    log_perpetual_order(
+        deps.storage,
+        order_id,
+        block,
+        OrderEvent::Executed {
+            fill_amount: order.escrowed_collateral.clone(),
+            data: None,
+        },
+    )?;
+
+    let (refund_msgs, linked_events) = cancel_linked_perpetual_orders(deps.storage, block, &order)?;
+
+    let resp: Response<ElysMsg> = Response::new()
+        .add_event(
+            perpetual_order_event("reply_to_add_perpetual_position_collateral", &order)
+                .add_attribute("result", "executed")
+                .add_attribute("perpetual_order_id", order_id.to_string())
+                .add_attribute(
+                    "perpetual_collateral_added",
+                    order.escrowed_collateral.unwrap_or_default().to_string(),
+                ),
+        )
+        .add_events(linked_events)
+        .add_messages(refund_msgs);
+
+    Ok(resp)
+}
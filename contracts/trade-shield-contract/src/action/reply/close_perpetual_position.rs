@@ -1,11 +1,15 @@
-use cosmwasm_std::{from_json, Binary, SubMsgResult};
+use cosmwasm_std::{from_json, to_json_binary, Binary, BlockInfo, SubMsgResult};
 
-use crate::helper::get_response_from_reply;
+use crate::helper::{
+    cancel_event, cancel_linked_perpetual_orders, get_position_custody, get_reply_error,
+    get_response_from_reply, log_perpetual_order, perpetual_order_event, resize_close_orders,
+};
 
 use super::*;
 
 pub fn reply_to_close_perpetual_order(
     deps: DepsMut<ElysQuery>,
+    block: &BlockInfo,
     data: Option<Binary>,
     module_resp: SubMsgResult,
 ) -> Result<Response<ElysMsg>, ContractError> {
@@ -16,24 +20,67 @@ pub fn reply_to_close_perpetual_order(
     let res: PerpetualCloseResponse = match get_response_from_reply(module_resp) {
         Ok(expr) => expr,
         Err(err) => {
-            order.status = Status::Canceled;
+            let reason = CancelReason::PositionCloseFailed {
+                error: get_reply_error(&err),
+            };
+            order.cancel(reason.clone());
             PERPETUAL_ORDER.save(deps.storage, order_id, &order)?;
-            PENDING_PERPETUAL_ORDER.remove(deps.storage, order.order_id);
-            return Ok(err);
+            PENDING_PERPETUAL_ORDER.remove(deps.storage, order.order_id)?;
+            let event = cancel_event(
+                perpetual_order_event("reply_to_close_perpetual_order", &order),
+                &reason,
+            )?;
+            log_perpetual_order(
+                deps.storage,
+                order_id,
+                block,
+                OrderEvent::Canceled { reason },
+            )?;
+            return Ok(err.add_event(event));
         }
     };
 
     order.status = Status::Executed;
 
-    PENDING_PERPETUAL_ORDER.remove(deps.storage, order.order_id);
+    PENDING_PERPETUAL_ORDER.remove(deps.storage, order.order_id)?;
     PERPETUAL_ORDER.save(deps.storage, order_id, &order)?;
 
-    let resp: Response<ElysMsg> = Response::new().add_event(
-        Event::new("reply_to_close_perpetual_order")
-            .add_attribute("perpetual_order_id", order_id.to_string())
-            .add_attribute("perpetual_trading_position_closed_id", res.id.to_string())
-            .add_attribute("perpetual_amount_closed", res.amount.i128().to_string()),
-    );
+    log_perpetual_order(
+        deps.storage,
+        order_id,
+        block,
+        OrderEvent::Executed {
+            fill_amount: None,
+            data: Some(to_json_binary(&res)?),
+        },
+    )?;
+
+    // a partial close leaves the position open, its linked orders keep protecting what is left
+    let custody = get_position_custody(&ElysQuerier::new(&deps.querier), &order);
+
+    let (refund_msgs, mut linked_events) = if custody.is_some() {
+        (vec![], vec![])
+    } else {
+        cancel_linked_perpetual_orders(deps.storage, block, &order)?
+    };
+    linked_events.extend(resize_close_orders(
+        deps.storage,
+        block,
+        &order,
+        res.amount,
+        custody,
+    )?);
+
+    let resp: Response<ElysMsg> = Response::new()
+        .add_event(
+            perpetual_order_event("reply_to_close_perpetual_order", &order)
+                .add_attribute("result", "executed")
+                .add_attribute("perpetual_order_id", order_id.to_string())
+                .add_attribute("perpetual_trading_position_closed_id", res.id.to_string())
+                .add_attribute("perpetual_amount_closed", res.amount.i128().to_string()),
+        )
+        .add_events(linked_events)
+        .add_messages(refund_msgs);
 
     Ok(resp)
 }
@@ -1,8 +1,12 @@
 use super::*;
-use cosmwasm_std::{from_json, Binary, StdError, SubMsgResult};
+use crate::helper::{
+    get_position_custody, log_perpetual_order, perpetual_order_event, resize_close_orders,
+};
+use cosmwasm_std::{from_json, Binary, BlockInfo, StdError, SubMsgResult};
 
 pub fn reply_to_create_perpetual_market_close(
     deps: DepsMut<ElysQuery>,
+    block: &BlockInfo,
     data: Option<Binary>,
     module_resp: SubMsgResult,
 ) -> Result<Response<ElysMsg>, ContractError> {
@@ -25,27 +29,44 @@ pub fn reply_to_create_perpetual_market_close(
         None => return Err(ContractError::OrderNotFound { order_id }),
     };
 
-    let perpetual_resp: PerpetualCloseResponse = match from_json(&resp_data.unwrap()) {
+    let resp_data = resp_data.unwrap();
+    let perpetual_resp: PerpetualCloseResponse = match from_json(&resp_data) {
         Ok(resp) => resp,
         Err(err) => return Err(err.into()),
     };
 
     order.status = Status::Executed;
 
-    let resp = Response::new().add_event(
-        Event::new("reply_to_create_perpetual_market_close")
-            .add_attribute("perpetual_order_id", order_id.to_string())
-            .add_attribute(
-                "perpetual_trading_position_closed_id",
-                perpetual_resp.id.to_string(),
-            )
-            .add_attribute(
-                "perpetual_amount_closed",
-                perpetual_resp.amount.i128().to_string(),
-            ),
-    );
+    let custody = get_position_custody(&ElysQuerier::new(&deps.querier), &order);
+    let resize_events =
+        resize_close_orders(deps.storage, block, &order, perpetual_resp.amount, custody)?;
+
+    let resp = Response::new()
+        .add_event(
+            perpetual_order_event("reply_to_create_perpetual_market_close", &order)
+                .add_attribute("result", "executed")
+                .add_attribute("perpetual_order_id", order_id.to_string())
+                .add_attribute(
+                    "perpetual_trading_position_closed_id",
+                    perpetual_resp.id.to_string(),
+                )
+                .add_attribute(
+                    "perpetual_amount_closed",
+                    perpetual_resp.amount.i128().to_string(),
+                ),
+        )
+        .add_events(resize_events);
 
     PERPETUAL_ORDER.save(deps.storage, order_id, &order)?;
+    log_perpetual_order(
+        deps.storage,
+        order_id,
+        block,
+        OrderEvent::Executed {
+            fill_amount: None,
+            data: Some(resp_data),
+        },
+    )?;
 
     Ok(resp)
 }
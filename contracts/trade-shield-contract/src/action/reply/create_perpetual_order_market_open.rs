@@ -1,8 +1,10 @@
 use super::*;
-use cosmwasm_std::{from_json, Binary, StdError, SubMsgResult};
+use crate::helper::{activate_child_perpetual_orders, log_perpetual_order, perpetual_order_event};
+use cosmwasm_std::{from_json, Binary, BlockInfo, StdError, SubMsgResult};
 
 pub fn reply_to_create_perpetual_market_open(
     deps: DepsMut<ElysQuery>,
+    block: &BlockInfo,
     data: Option<Binary>,
     module_resp: SubMsgResult,
 ) -> Result<Response<ElysMsg>, ContractError> {
@@ -25,7 +27,8 @@ pub fn reply_to_create_perpetual_market_open(
         None => return Err(ContractError::OrderNotFound { order_id }),
     };
 
-    let perpetual_resp: PerpetualOpenResponse = match from_json(&resp_data.unwrap()) {
+    let resp_data = resp_data.unwrap();
+    let perpetual_resp: PerpetualOpenResponse = match from_json(&resp_data) {
         Ok(resp) => resp,
         Err(err) => return Err(err.into()),
     };
@@ -34,9 +37,20 @@ pub fn reply_to_create_perpetual_market_open(
     order.position_id = Some(perpetual_resp.id);
 
     PERPETUAL_ORDER.save(deps.storage, order_id, &order)?;
+    activate_child_perpetual_orders(deps.storage, &order)?;
+    log_perpetual_order(
+        deps.storage,
+        order_id,
+        block,
+        OrderEvent::Executed {
+            fill_amount: Some(order.collateral.clone()),
+            data: Some(resp_data),
+        },
+    )?;
 
     let resp = Response::new().add_event(
-        Event::new("reply_to_create_perpetual_market_open")
+        perpetual_order_event("reply_to_create_perpetual_market_open", &order)
+            .add_attribute("result", "executed")
             .add_attribute("perpetual_order_id", order_id.to_string())
             .add_attribute(
                 "perpetual_trading_position_opened_id",
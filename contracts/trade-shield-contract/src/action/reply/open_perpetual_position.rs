@@ -1,39 +1,87 @@
-use cosmwasm_std::{from_json, Binary, SubMsgResult};
+use cosmwasm_std::{from_json, to_json_binary, Binary, BlockInfo, Coin, SubMsgResult};
 
-use crate::helper::get_response_from_reply;
+use crate::helper::{
+    accrue_protocol_fee, activate_child_perpetual_orders, cancel_child_perpetual_orders,
+    cancel_event, cancel_linked_perpetual_orders, get_reply_error, get_response_from_reply,
+    log_perpetual_order, perpetual_order_event,
+};
 
 use super::*;
 
 pub fn reply_to_open_perpetual_position(
     deps: DepsMut<ElysQuery>,
+    block: &BlockInfo,
     data: Option<Binary>,
     module_resp: SubMsgResult,
 ) -> Result<Response<ElysMsg>, ContractError> {
-    let order_id: u64 = from_json(&data.unwrap()).unwrap();
+    let (order_id, fee): (u64, Coin) = from_json(&data.unwrap()).unwrap();
 
     let mut order: PerpetualOrder = PERPETUAL_ORDER.load(deps.storage, order_id)?;
 
     let res: PerpetualOpenResponse = match get_response_from_reply(module_resp) {
         Ok(expr) => expr,
         Err(err) => {
-            order.status = Status::Canceled;
+            let reason = CancelReason::PositionOpenFailed {
+                error: get_reply_error(&err),
+            };
+            order.cancel(reason.clone());
             PERPETUAL_ORDER.save(deps.storage, order_id, &order)?;
-            PENDING_PERPETUAL_ORDER.remove(deps.storage, order.order_id);
-            return Ok(err);
+            PENDING_PERPETUAL_ORDER.remove(deps.storage, order.order_id)?;
+            let event = cancel_event(
+                perpetual_order_event("reply_to_open_perpetual_position", &order),
+                &reason,
+            )?;
+            log_perpetual_order(
+                deps.storage,
+                order_id,
+                block,
+                OrderEvent::Canceled { reason },
+            )?;
+            let child_events = cancel_child_perpetual_orders(deps.storage, block, order_id)?;
+            // the fee is only accrued once the position is opened, the whole collateral is refunded
+            return Ok(err
+                .add_event(event)
+                .add_events(child_events)
+                .add_message(BankMsg::Send {
+                    to_address: order.owner,
+                    amount: vec![order.collateral],
+                }));
         }
     };
 
     order.status = Status::Executed;
     order.position_id = Some(res.id);
+    if !fee.amount.is_zero() {
+        accrue_protocol_fee(deps.storage, &fee)?;
+        order.fee = Some(fee.clone());
+    }
 
-    PENDING_PERPETUAL_ORDER.remove(deps.storage, order.order_id);
+    PENDING_PERPETUAL_ORDER.remove(deps.storage, order.order_id)?;
     PERPETUAL_ORDER.save(deps.storage, order_id, &order)?;
 
-    let resp: Response<ElysMsg> = Response::new().add_event(
-        Event::new("reply_to_open_perpetual_position")
-            .add_attribute("perpetual_order_id", order_id.to_string())
-            .add_attribute("perpetual_trading_position_opened_id", res.id.to_string()),
-    );
+    log_perpetual_order(
+        deps.storage,
+        order_id,
+        block,
+        OrderEvent::Executed {
+            fill_amount: Some(order.collateral.clone()),
+            data: Some(to_json_binary(&res)?),
+        },
+    )?;
+
+    activate_child_perpetual_orders(deps.storage, &order)?;
+    let (refund_msgs, linked_events) = cancel_linked_perpetual_orders(deps.storage, block, &order)?;
+
+    let resp: Response<ElysMsg> = Response::new()
+        .add_event(
+            perpetual_order_event("reply_to_open_perpetual_position", &order)
+                .add_attribute("result", "executed")
+                .add_attribute("perpetual_order_id", order_id.to_string())
+                .add_attribute("perpetual_trading_position_opened_id", res.id.to_string())
+                .add_attribute("fee", fee.to_string()),
+        )
+        .add_events(linked_events)
+        .add_messages(refund_msgs);
 
     Ok(resp)
 }
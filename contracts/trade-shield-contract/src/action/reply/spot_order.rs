@@ -1,40 +1,100 @@
-use cosmwasm_std::{from_json, Binary, DepsMut, SubMsgResult};
+use cosmwasm_std::{
+    coin, from_json, to_json_binary, Binary, BlockInfo, DepsMut, StdError, SubMsgResult, Uint128,
+};
 
-use crate::helper::get_response_from_reply;
+use crate::helper::{
+    accrue_protocol_fee, cancel_event, cancel_linked_spot_orders, get_reply_error,
+    get_response_from_reply, log_spot_order, spot_order_event,
+};
 
 use super::*;
 
 pub fn reply_to_spot_order(
     deps: DepsMut<ElysQuery>,
+    block: &BlockInfo,
     data: Option<Binary>,
     module_resp: SubMsgResult,
 ) -> Result<Response<ElysMsg>, ContractError> {
-    let order_id: u64 = from_json(&data.unwrap()).unwrap();
+    let (order_id, fill_amount, fee): (u64, Uint128, Uint128) = from_json(&data.unwrap()).unwrap();
 
     let mut order = SPOT_ORDER.load(deps.storage, order_id)?;
 
-    let _: AmmSwapExactAmountInResp = match get_response_from_reply(module_resp) {
+    let swap_resp: AmmSwapExactAmountInResp = match get_response_from_reply(module_resp) {
         Ok(expr) => expr,
         Err(err) => {
-            order.status = Status::Canceled;
+            let reason = CancelReason::SwapFailed {
+                error: get_reply_error(&err),
+            };
+            order.cancel(reason.clone());
             SPOT_ORDER.save(deps.storage, order_id, &order)?;
-            PENDING_SPOT_ORDER.remove(deps.storage, order.order_id);
+            PENDING_SPOT_ORDER.remove(deps.storage, order.order_id)?;
+            let event = cancel_event(spot_order_event("reply_to_spot_order", &order), &reason)?;
+            log_spot_order(
+                deps.storage,
+                order_id,
+                block,
+                OrderEvent::Canceled { reason },
+            )?;
 
-            return Ok(err.add_message(BankMsg::Send {
+            return Ok(err.add_event(event).add_message(BankMsg::Send {
                 to_address: order.owner_address.to_string(),
-                amount: vec![order.order_amount],
+                amount: vec![order.remaining_amount()],
             }));
         }
     };
 
-    order.status = Status::Executed;
+    order.fill(fill_amount);
+
+    let fee = coin(fee.u128(), &order.order_amount.denom);
+    order.pay_fee(&fee);
+    accrue_protocol_fee(deps.storage, &fee)?;
+
+    if let Some(twap_schedule) = order.twap_schedule.as_mut() {
+        let token_out_amount = u128::try_from(swap_resp.token_out_amount.i64()).map_err(|_| {
+            StdError::generic_err("token_out_amount: The swap output cannot be negative")
+        })?;
+        twap_schedule.record_slice(
+            order.filled_amount.unwrap_or_default(),
+            Uint128::new(token_out_amount),
+            block,
+        );
+    }
 
     SPOT_ORDER.save(deps.storage, order_id, &order)?;
-    PENDING_SPOT_ORDER.remove(deps.storage, order.order_id);
+    if order.status == Status::Executed {
+        PENDING_SPOT_ORDER.remove(deps.storage, order.order_id)?;
+    } else {
+        PENDING_SPOT_ORDER.save(deps.storage, order.order_id, &order)?;
+    }
+
+    log_spot_order(
+        deps.storage,
+        order_id,
+        block,
+        OrderEvent::Executed {
+            fill_amount: Some(coin(fill_amount.u128(), &order.order_amount.denom)),
+            data: Some(to_json_binary(&swap_resp)?),
+        },
+    )?;
+
+    let (refund_msgs, linked_events) = cancel_linked_spot_orders(deps.storage, block, &order)?;
+
+    let result = if order.status == Status::Executed {
+        "executed"
+    } else {
+        "partially_filled"
+    };
 
-    let resp: Response<ElysMsg> = Response::new().add_event(
-        Event::new("reply_to_spot_order").add_attribute("order_id", order_id.to_string()),
-    );
+    let resp: Response<ElysMsg> = Response::new()
+        .add_event(
+            spot_order_event("reply_to_spot_order", &order)
+                .add_attribute("result", result)
+                .add_attribute("fill_amount", fill_amount.to_string())
+                .add_attribute("amount_out", swap_resp.token_out_amount.to_string())
+                .add_attribute("fee", fee.to_string()),
+        )
+        .add_events(linked_events)
+        .add_messages(refund_msgs);
 
     Ok(resp)
 }
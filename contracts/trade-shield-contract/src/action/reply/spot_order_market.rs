@@ -1,14 +1,18 @@
-use cosmwasm_std::{from_json, Binary, DepsMut, StdError, SubMsgResult};
+use cosmwasm_std::{from_json, Binary, BlockInfo, DepsMut, StdError, SubMsgResult};
+
+use crate::helper::{log_spot_order, spot_order_event};
 
 use super::*;
 
 pub fn reply_to_spot_order_market(
     deps: DepsMut<ElysQuery>,
+    block: &BlockInfo,
     data: Option<Binary>,
     module_resp: SubMsgResult,
 ) -> Result<Response<ElysMsg>, ContractError> {
-    if let Err(err) = module_resp.into_result() {
-        return Err(StdError::generic_err(err).into());
+    let resp_data = match module_resp.into_result() {
+        Ok(resp) => resp.data,
+        Err(err) => return Err(StdError::generic_err(err).into()),
     };
 
     let order_id: u64 = match data {
@@ -21,9 +25,18 @@ pub fn reply_to_spot_order_market(
     order.status = Status::Executed;
 
     SPOT_ORDER.save(deps.storage, order_id, &order)?;
+    log_spot_order(
+        deps.storage,
+        order_id,
+        block,
+        OrderEvent::Executed {
+            fill_amount: Some(order.order_amount.clone()),
+            data: resp_data,
+        },
+    )?;
 
     let resp: Response<ElysMsg> = Response::new().add_event(
-        Event::new("reply_to_spot_order_market").add_attribute("order_id", order_id.to_string()),
+        spot_order_event("reply_to_spot_order_market", &order).add_attribute("result", "executed"),
     );
 
     Ok(resp)
@@ -1,30 +1,41 @@
-use crate::{helper::get_discount, msg::ReplyType};
+use crate::{
+    helper::{
+        cancel_child_perpetual_orders, cancel_event, get_discount, get_protocol_fee,
+        log_perpetual_order, log_spot_order, perpetual_order_event, spot_order_event,
+    },
+    msg::ReplyType,
+};
 use cosmwasm_std::{
-    to_json_binary, Decimal, Int128, OverflowError, StdError, StdResult, Storage, SubMsg,
+    coin, to_json_binary, BlockInfo, Coin, Decimal, Int128, OverflowError, SignedDecimal, StdError,
+    StdResult, Storage, SubMsg,
 };
+use cw_utils::Expiration;
 use elys_bindings::query_resp::{AmmSwapEstimationByDenomResponse, Entry, QueryGetEntryResponse};
 
 use super::*;
+use std::collections::HashMap;
+
+// slippage tolerated on a limit order when the owner did not set one
+const DEFAULT_MAX_SLIPPAGE: Decimal = Decimal::percent(1);
 
 pub fn process_orders(
     deps: DepsMut<ElysQuery>,
     env: Env,
 ) -> Result<Response<ElysMsg>, ContractError> {
-    let spot_orders: Vec<SpotOrder> = PENDING_SPOT_ORDER
-        .prefix_range(deps.storage, None, None, Order::Ascending)
-        .filter_map(|res| res.ok().map(|r| r.1))
-        .collect();
+    let config = CONFIG.load(deps.storage)?;
 
-    let perpetual_orders: Vec<PerpetualOrder> = PENDING_PERPETUAL_ORDER
-        .prefix_range(deps.storage, None, None, Order::Ascending)
-        .filter_map(|res| res.ok().map(|r| r.1))
-        .collect();
+    // the pending orders stay untouched until the processing is resumed
+    if config.pause.process_orders {
+        return Ok(Response::new());
+    }
 
     let mut reply_info_id = MAX_REPLY_ID.load(deps.storage)?;
 
     let querier = ElysQuerier::new(&deps.querier);
     let mut submsgs: Vec<SubMsg<ElysMsg>> = vec![];
     let mut bank_msgs: Vec<BankMsg> = vec![];
+    let mut events: Vec<Event> = vec![];
+    let mut market_prices: HashMap<(String, String), Decimal> = HashMap::new();
 
     let QueryGetEntryResponse {
         entry: Entry {
@@ -32,165 +43,565 @@ pub fn process_orders(
         },
     } = querier.get_asset_profile("uusdc".to_string())?;
 
-    for spot_order in spot_orders.iter() {
-        if spot_order.order_price.base_denom != spot_order.order_amount.denom
+    // a page of the pending orders is checked every block to cancel the expired or invalid ones
+    let mut spot_pagination = PROCESS_SPOT_ORDER_PAGINATION.load(deps.storage)?;
+    let spot_orders = spot_pagination.load_next_page(deps.storage, &PENDING_SPOT_ORDER)?;
+    PROCESS_SPOT_ORDER_PAGINATION.save(deps.storage, &spot_pagination)?;
+
+    let mut perpetual_pagination = PROCESS_PERPETUAL_ORDER_PAGINATION.load(deps.storage)?;
+    let perpetual_orders =
+        perpetual_pagination.load_next_page(deps.storage, &PENDING_PERPETUAL_ORDER)?;
+    PROCESS_PERPETUAL_ORDER_PAGINATION.save(deps.storage, &perpetual_pagination)?;
+
+    for mut spot_order in spot_orders {
+        let market_price = get_market_price(
+            &querier,
+            &mut market_prices,
+            &spot_order.order_amount.denom,
+            &spot_order.order_target_denom,
+        );
+
+        let cancel_reason = if is_expired(&spot_order.expiration, &env) {
+            Some(CancelReason::Expired)
+        } else if spot_order.order_price.base_denom != spot_order.order_amount.denom
             || spot_order.order_price.quote_denom != spot_order.order_target_denom
         {
-            let mut order = spot_order.to_owned();
-            order.status = Status::Canceled;
-            bank_msgs.push(BankMsg::Send {
-                to_address: order.owner_address.to_string(),
-                amount: vec![order.order_amount.clone()],
-            });
-            PENDING_SPOT_ORDER.remove(deps.storage, order.order_id);
-            SPOT_ORDER.save(deps.storage, order.order_id, &order)?;
+            Some(CancelReason::DenomMismatch)
+        } else if market_price.is_err() {
+            Some(CancelReason::PriceUnavailable)
+        } else {
+            None
+        };
+
+        if let Some(reason) = cancel_reason {
+            events.push(cancel_pending_spot_order(
+                deps.storage,
+                &env.block,
+                &mut spot_order,
+                reason,
+                &mut bank_msgs,
+            )?);
             continue;
         }
 
-        let discount = get_discount(&deps.as_ref(), spot_order.owner_address.to_string())?;
+        // a trailing stop follows the market while it is on the page, before the crossed orders
+        // are loaded so that it is triggered at its current stop price
+        if let Ok(market_price) = market_price {
+            if spot_order.follow_market(market_price) {
+                SPOT_ORDER.save(deps.storage, spot_order.order_id, &spot_order)?;
+                PENDING_SPOT_ORDER.save(deps.storage, spot_order.order_id, &spot_order)?;
+            }
+        }
+    }
+
+    let mut unhealthy_perpetual_orders: Vec<(PerpetualOrder, SignedDecimal, Decimal)> = vec![];
+    let mut monitor_checks: usize = 0;
 
-        let amm_swap_estimation = match querier.amm_swap_estimation_by_denom(
-            &spot_order.order_amount,
-            &spot_order.order_amount.denom,
-            &spot_order.order_target_denom,
-            &discount,
-        ) {
-            Ok(market_price) => market_price,
-            Err(_) => {
-                let mut order = spot_order.to_owned();
-                order.status = Status::Canceled;
-                bank_msgs.push(BankMsg::Send {
-                    to_address: order.owner_address.to_string(),
-                    amount: vec![order.order_amount.clone()],
-                });
-                PENDING_SPOT_ORDER.remove(deps.storage, order.order_id);
-                SPOT_ORDER.save(deps.storage, order.order_id, &order)?;
-                continue;
+    for mut order in perpetual_orders {
+        if is_expired(&order.expiration, &env) {
+            events.push(cancel_pending_perpetual_order(
+                deps.storage,
+                &env.block,
+                &mut order,
+                CancelReason::Expired,
+                &mut bank_msgs,
+            )?);
+            events.extend(cancel_child_perpetual_orders(
+                deps.storage,
+                &env.block,
+                order.order_id,
+            )?);
+            continue;
+        }
+
+        // health monitors have no trigger price, the health of their position is checked while
+        // they are on the page and each check counts against the orders processed in the block
+        if order.order_type == PerpetualOrderType::HealthMonitor {
+            monitor_checks += 1;
+            let mtp = querier
+                .mtp(order.owner.clone(), order.position_id.unwrap())
+                .ok()
+                .and_then(|resp| resp.mtp);
+            match mtp {
+                Some(mtp) => {
+                    if order
+                        .health_threshold
+                        .is_some_and(|threshold| mtp.mtp_health < threshold)
+                    {
+                        let market_price = get_market_price(
+                            &querier,
+                            &mut market_prices,
+                            &order.collateral.denom,
+                            &order.trading_asset,
+                        )
+                        .unwrap_or_default();
+                        unhealthy_perpetual_orders.push((order, mtp.mtp_health, market_price));
+                    }
+                }
+                None => events.push(cancel_pending_perpetual_order(
+                    deps.storage,
+                    &env.block,
+                    &mut order,
+                    CancelReason::PositionNotFound,
+                    &mut bank_msgs,
+                )?),
             }
+            continue;
+        }
+
+        let market_price = get_market_price(
+            &querier,
+            &mut market_prices,
+            &order.collateral.denom,
+            &order.trading_asset,
+        );
+
+        let cancel_reason = if order.trigger_price.as_ref().unwrap().base_denom != usdc_denom
+            || order.trigger_price.as_ref().unwrap().quote_denom != order.trading_asset
+        {
+            Some(CancelReason::DenomMismatch)
+        } else if market_price.is_err() {
+            Some(CancelReason::PriceUnavailable)
+        } else {
+            None
         };
 
-        let market_price = match querier.get_asset_price_from_denom_in_to_denom_out(
-            &spot_order.order_amount.denom,
-            &spot_order.order_target_denom,
+        if let Some(reason) = cancel_reason {
+            events.push(cancel_pending_perpetual_order(
+                deps.storage,
+                &env.block,
+                &mut order,
+                reason,
+                &mut bank_msgs,
+            )?);
+            continue;
+        }
+
+        if let Ok(market_price) = market_price {
+            if order.follow_market(market_price) {
+                PERPETUAL_ORDER.save(deps.storage, order.order_id, &order)?;
+                PENDING_PERPETUAL_ORDER.save(deps.storage, order.order_id, &order)?;
+            }
+        }
+    }
+
+    // only one order of a linked group can be triggered per block,
+    // the others are canceled by the reply of the triggered one
+    let mut triggered_spot_groups: Vec<u64> = vec![];
+    let mut triggered_perpetual_groups: Vec<u64> = vec![];
+
+    let spot_books = order_books(
+        deps.storage,
+        &PENDING_SPOT_ORDER.idx.order_book,
+        spot_order_book_key,
+    )?;
+    let perpetual_books = order_books(
+        deps.storage,
+        &PENDING_PERPETUAL_ORDER.idx.order_book,
+        perpetual_order_book_key,
+    )?;
+
+    // only the orders crossed by the market price are loaded from the order books
+    let mut remaining = spot_pagination.limit as usize;
+
+    for book in spot_books {
+        if remaining == 0 {
+            break;
+        }
+
+        let market_price = match get_market_price(
+            &querier,
+            &mut market_prices,
+            &book.order_amount.denom,
+            &book.order_target_denom,
         ) {
             Ok(market_price) => market_price,
-            Err(_) => {
-                let mut order = spot_order.to_owned();
-                order.status = Status::Canceled;
-                bank_msgs.push(BankMsg::Send {
-                    to_address: order.owner_address.to_string(),
-                    amount: vec![order.order_amount.clone()],
-                });
-                PENDING_SPOT_ORDER.remove(deps.storage, order.order_id);
-                SPOT_ORDER.save(deps.storage, order.order_id, &order)?;
+            Err(_) => continue,
+        };
+
+        let (market_rate, rate_below) = match book.order_type {
+            SpotOrderType::LimitSell => (market_price, true),
+            SpotOrderType::LimitBuy => match Decimal::one().checked_div(market_price) {
+                Ok(market_rate) => (market_rate, false),
+                Err(_) => continue,
+            },
+            SpotOrderType::StopLoss | SpotOrderType::TrailingStop => (market_price, false),
+            SpotOrderType::Twap => match &book.twap_schedule {
+                Some(twap_schedule) => (twap_schedule.block_rate(&env.block), true),
+                None => continue,
+            },
+            SpotOrderType::MarketBuy => continue,
+        };
+
+        let crossed_spot_orders = crossed_orders(
+            deps.storage,
+            &PENDING_SPOT_ORDER.idx.order_book,
+            spot_order_book_key(&[], &book),
+            market_rate,
+            rate_below,
+            remaining,
+        )?;
+
+        for mut spot_order in crossed_spot_orders {
+            if let Some(group_id) = spot_order.group_id {
+                if triggered_spot_groups.contains(&group_id) {
+                    continue;
+                }
+            }
+
+            // orders off the page checked above can expire while crossed by the market
+            if is_expired(&spot_order.expiration, &env) {
+                events.push(cancel_pending_spot_order(
+                    deps.storage,
+                    &env.block,
+                    &mut spot_order,
+                    CancelReason::Expired,
+                    &mut bank_msgs,
+                )?);
                 continue;
             }
-        };
 
-        if check_spot_order(&spot_order, market_price) {
-            process_spot_order(
-                spot_order,
+            let triggered = match &spot_order.twap_schedule {
+                Some(twap_schedule) => twap_schedule.is_due(&env.block),
+                None => check_spot_order(&spot_order, market_price),
+            };
+            if !triggered {
+                continue;
+            }
+
+            let discount = get_discount(&deps.as_ref(), spot_order.owner_address.to_string())?;
+
+            let amm_swap_estimation = match querier.amm_swap_estimation_by_denom(
+                &spot_order.remaining_amount(),
+                &spot_order.order_amount.denom,
+                &spot_order.order_target_denom,
+                &discount,
+            ) {
+                Ok(amm_swap_estimation) => amm_swap_estimation,
+                Err(err) => {
+                    events.push(cancel_pending_spot_order(
+                        deps.storage,
+                        &env.block,
+                        &mut spot_order,
+                        CancelReason::SwapEstimationFailed {
+                            error: err.to_string(),
+                        },
+                        &mut bank_msgs,
+                    )?);
+                    continue;
+                }
+            };
+
+            let outcome = process_spot_order(
+                &spot_order,
                 &mut submsgs,
                 env.contract.address.as_str(),
                 &mut reply_info_id,
                 amm_swap_estimation,
                 deps.storage,
+                &config,
                 discount,
             )?;
+            match outcome {
+                SpotOrderOutcome::Swapped => {}
+                SpotOrderOutcome::NotFillable => continue,
+                SpotOrderOutcome::Canceled(reason) => {
+                    events.push(cancel_pending_spot_order(
+                        deps.storage,
+                        &env.block,
+                        &mut spot_order,
+                        reason,
+                        &mut bank_msgs,
+                    )?);
+                    continue;
+                }
+            }
+            log_spot_order(
+                deps.storage,
+                spot_order.order_id,
+                &env.block,
+                OrderEvent::Triggered { market_price },
+            )?;
+            events.push(
+                spot_order_event("process_spot_order", &spot_order)
+                    .add_attribute("market_price", market_price.to_string())
+                    .add_attribute("result", "triggered"),
+            );
+
+            if let Some(group_id) = spot_order.group_id {
+                triggered_spot_groups.push(group_id);
+            }
+            remaining -= 1;
         }
     }
 
-    for perpetual_order in perpetual_orders.iter() {
-        let mut order = perpetual_order.to_owned();
+    let mut remaining = (perpetual_pagination.limit as usize).saturating_sub(monitor_checks);
 
-        if perpetual_order.trigger_price.as_ref().unwrap().base_denom != usdc_denom
-            || perpetual_order.trigger_price.as_ref().unwrap().quote_denom
-                != perpetual_order.trading_asset
-        {
-            let mut order = perpetual_order.to_owned();
-            order.status = Status::Canceled;
-            if perpetual_order.order_type == PerpetualOrderType::LimitOpen {
-                bank_msgs.push(BankMsg::Send {
-                    to_address: order.owner.clone(),
-                    amount: vec![order.collateral.clone()],
-                })
-            }
-            PENDING_PERPETUAL_ORDER.remove(deps.storage, order.order_id);
-            PERPETUAL_ORDER.save(deps.storage, order.order_id, &order)?;
-            continue;
+    for book in perpetual_books {
+        if remaining == 0 {
+            break;
         }
 
-        let market_price = match querier.get_asset_price_from_denom_in_to_denom_out(
-            &perpetual_order.collateral.denom,
-            &perpetual_order.trading_asset,
+        let market_price = match get_market_price(
+            &querier,
+            &mut market_prices,
+            &book.collateral.denom,
+            &book.trading_asset,
         ) {
             Ok(market_price) => market_price,
-            Err(_) => {
-                order.status = Status::Canceled;
-                PENDING_PERPETUAL_ORDER.remove(deps.storage, order.order_id);
-                PERPETUAL_ORDER.save(deps.storage, order.order_id, &order)?;
-                if order.order_type == PerpetualOrderType::LimitOpen {
-                    bank_msgs.push(BankMsg::Send {
-                        to_address: order.owner.to_string(),
-                        amount: vec![order.collateral.clone()],
-                    });
+            Err(_) => continue,
+        };
+
+        let rate_below = match (&book.order_type, &book.position) {
+            (PerpetualOrderType::LimitOpen, PerpetualPosition::Long) => false,
+            (PerpetualOrderType::LimitOpen, PerpetualPosition::Short) => true,
+            (PerpetualOrderType::LimitClose, PerpetualPosition::Long) => true,
+            (PerpetualOrderType::LimitClose, PerpetualPosition::Short) => false,
+            (PerpetualOrderType::StopLoss, PerpetualPosition::Long) => false,
+            (PerpetualOrderType::StopLoss, PerpetualPosition::Short) => true,
+            (PerpetualOrderType::TrailingStop, PerpetualPosition::Long) => false,
+            (PerpetualOrderType::TrailingStop, PerpetualPosition::Short) => true,
+            _ => continue,
+        };
+
+        let crossed_perpetual_orders = crossed_orders(
+            deps.storage,
+            &PENDING_PERPETUAL_ORDER.idx.order_book,
+            perpetual_order_book_key(&[], &book),
+            market_price,
+            rate_below,
+            remaining,
+        )?;
+
+        for mut order in crossed_perpetual_orders {
+            if let Some(group_id) = order.group_id {
+                if triggered_perpetual_groups.contains(&group_id) {
+                    continue;
                 }
+            }
+
+            if is_expired(&order.expiration, &env) {
+                events.push(cancel_pending_perpetual_order(
+                    deps.storage,
+                    &env.block,
+                    &mut order,
+                    CancelReason::Expired,
+                    &mut bank_msgs,
+                )?);
+                events.extend(cancel_child_perpetual_orders(
+                    deps.storage,
+                    &env.block,
+                    order.order_id,
+                )?);
                 continue;
             }
-        };
 
-        if order.order_type != PerpetualOrderType::LimitOpen {
-            match querier.mtp(order.owner.clone(), order.position_id.clone().unwrap()) {
-                Ok(mtp) => match mtp.mtp {
-                    Some(_) => {}
-                    None => {
-                        order.status = Status::Canceled;
-                        PENDING_PERPETUAL_ORDER.remove(deps.storage, order.order_id);
-                        PERPETUAL_ORDER.save(deps.storage, order.order_id, &order)?;
-                        continue;
-                    }
-                },
-                Err(_) => {
-                    order.status = Status::Canceled;
-                    PENDING_PERPETUAL_ORDER.remove(deps.storage, order.order_id);
-                    PERPETUAL_ORDER.save(deps.storage, order.order_id, &order)?;
+            if !check_perpetual_order(&order, market_price) {
+                continue;
+            }
+
+            if order.order_type != PerpetualOrderType::LimitOpen {
+                let mtp_found = match querier.mtp(order.owner.clone(), order.position_id.unwrap()) {
+                    Ok(mtp) => mtp.mtp.is_some(),
+                    Err(_) => false,
+                };
+                if !mtp_found {
+                    events.push(cancel_pending_perpetual_order(
+                        deps.storage,
+                        &env.block,
+                        &mut order,
+                        CancelReason::PositionNotFound,
+                        &mut bank_msgs,
+                    )?);
                     continue;
                 }
+            }
+
+            let fee = if order.order_type == PerpetualOrderType::LimitOpen {
+                let discount = get_discount(&deps.as_ref(), order.owner.clone())?;
+                get_protocol_fee(&config, &order.collateral, discount)
+            } else {
+                coin(0, &order.collateral.denom)
             };
-        }
 
-        if check_perpetual_order(&perpetual_order, market_price) {
-            process_perpetual_order(
-                perpetual_order,
+            let processed = process_perpetual_order(
+                &order,
+                &fee,
                 &mut submsgs,
                 &mut reply_info_id,
                 deps.storage,
                 &querier,
                 env.contract.address.as_str(),
             )?;
+            if !processed {
+                events.push(cancel_pending_perpetual_order(
+                    deps.storage,
+                    &env.block,
+                    &mut order,
+                    CancelReason::PositionNotFound,
+                    &mut bank_msgs,
+                )?);
+                continue;
+            }
+            log_perpetual_order(
+                deps.storage,
+                order.order_id,
+                &env.block,
+                OrderEvent::Triggered { market_price },
+            )?;
+            events.push(
+                perpetual_order_event("process_perpetual_order", &order)
+                    .add_attribute("market_price", market_price.to_string())
+                    .add_attribute("result", "triggered"),
+            );
+
+            if let Some(group_id) = order.group_id {
+                triggered_perpetual_groups.push(group_id);
+            }
+            remaining -= 1;
+        }
+    }
+
+    for (mut order, mtp_health, market_price) in unhealthy_perpetual_orders {
+        if let Some(group_id) = order.group_id {
+            if triggered_perpetual_groups.contains(&group_id) {
+                continue;
+            }
+        }
+
+        let processed = process_perpetual_order(
+            &order,
+            &coin(0, &order.collateral.denom),
+            &mut submsgs,
+            &mut reply_info_id,
+            deps.storage,
+            &querier,
+            env.contract.address.as_str(),
+        )?;
+        if !processed {
+            events.push(cancel_pending_perpetual_order(
+                deps.storage,
+                &env.block,
+                &mut order,
+                CancelReason::PositionNotFound,
+                &mut bank_msgs,
+            )?);
+            continue;
+        }
+        log_perpetual_order(
+            deps.storage,
+            order.order_id,
+            &env.block,
+            OrderEvent::Triggered { market_price },
+        )?;
+        events.push(
+            perpetual_order_event("process_perpetual_order", &order)
+                .add_attribute("mtp_health", mtp_health.to_string())
+                .add_attribute("result", "triggered"),
+        );
+
+        if let Some(group_id) = order.group_id {
+            triggered_perpetual_groups.push(group_id);
         }
     }
 
     MAX_REPLY_ID.save(deps.storage, &reply_info_id)?;
 
-    let resp = Response::new().add_submessages(submsgs);
+    let resp = Response::new()
+        .add_submessages(submsgs)
+        .add_messages(bank_msgs)
+        .add_events(events);
 
     Ok(resp)
 }
 
+fn cancel_pending_spot_order(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    order: &mut SpotOrder,
+    reason: CancelReason,
+    bank_msgs: &mut Vec<BankMsg>,
+) -> StdResult<Event> {
+    order.cancel(reason.clone());
+    bank_msgs.push(BankMsg::Send {
+        to_address: order.owner_address.to_string(),
+        amount: vec![order.remaining_amount()],
+    });
+    PENDING_SPOT_ORDER.remove(storage, order.order_id)?;
+    SPOT_ORDER.save(storage, order.order_id, order)?;
+    let event = cancel_event(spot_order_event("process_spot_order", order), &reason)?;
+    log_spot_order(
+        storage,
+        order.order_id,
+        block,
+        OrderEvent::Canceled { reason },
+    )?;
+    Ok(event)
+}
+
+fn cancel_pending_perpetual_order(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    order: &mut PerpetualOrder,
+    reason: CancelReason,
+    bank_msgs: &mut Vec<BankMsg>,
+) -> StdResult<Event> {
+    order.cancel(reason.clone());
+    if let Some(escrow) = order.escrow() {
+        bank_msgs.push(BankMsg::Send {
+            to_address: order.owner.clone(),
+            amount: vec![escrow],
+        })
+    }
+    PENDING_PERPETUAL_ORDER.remove(storage, order.order_id)?;
+    PERPETUAL_ORDER.save(storage, order.order_id, order)?;
+    let event = cancel_event(
+        perpetual_order_event("process_perpetual_order", order),
+        &reason,
+    )?;
+    log_perpetual_order(
+        storage,
+        order.order_id,
+        block,
+        OrderEvent::Canceled { reason },
+    )?;
+    Ok(event)
+}
+
+fn get_market_price(
+    querier: &ElysQuerier<'_>,
+    market_prices: &mut HashMap<(String, String), Decimal>,
+    denom_in: &str,
+    denom_out: &str,
+) -> StdResult<Decimal> {
+    let pair = (denom_in.to_string(), denom_out.to_string());
+    if let Some(market_price) = market_prices.get(&pair) {
+        return Ok(*market_price);
+    }
+    let market_price = querier.get_asset_price_from_denom_in_to_denom_out(denom_in, denom_out)?;
+    market_prices.insert(pair, market_price);
+    Ok(market_price)
+}
+
 fn process_perpetual_order(
     order: &PerpetualOrder,
+    fee: &Coin,
     submsgs: &mut Vec<SubMsg<ElysMsg>>,
     reply_info_id: &mut u64,
     storage: &mut dyn Storage,
     querier: &ElysQuerier<'_>,
     creator: &str,
-) -> StdResult<()> {
-    let (msg, reply_type) = if order.order_type == PerpetualOrderType::LimitOpen {
+) -> StdResult<bool> {
+    let (msg, reply_type, data) = if order.order_type == PerpetualOrderType::LimitOpen {
+        // the fee stays in the contract, only the rest of the collateral is sent to the module
+        let collateral = coin(
+            (order.collateral.amount - fee.amount).u128(),
+            &order.collateral.denom,
+        );
         (
             ElysMsg::perpetual_open_position(
                 creator,
-                order.collateral.clone(),
+                collateral,
                 &order.trading_asset,
                 order.position.clone(),
                 order.leverage.clone(),
@@ -198,6 +609,19 @@ fn process_perpetual_order(
                 &order.owner,
             ),
             ReplyType::PerpetualBrokerOpen,
+            to_json_binary(&(order.order_id, fee))?,
+        )
+    } else if let Some(escrow) = &order.escrowed_collateral {
+        // a health monitor holding collateral adds it to the position instead of closing it
+        (
+            ElysMsg::perpetual_add_collateral(
+                creator,
+                order.position_id.unwrap(),
+                Int128::try_from(escrow.amount)?.i128(),
+                &order.owner,
+            ),
+            ReplyType::PerpetualBrokerAddCollateral,
+            to_json_binary(&order.order_id)?,
         )
     } else {
         let mtp = match querier
@@ -205,16 +629,10 @@ fn process_perpetual_order(
             .mtp
         {
             Some(mtp) => mtp,
-            None => {
-                let mut order = order.to_owned();
-                order.status = Status::Canceled;
-                PENDING_PERPETUAL_ORDER.remove(storage, order.order_id);
-                PERPETUAL_ORDER.save(storage, order.order_id, &order)?;
-                return Ok(());
-            }
+            None => return Ok(false),
         };
 
-        let amount = mtp.custody.i128();
+        let amount = order.close_amount(mtp.custody).i128();
         (
             ElysMsg::perpetual_close_position(
                 creator,
@@ -223,6 +641,7 @@ fn process_perpetual_order(
                 &order.owner,
             ),
             ReplyType::PerpetualBrokerClose,
+            to_json_binary(&order.order_id)?,
         )
     };
 
@@ -241,13 +660,20 @@ fn process_perpetual_order(
     let reply_info = ReplyInfo {
         id: *reply_info_id,
         reply_type,
-        data: Some(to_json_binary(&order.order_id)?),
+        data: Some(data),
     };
     submsgs.push(SubMsg::reply_always(msg, *reply_info_id));
 
     REPLY_INFO.save(storage, *reply_info_id, &reply_info)?;
 
-    Ok(())
+    Ok(true)
+}
+
+fn is_expired(expiration: &Option<Expiration>, env: &Env) -> bool {
+    match expiration {
+        Some(expiration) => expiration.is_expired(&env.block),
+        None => false,
+    }
 }
 
 fn check_perpetual_order(order: &PerpetualOrder, market_price: Decimal) -> bool {
@@ -266,6 +692,8 @@ fn check_perpetual_order(order: &PerpetualOrder, market_price: Decimal) -> bool
         (PerpetualOrderType::LimitClose, PerpetualPosition::Short) => market_price <= order_price,
         (PerpetualOrderType::StopLoss, PerpetualPosition::Long) => market_price <= order_price,
         (PerpetualOrderType::StopLoss, PerpetualPosition::Short) => market_price >= order_price,
+        (PerpetualOrderType::TrailingStop, PerpetualPosition::Long) => market_price <= order_price,
+        (PerpetualOrderType::TrailingStop, PerpetualPosition::Short) => market_price >= order_price,
         _ => false,
     }
 }
@@ -290,10 +718,45 @@ fn check_spot_order(order: &SpotOrder, market_price: Decimal) -> bool {
         SpotOrderType::LimitBuy => market_price <= order_price,
         SpotOrderType::LimitSell => market_price >= order_price,
         SpotOrderType::StopLoss => market_price <= order_price,
+        SpotOrderType::TrailingStop => market_price <= order_price,
         _ => false,
     }
 }
 
+// a limit order is filled in slices when swapping all of it would slip past its max slippage
+fn get_fill_amount(order: &SpotOrder, slippage: Decimal) -> Coin {
+    let remaining_amount = order.remaining_amount();
+
+    if let Some(twap_schedule) = &order.twap_schedule {
+        let amount = twap_schedule.slice_amount(remaining_amount.amount);
+        return coin(amount.u128(), remaining_amount.denom);
+    }
+
+    if order.order_type != SpotOrderType::LimitBuy && order.order_type != SpotOrderType::LimitSell {
+        return remaining_amount;
+    }
+
+    let max_slippage = order.max_slippage.unwrap_or(DEFAULT_MAX_SLIPPAGE);
+
+    if slippage <= max_slippage {
+        return remaining_amount;
+    }
+
+    // the slippage grows with the swapped amount, only the part that stays within the tolerance is swapped
+    let amount = remaining_amount
+        .amount
+        .multiply_ratio(max_slippage.atomics(), slippage.atomics());
+
+    coin(amount.u128(), remaining_amount.denom)
+}
+
+enum SpotOrderOutcome {
+    Swapped,
+    // no part of the order can be swapped within its max slippage
+    NotFillable,
+    Canceled(CancelReason),
+}
+
 fn process_spot_order(
     order: &SpotOrder,
     submsgs: &mut Vec<SubMsg<ElysMsg>>,
@@ -301,18 +764,41 @@ fn process_spot_order(
     reply_info_id: &mut u64,
     amm_swap_estimation: AmmSwapEstimationByDenomResponse,
     storage: &mut dyn Storage,
+    config: &Config,
     discount: Decimal,
-) -> StdResult<()> {
-    let token_out_min_amount: Int128 = match order.order_type {
-        SpotOrderType::LimitBuy => calculate_token_out_min_amount(order),
-        SpotOrderType::LimitSell => calculate_token_out_min_amount(order),
-        SpotOrderType::StopLoss => Int128::zero(),
-        _ => Int128::zero(),
+) -> StdResult<SpotOrderOutcome> {
+    let fill_amount = get_fill_amount(order, amm_swap_estimation.slippage);
+    if fill_amount.amount.is_zero() {
+        return Ok(SpotOrderOutcome::NotFillable);
+    }
+
+    // the fee stays in the contract, only the rest of the fill is swapped
+    let fee = get_protocol_fee(config, &fill_amount, discount);
+    let swap_amount = coin((fill_amount.amount - fee.amount).u128(), &fill_amount.denom);
+
+    let token_out_min_amount = match order.order_type {
+        SpotOrderType::LimitBuy => calculate_token_out_min_amount(order, &swap_amount),
+        SpotOrderType::LimitSell => calculate_token_out_min_amount(order, &swap_amount),
+        SpotOrderType::StopLoss => Ok(Int128::zero()),
+        SpotOrderType::Twap => {
+            calculate_slice_min_amount(order, &swap_amount, &amm_swap_estimation.amount)
+        }
+        _ => Ok(Int128::zero()),
+    };
+    let token_out_min_amount = match token_out_min_amount {
+        Ok(token_out_min_amount) => token_out_min_amount,
+        Err(err) => {
+            return Ok(SpotOrderOutcome::Canceled(
+                CancelReason::MinAmountOutFailed {
+                    error: err.to_string(),
+                },
+            ))
+        }
     };
 
     let msg = ElysMsg::amm_swap_exact_amount_in(
         sender,
-        &order.order_amount,
+        &swap_amount,
         &amm_swap_estimation.in_route.unwrap(),
         token_out_min_amount,
         discount,
@@ -334,35 +820,62 @@ fn process_spot_order(
     let reply_info = ReplyInfo {
         id: *reply_info_id,
         reply_type: ReplyType::SpotOrder,
-        data: Some(to_json_binary(&order.order_id)?),
+        data: Some(to_json_binary(&(
+            order.order_id,
+            fill_amount.amount,
+            fee.amount,
+        ))?),
     };
 
     submsgs.push(SubMsg::reply_always(msg, *reply_info_id));
 
     REPLY_INFO.save(storage, *reply_info_id, &reply_info)?;
 
-    Ok(())
+    Ok(SpotOrderOutcome::Swapped)
+}
+
+// minimum amount expected from the swap, the amount at the order rate reduced by the order max slippage
+fn calculate_token_out_min_amount(order: &SpotOrder, amount_in: &Coin) -> StdResult<Int128> {
+    let SpotOrder {
+        order_price,
+        max_slippage,
+        ..
+    } = order;
+
+    // the rate of a limit buy is the amount of source token paid for one target token
+    let amount = if order.order_type == SpotOrderType::LimitBuy {
+        amount_in
+            .amount
+            .checked_multiply_ratio(Decimal::one().atomics(), order_price.rate.atomics())
+            .map_err(|err| StdError::generic_err(err.to_string()))?
+    } else {
+        amount_in
+            .amount
+            .checked_mul_floor(order_price.rate)
+            .map_err(|err| StdError::generic_err(err.to_string()))?
+    };
+
+    let max_slippage = max_slippage.unwrap_or(DEFAULT_MAX_SLIPPAGE);
+    let amount = amount * (Decimal::one() - max_slippage);
+
+    Ok(Int128::try_from(amount)?)
 }
 
-fn calculate_token_out_min_amount(_order: &SpotOrder) -> Int128 {
-    // FIXME:
-    // insteade we want to use the amount field from swap-estimation-by-denom that
-    // include slippage and reduce it by 1% that should be our token out min amount to return here
-    //
-    // let SpotOrder {
-    //     order_amount,
-    //     order_price,
-    //     ..
-    // } = order;
-
-    // let amount = if order_amount.denom == order_price.base_denom {
-    //     order_amount.amount * order_price.rate
-    // } else {
-    //     order_amount.amount * Decimal::one().div(order_price.rate)
-    // };
-
-    // Int128::new((amount.u128()) as i128)
-    Int128::zero()
+// a twap slice is swapped at the market, its minimum output is its share of the estimation of the remaining amount
+fn calculate_slice_min_amount(
+    order: &SpotOrder,
+    amount_in: &Coin,
+    estimation: &Coin,
+) -> StdResult<Int128> {
+    let amount = estimation
+        .amount
+        .checked_multiply_ratio(amount_in.amount, order.remaining_amount().amount)
+        .map_err(|err| StdError::generic_err(err.to_string()))?;
+
+    let max_slippage = order.max_slippage.unwrap_or(DEFAULT_MAX_SLIPPAGE);
+    let amount = amount * (Decimal::one() - max_slippage);
+
+    Ok(Int128::try_from(amount)?)
 }
 
 #[cfg(test)]
@@ -393,6 +906,14 @@ mod tests {
                 height: 5,
                 time: Timestamp::from_seconds(5),
             },
+            trailing_stop: None,
+            group_id: None,
+            expiration: None,
+            max_slippage: None,
+            filled_amount: None,
+            twap_schedule: None,
+            cancel_reason: None,
+            fee: None,
             // Initialize the rest of the SpotOrder fields here
         };
         let market_price = Decimal::from_str("9.29").unwrap();
@@ -424,6 +945,14 @@ mod tests {
                 height: 5,
                 time: Timestamp::from_seconds(5),
             },
+            trailing_stop: None,
+            group_id: None,
+            expiration: None,
+            max_slippage: None,
+            filled_amount: None,
+            twap_schedule: None,
+            cancel_reason: None,
+            fee: None,
             // Initialize the rest of the SpotOrder fields here
         };
         let market_price = Decimal::from_str("9.29").unwrap();
@@ -433,4 +962,64 @@ mod tests {
         // Assert
         assert_eq!(result, true); // Change as needed
     }
+
+    #[test]
+    fn test_calculate_token_out_min_amount() {
+        let mut spot_order = SpotOrder {
+            order_type: SpotOrderType::LimitSell,
+            order_id: 1,
+            order_price: OrderPrice {
+                base_denom: "uatom".to_string(),
+                quote_denom: "usdc".to_string(),
+                rate: Decimal::from_str("9.5").unwrap(),
+            },
+            order_amount: coin(1000000, "uatom"),
+
+            owner_address: Addr::unchecked("elysd"),
+            order_target_denom: "usdc".to_string(),
+            status: Status::Pending,
+            date: Date {
+                height: 5,
+                time: Timestamp::from_seconds(5),
+            },
+            trailing_stop: None,
+            group_id: None,
+            expiration: None,
+            max_slippage: None,
+            filled_amount: None,
+            twap_schedule: None,
+            cancel_reason: None,
+            fee: None,
+        };
+
+        // 9.5 usdc per uatom with the default 1% slippage
+        assert_eq!(
+            calculate_token_out_min_amount(&spot_order, &spot_order.order_amount).unwrap(),
+            Int128::new(9405000)
+        );
+
+        spot_order.max_slippage = Some(Decimal::percent(5));
+        assert_eq!(
+            calculate_token_out_min_amount(&spot_order, &spot_order.order_amount).unwrap(),
+            Int128::new(9025000)
+        );
+
+        // 0.5 usdc paid per uatom bought
+        spot_order.order_type = SpotOrderType::LimitBuy;
+        spot_order.order_amount = coin(1000000, "usdc");
+        spot_order.order_price.rate = Decimal::from_str("0.5").unwrap();
+        assert_eq!(
+            calculate_token_out_min_amount(&spot_order, &spot_order.order_amount).unwrap(),
+            Int128::new(1900000)
+        );
+
+        // the amounts out of range are errors instead of panics or wrapped amounts
+        spot_order.order_type = SpotOrderType::LimitSell;
+        spot_order.order_price.rate = Decimal::from_str("2").unwrap();
+        assert!(calculate_token_out_min_amount(&spot_order, &coin(u128::MAX, "uatom")).is_err());
+
+        spot_order.order_price.rate = Decimal::one();
+        spot_order.max_slippage = Some(Decimal::zero());
+        assert!(calculate_token_out_min_amount(&spot_order, &coin(1 << 127, "uatom")).is_err());
+    }
 }
@@ -1,4 +1,5 @@
-use crate::helper::get_discount;
+use crate::helper::{get_discount, paused_feature};
+use crate::states::CONFIG;
 
 use super::*;
 use cosmwasm_std::{Int128, SubMsg};
@@ -14,12 +15,23 @@ pub fn execute(
     use action::execute::*;
     use ExecuteMsg::*;
 
+    if let Some(feature) = paused_feature(&CONFIG.load(deps.storage)?.pause, &msg) {
+        return Err(ContractError::Paused {
+            feature: feature.to_string(),
+        });
+    }
+
     match msg {
         CreateSpotOrder {
             order_type,
             order_source_denom,
             order_target_denom,
             order_price,
+            trailing_distance,
+            linked_order_id,
+            expiration,
+            max_slippage,
+            min_out,
         } => create_spot_order(
             env,
             deps,
@@ -28,12 +40,51 @@ pub fn execute(
             order_source_denom,
             order_target_denom,
             order_price,
+            trailing_distance,
+            linked_order_id,
+            expiration,
+            max_slippage,
+            min_out,
+        ),
+        CreateTwapOrder {
+            order_source_denom,
+            order_target_denom,
+            slices,
+            interval,
+            max_slippage,
+            expiration,
+        } => create_twap_order(
+            env,
+            deps,
+            info,
+            order_source_denom,
+            order_target_denom,
+            slices,
+            interval,
+            max_slippage,
+            expiration,
+        ),
+        UpdateSpotOrder {
+            order_id,
+            order_price,
+            order_amount,
+            max_slippage,
+            expiration,
+        } => update_spot_order(
+            env,
+            deps,
+            info,
+            order_id,
+            order_price,
+            order_amount,
+            max_slippage,
+            expiration,
         ),
-        CancelSpotOrder { order_id } => cancel_spot_order(info, deps, order_id),
+        CancelSpotOrder { order_id } => cancel_spot_order(info, env, deps, order_id),
         CancelSpotOrders {
             order_ids,
             order_type,
-        } => cancel_spot_orders(info, deps, order_ids, order_type),
+        } => cancel_spot_orders(info, env, deps, order_ids, order_type),
 
         CreatePerpetualOrder {
             position,
@@ -43,6 +94,11 @@ pub fn execute(
             order_type,
             trigger_price,
             position_id,
+            trailing_distance,
+            linked_order_id,
+            expiration,
+            close_size,
+            health_threshold,
         } => create_perpetual_order(
             env,
             info,
@@ -54,13 +110,67 @@ pub fn execute(
             order_type,
             trigger_price,
             position_id,
+            trailing_distance,
+            linked_order_id,
+            expiration,
+            close_size,
+            health_threshold,
         ),
-        CancelPerpetualOrder { order_id } => cancel_perpetual_order(info, deps, order_id),
+        CreatePerpetualBracketOrder {
+            position,
+            leverage,
+            trading_asset,
+            take_profit_price,
+            order_type,
+            trigger_price,
+            take_profit_trigger_price,
+            stop_loss_trigger_price,
+            expiration,
+        } => create_perpetual_bracket_order(
+            env,
+            info,
+            deps,
+            position,
+            leverage,
+            trading_asset,
+            take_profit_price,
+            order_type,
+            trigger_price,
+            take_profit_trigger_price,
+            stop_loss_trigger_price,
+            expiration,
+        ),
+        UpdatePerpetualOrder {
+            order_id,
+            trigger_price,
+            collateral,
+            leverage,
+            take_profit_price,
+            expiration,
+        } => update_perpetual_order(
+            env,
+            deps,
+            info,
+            order_id,
+            trigger_price,
+            collateral,
+            leverage,
+            take_profit_price,
+            expiration,
+        ),
+        CancelPerpetualOrder { order_id } => cancel_perpetual_order(info, env, deps, order_id),
         CancelPerpetualOrders {
             order_ids,
             order_type,
-        } => cancel_perpetual_orders(info, deps, order_ids, order_type),
+        } => cancel_perpetual_orders(info, env, deps, order_ids, order_type),
         ClosePerpetualPosition { id, amount } => close_perpetual_position(info, env, id, amount),
+        AddPerpetualPositionCollateral { id } => {
+            add_perpetual_position_collateral(info, env, deps, id)
+        }
+        UpdatePerpetualPositionTakeProfitPrice {
+            id,
+            take_profit_price,
+        } => update_perpetual_position_take_profit_price(info, env, deps, id, take_profit_price),
 
         StakeRequest {
             amount,
@@ -156,5 +266,37 @@ pub fn execute(
             position_id,
             amount,
         } => close_leveragelp_position_request(info, position_id, amount),
+        UpdateConfig {
+            admin,
+            account_history_address,
+            allowed_pairs,
+            min_order_amounts,
+            max_order_amounts,
+            max_leverage,
+            pause,
+            fee_bps,
+            fee_collector,
+            allowed_denoms,
+            validate_assets,
+            process_order_limit,
+        } => update_config(
+            info,
+            deps,
+            ConfigUpdate {
+                admin,
+                account_history_address,
+                allowed_pairs,
+                min_order_amounts,
+                max_order_amounts,
+                max_leverage,
+                pause,
+                fee_bps,
+                fee_collector,
+                allowed_denoms,
+                validate_assets,
+                process_order_limit,
+            },
+        ),
+        WithdrawProtocolFees {} => withdraw_protocol_fees(info, deps),
     }
 }
@@ -1,17 +1,33 @@
 use super::*;
+use crate::helper::check_process_order_limit;
 use crate::states::*;
+use crate::types::Config;
+use elys_bindings::types::PageRequest;
 use msg::InstantiateMsg;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut<ElysQuery>,
     _env: Env,
-    _info: MessageInfo,
+    info: MessageInfo,
     msg: InstantiateMsg,
 ) -> StdResult<Response<ElysMsg>> {
     MAX_REPLY_ID.save(deps.storage, &0)?;
     SPOT_ORDER_MAX_ID.save(deps.storage, &0)?;
-    ACCOUNT_HISTORY_ADDRESS.save(deps.storage, &msg.account_history_address)?;
+    ORDER_INDEX_VERSION.save(deps.storage, &LATEST_ORDER_INDEX_VERSION)?;
+
+    let admin = match msg.admin {
+        Some(admin) => deps.api.addr_validate(&admin)?,
+        None => info.sender,
+    };
+    CONFIG.save(
+        deps.storage,
+        &Config::new(admin, msg.account_history_address),
+    )?;
+
+    let limit = check_process_order_limit(msg.process_order_limit.unwrap_or(100))?;
+    PROCESS_SPOT_ORDER_PAGINATION.save(deps.storage, &PageRequest::new(limit))?;
+    PROCESS_PERPETUAL_ORDER_PAGINATION.save(deps.storage, &PageRequest::new(limit))?;
 
     Ok(Response::new())
 }
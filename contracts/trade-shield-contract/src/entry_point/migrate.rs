@@ -1,15 +1,130 @@
-use elys_bindings::trade_shield::{msg::MigrateMsg, states::ACCOUNT_HISTORY_ADDRESS};
+use cosmwasm_std::{Order, StdError, Storage};
+use elys_bindings::trade_shield::{
+    msg::MigrateMsg,
+    states::{
+        ACCOUNT_HISTORY_ADDRESS, CONFIG, LATEST_ORDER_INDEX_VERSION, ORDER_INDEX_VERSION,
+        PENDING_PERPETUAL_ORDER, PENDING_SPOT_ORDER, PERPETUAL_ORDER, PERPETUAL_ORDER_MAX_ID,
+        PROCESS_PERPETUAL_ORDER_PAGINATION, PROCESS_SPOT_ORDER_PAGINATION, SPOT_ORDER,
+    },
+    types::{Config, PerpetualOrder, SpotOrder},
+};
+use elys_bindings::types::PageRequest;
 
 use super::*;
+use crate::helper::check_process_order_limit;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(
-    _deps: DepsMut<ElysQuery>,
-    _env: Env,
+    deps: DepsMut<ElysQuery>,
+    env: Env,
     msg: MigrateMsg,
 ) -> StdResult<Response<ElysMsg>> {
+    let mut config = match CONFIG.may_load(deps.storage)? {
+        Some(config) => config,
+        None => {
+            let admin = match msg.admin {
+                Some(admin) => admin,
+                None => match deps
+                    .querier
+                    .query_wasm_contract_info(&env.contract.address)?
+                    .admin
+                {
+                    Some(admin) => admin,
+                    None => {
+                        return Err(StdError::generic_err(
+                            "admin: Required to create the config",
+                        ))
+                    }
+                },
+            };
+            let account_history_address = ACCOUNT_HISTORY_ADDRESS.may_load(deps.storage)?.flatten();
+            ACCOUNT_HISTORY_ADDRESS.remove(deps.storage);
+            Config::new(deps.api.addr_validate(&admin)?, account_history_address)
+        }
+    };
     if msg.account_history_address.is_some() {
-        ACCOUNT_HISTORY_ADDRESS.save(_deps.storage, &msg.account_history_address)?;
+        config.account_history_address = msg.account_history_address;
     }
+    CONFIG.save(deps.storage, &config)?;
+
+    // the stored pages keep their limit and position unless a new limit is set
+    let limit = match msg.process_order_limit {
+        Some(limit) => Some(check_process_order_limit(limit)?),
+        None => None,
+    };
+    for pagination in [
+        &PROCESS_SPOT_ORDER_PAGINATION,
+        &PROCESS_PERPETUAL_ORDER_PAGINATION,
+    ] {
+        let page = match (pagination.may_load(deps.storage)?, limit) {
+            (Some(mut page), Some(limit)) => {
+                page.limit = limit;
+                page
+            }
+            (Some(page), None) => page,
+            (None, limit) => PageRequest::new(limit.unwrap_or(100)),
+        };
+        pagination.save(deps.storage, &page)?;
+    }
+
+    // the perpetual order ids used to be given out from the highest stored order
+    if PERPETUAL_ORDER_MAX_ID.may_load(deps.storage)?.is_none() {
+        let max_id = PERPETUAL_ORDER
+            .keys(deps.storage, None, None, Order::Descending)
+            .next()
+            .transpose()?;
+        if let Some(max_id) = max_id {
+            PERPETUAL_ORDER_MAX_ID.save(deps.storage, &max_id)?;
+        }
+    }
+
+    // the orders are saved again only once to build the indexes added since the last migration
+    if ORDER_INDEX_VERSION
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        < LATEST_ORDER_INDEX_VERSION
+    {
+        rebuild_order_indexes(deps.storage)?;
+        ORDER_INDEX_VERSION.save(deps.storage, &LATEST_ORDER_INDEX_VERSION)?;
+    }
+
     Ok(Response::new())
 }
+
+fn rebuild_order_indexes(storage: &mut dyn Storage) -> StdResult<()> {
+    // saving the pending orders again builds their order book and group indexes
+    let spot_orders = PENDING_SPOT_ORDER
+        .range(storage, None, None, Order::Ascending)
+        .map(|res| res.map(|r| r.1))
+        .collect::<StdResult<Vec<SpotOrder>>>()?;
+    for order in spot_orders {
+        PENDING_SPOT_ORDER.save(storage, order.order_id, &order)?;
+    }
+
+    let perpetual_orders = PENDING_PERPETUAL_ORDER
+        .range(storage, None, None, Order::Ascending)
+        .map(|res| res.map(|r| r.1))
+        .collect::<StdResult<Vec<PerpetualOrder>>>()?;
+    for order in perpetual_orders {
+        PENDING_PERPETUAL_ORDER.save(storage, order.order_id, &order)?;
+    }
+
+    // saving every order again builds their owner and status indexes
+    let spot_orders = SPOT_ORDER
+        .range(storage, None, None, Order::Ascending)
+        .map(|res| res.map(|r| r.1))
+        .collect::<StdResult<Vec<SpotOrder>>>()?;
+    for order in spot_orders {
+        SPOT_ORDER.save(storage, order.order_id, &order)?;
+    }
+
+    let perpetual_orders = PERPETUAL_ORDER
+        .range(storage, None, None, Order::Ascending)
+        .map(|res| res.map(|r| r.1))
+        .collect::<StdResult<Vec<PerpetualOrder>>>()?;
+    for order in perpetual_orders {
+        PERPETUAL_ORDER.save(storage, order.order_id, &order)?;
+    }
+
+    Ok(())
+}
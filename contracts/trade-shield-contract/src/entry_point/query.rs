@@ -75,5 +75,14 @@ pub fn query(deps: Deps<ElysQuery>, _env: Env, msg: QueryMsg) -> Result<Binary,
         } => Ok(to_json_binary(&query::perpetual_get_position_for_address(
             deps, address, pagination,
         )?)?),
+        GetOrderHistory {
+            order_id,
+            order_kind,
+            pagination,
+        } => Ok(to_json_binary(&query::get_order_history(
+            deps, order_id, order_kind, pagination,
+        )?)?),
+        Config {} => Ok(to_json_binary(&query::config(deps)?)?),
+        GetProtocolFees {} => Ok(to_json_binary(&query::get_protocol_fees(deps)?)?),
     }
 }
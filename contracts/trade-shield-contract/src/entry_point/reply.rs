@@ -6,7 +6,7 @@ use msg::ReplyType;
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn reply(
     deps: DepsMut<ElysQuery>,
-    _env: Env,
+    env: Env,
     msg: Reply,
 ) -> Result<Response<ElysMsg>, ContractError> {
     let module_resp = msg.result;
@@ -15,21 +15,26 @@ pub fn reply(
     REPLY_INFO.remove(deps.storage, msg.id);
 
     match info.reply_type {
-        ReplyType::SpotOrder => reply_to_spot_order(deps, info.data, module_resp),
+        ReplyType::SpotOrder => reply_to_spot_order(deps, &env.block, info.data, module_resp),
         ReplyType::PerpetualBrokerMarketOpen => {
-            reply_to_create_perpetual_market_open(deps, info.data, module_resp)
+            reply_to_create_perpetual_market_open(deps, &env.block, info.data, module_resp)
         }
 
         ReplyType::PerpetualBrokerMarketClose => {
-            reply_to_create_perpetual_market_close(deps, info.data, module_resp)
+            reply_to_create_perpetual_market_close(deps, &env.block, info.data, module_resp)
         }
 
         ReplyType::PerpetualBrokerClose => {
-            reply_to_close_perpetual_order(deps, info.data, module_resp)
+            reply_to_close_perpetual_order(deps, &env.block, info.data, module_resp)
+        }
+        ReplyType::SpotOrderMarketBuy => {
+            reply_to_spot_order_market(deps, &env.block, info.data, module_resp)
         }
-        ReplyType::SpotOrderMarketBuy => reply_to_spot_order_market(deps, info.data, module_resp),
         ReplyType::PerpetualBrokerOpen => {
-            reply_to_open_perpetual_position(deps, info.data, module_resp)
+            reply_to_open_perpetual_position(deps, &env.block, info.data, module_resp)
+        }
+        ReplyType::PerpetualBrokerAddCollateral => {
+            reply_to_add_perpetual_position_collateral(deps, &env.block, info.data, module_resp)
         }
     }
 }
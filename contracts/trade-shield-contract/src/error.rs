@@ -1,4 +1,4 @@
-use cosmwasm_std::{Addr, StdError};
+use cosmwasm_std::{Addr, Coin, Int128, SignedDecimal, StdError};
 use cw_utils::PaymentError;
 use thiserror::Error;
 
@@ -32,6 +32,40 @@ pub enum ContractError {
     Leverage,
     #[error("cannot cancel order: {order_id}, status: {status:?}")]
     CancelStatusError { order_id: u64, status: Status },
+    #[error("cannot update order: {order_id}, status: {status:?}")]
+    UpdateStatusError { order_id: u64, status: Status },
+    #[error("cannot link order: {order_id}, status: {status:?}")]
+    LinkStatusError { order_id: u64, status: Status },
+    #[error("market orders cannot be linked")]
+    LinkMarketOrder,
+    #[error("cannot link order: {order_id}, it is on another position or trading pair")]
+    LinkPositionMismatch { order_id: u64 },
     #[error("{balance} is smaller than {amount}")]
     InsufficientBalanceError {balance: u128, amount: u64},
+    #[error("{sender} is not the admin of the contract")]
+    AdminUnauthorized { sender: Addr },
+    #[error("{sender} is not the fee collector")]
+    FeeCollectorUnauthorized { sender: Addr },
+    #[error("{feature} are paused")]
+    Paused { feature: String },
+    #[error("{denom_a}/{denom_b} is not an allowed trading pair")]
+    PairNotAllowed { denom_a: String, denom_b: String },
+    #[error("order amount is smaller than the minimum {min}")]
+    OrderAmountTooLow { min: Coin },
+    #[error("order amount is larger than the maximum {max}")]
+    OrderAmountTooHigh { max: Coin },
+    #[error("leverage is larger than the maximum {max}")]
+    LeverageTooHigh { max: SignedDecimal },
+    #[error("the minimum order amount {min} is larger than the maximum {max}")]
+    OrderAmountRange { min: Coin, max: Coin },
+    #[error("the maximum leverage {max_leverage} should be larger than zero")]
+    MaxLeverageNotPositive { max_leverage: SignedDecimal },
+    #[error("{denom} is not an allowed denom")]
+    DenomNotAllowed { denom: String },
+    #[error("{denom} is not a registered asset")]
+    AssetNotRegistered { denom: String },
+    #[error("{denom} has no price")]
+    PriceUnavailable { denom: String },
+    #[error("the close orders of this type can close at most {available} more of the position")]
+    CloseSizeTooLarge { available: Int128 },
 }
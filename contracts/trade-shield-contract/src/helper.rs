@@ -1,7 +1,16 @@
-use cosmwasm_std::{from_json, Decimal, Deps, Response, StdResult, SubMsgResult};
+use cosmwasm_std::{
+    coin, coins, from_json, to_json_string, Addr, BankMsg, BlockInfo, Coin, Decimal, Deps, Event,
+    Int128, MessageInfo, Order, OverflowError, OverflowOperation, Response, SignedDecimal,
+    StdError, StdResult, Storage, SubMsgResult, Uint128,
+};
+use cw_utils::Expiration;
 use elys_bindings::account_history::msg::query_resp::MembershipTierResponse;
 use elys_bindings::account_history::msg::QueryMsg as AccountHistoryQueryMsg;
-use elys_bindings::{trade_shield::states::ACCOUNT_HISTORY_ADDRESS, ElysMsg, ElysQuery};
+use elys_bindings::query_resp::{Entry, QueryGetEntryResponse};
+use elys_bindings::types::{PageRequest, PageResponse};
+use elys_bindings::{ElysMsg, ElysQuerier, ElysQuery};
+
+use crate::{msg::ExecuteMsg, states::*, types::*, ContractError};
 
 use serde::de::DeserializeOwned;
 
@@ -24,8 +33,103 @@ pub fn get_response_from_reply<T: DeserializeOwned>(
     }
 }
 
+// the error attribute set by get_response_from_reply on a failed reply
+pub fn get_reply_error(response: &Response<ElysMsg>) -> String {
+    response
+        .attributes
+        .iter()
+        .find(|attr| attr.key == "error")
+        .map(|attr| attr.value.to_owned())
+        .unwrap_or_default()
+}
+
+// the events of an order share these attributes so they can be indexed the same way
+pub fn spot_order_event(ty: &str, order: &SpotOrder) -> Event {
+    Event::new(ty)
+        .add_attribute("order_id", order.order_id.to_string())
+        .add_attribute("owner", order.owner_address.to_string())
+        .add_attribute("order_type", format!("{:?}", order.order_type))
+        .add_attribute(
+            "pair",
+            format!("{}/{}", order.order_amount.denom, order.order_target_denom),
+        )
+        .add_attribute("trigger_rate", order.order_price.rate.to_string())
+}
+
+pub fn perpetual_order_event(ty: &str, order: &PerpetualOrder) -> Event {
+    let event = Event::new(ty)
+        .add_attribute("order_id", order.order_id.to_string())
+        .add_attribute("owner", order.owner.to_owned())
+        .add_attribute("order_type", format!("{:?}", order.order_type))
+        .add_attribute(
+            "pair",
+            format!("{}/{}", order.collateral.denom, order.trading_asset),
+        );
+
+    // market orders have no trigger price
+    match &order.trigger_price {
+        Some(trigger_price) => event.add_attribute("trigger_rate", trigger_price.rate.to_string()),
+        None => event,
+    }
+}
+
+pub fn cancel_event(event: Event, reason: &CancelReason) -> StdResult<Event> {
+    Ok(event
+        .add_attribute("result", "canceled")
+        .add_attribute("reason", to_json_string(reason)?))
+}
+
+pub fn log_spot_order(
+    storage: &mut dyn Storage,
+    order_id: u64,
+    block: &BlockInfo,
+    event: OrderEvent,
+) -> StdResult<()> {
+    // an entry is added after the last one of the order instead of rewriting its whole history
+    let seq = match SPOT_ORDER_HISTORY
+        .prefix(order_id)
+        .keys(storage, None, None, Order::Descending)
+        .next()
+    {
+        Some(last_seq) => last_seq? + 1,
+        None => 0,
+    };
+    SPOT_ORDER_HISTORY.save(
+        storage,
+        (order_id, seq),
+        &OrderHistoryEntry {
+            date: block.into(),
+            event,
+        },
+    )
+}
+
+pub fn log_perpetual_order(
+    storage: &mut dyn Storage,
+    order_id: u64,
+    block: &BlockInfo,
+    event: OrderEvent,
+) -> StdResult<()> {
+    let seq = match PERPETUAL_ORDER_HISTORY
+        .prefix(order_id)
+        .keys(storage, None, None, Order::Descending)
+        .next()
+    {
+        Some(last_seq) => last_seq? + 1,
+        None => 0,
+    };
+    PERPETUAL_ORDER_HISTORY.save(
+        storage,
+        (order_id, seq),
+        &OrderHistoryEntry {
+            date: block.into(),
+            event,
+        },
+    )
+}
+
 pub fn get_discount(deps: &Deps<ElysQuery>, user_address: String) -> StdResult<Decimal> {
-    let account_history_address = match ACCOUNT_HISTORY_ADDRESS.load(deps.storage)? {
+    let account_history_address = match CONFIG.load(deps.storage)?.account_history_address {
         Some(account_history_address) => account_history_address,
         None => return Ok(Decimal::zero()),
     };
@@ -40,3 +144,723 @@ pub fn get_discount(deps: &Deps<ElysQuery>, user_address: String) -> StdResult<D
 
     Ok(discount)
 }
+
+// feature of the message paused in the config, only the messages adding exposure are paused,
+// cancellations, position closes and the messages withdrawing funds never are
+pub fn paused_feature(pause: &PauseFlags, msg: &ExecuteMsg) -> Option<&'static str> {
+    use ExecuteMsg::*;
+
+    let (paused, feature) = match msg {
+        CreateSpotOrder { .. } | CreateTwapOrder { .. } | UpdateSpotOrder { .. } => {
+            (pause.spot_orders, "spot orders")
+        }
+        CreatePerpetualOrder { .. }
+        | CreatePerpetualBracketOrder { .. }
+        | UpdatePerpetualOrder { .. } => (pause.perpetual_orders, "perpetual orders"),
+        StakeRequest { .. } | ElysRedelegateRequest { .. } | EdenVestRequest { .. } => {
+            (pause.staking, "staking messages")
+        }
+        AmmJoinPoolRequest { .. } | AmmSwapExactAmountIn { .. } | LeveragelpOpen { .. } => {
+            (pause.liquidity, "liquidity messages")
+        }
+        CancelSpotOrder { .. }
+        | CancelSpotOrders { .. }
+        | CancelPerpetualOrder { .. }
+        | CancelPerpetualOrders { .. }
+        | ClosePerpetualPosition { .. }
+        | AddPerpetualPositionCollateral { .. }
+        | UpdatePerpetualPositionTakeProfitPrice { .. }
+        | UnstakeRequest { .. }
+        | ElysCancelUnstakeRequest { .. }
+        | EdenCancelVestRequest { .. }
+        | ClaimRewardsRequest { .. }
+        | ClaimValidatorCommissionRequest { .. }
+        | AmmExitPoolRequest { .. }
+        | LeveragelpClose { .. }
+        | UpdateConfig { .. }
+        | WithdrawProtocolFees { .. } => (false, ""),
+    };
+
+    if paused {
+        Some(feature)
+    } else {
+        None
+    }
+}
+
+pub fn check_pair(config: &Config, denom_a: &str, denom_b: &str) -> Result<(), ContractError> {
+    if !config.is_pair_allowed(denom_a, denom_b) {
+        return Err(ContractError::PairNotAllowed {
+            denom_a: denom_a.to_string(),
+            denom_b: denom_b.to_string(),
+        });
+    }
+    Ok(())
+}
+
+// the denoms of an order must be allowed by the config and, when assets are validated,
+// be registered in the oracle with a price, only the denoms of the order are queried
+pub fn check_assets(
+    deps: &Deps<ElysQuery>,
+    config: &Config,
+    denoms: &[&str],
+) -> Result<(), ContractError> {
+    if let Some(denom) = denoms.iter().find(|denom| !config.is_denom_allowed(denom)) {
+        return Err(ContractError::DenomNotAllowed {
+            denom: denom.to_string(),
+        });
+    }
+
+    if !config.validate_assets {
+        return Ok(());
+    }
+
+    let querier = ElysQuerier::new(&deps.querier);
+    let QueryGetEntryResponse {
+        entry: Entry {
+            denom: usdc_denom, ..
+        },
+    } = querier.get_asset_profile("uusdc".to_string())?;
+
+    for denom in denoms {
+        // usdc is priced from its asset profile, the other assets need an oracle entry
+        if *denom != usdc_denom && querier.asset_info(denom.to_string()).is_err() {
+            return Err(ContractError::AssetNotRegistered {
+                denom: denom.to_string(),
+            });
+        }
+
+        match querier.get_asset_price(*denom) {
+            Ok(price) if !price.is_zero() => {}
+            _ => {
+                return Err(ContractError::PriceUnavailable {
+                    denom: denom.to_string(),
+                })
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// check the amount against the limits set for its denom
+pub fn check_order_amount(config: &Config, amount: &Coin) -> Result<(), ContractError> {
+    if let Some(min) = config
+        .min_order_amounts
+        .iter()
+        .find(|min| min.denom == amount.denom)
+    {
+        if amount.amount < min.amount {
+            return Err(ContractError::OrderAmountTooLow { min: min.clone() });
+        }
+    }
+
+    if let Some(max) = config
+        .max_order_amounts
+        .iter()
+        .find(|max| max.denom == amount.denom)
+    {
+        if amount.amount > max.amount {
+            return Err(ContractError::OrderAmountTooHigh { max: max.clone() });
+        }
+    }
+
+    Ok(())
+}
+
+pub fn check_leverage(config: &Config, leverage: &SignedDecimal) -> Result<(), ContractError> {
+    if let Some(max) = config.max_leverage {
+        if leverage > &max {
+            return Err(ContractError::LeverageTooHigh { max });
+        }
+    }
+    Ok(())
+}
+
+// protocol fee taken from an executed amount, reduced by the membership discount of the owner
+pub fn get_protocol_fee(config: &Config, amount: &Coin, discount: Decimal) -> Coin {
+    let fee = amount.amount.multiply_ratio(config.fee_bps, 10_000u64)
+        * (Decimal::one().saturating_sub(discount));
+    coin(fee.u128(), &amount.denom)
+}
+
+pub fn accrue_protocol_fee(storage: &mut dyn Storage, fee: &Coin) -> StdResult<()> {
+    if fee.amount.is_zero() {
+        return Ok(());
+    }
+    PROTOCOL_FEES.update(storage, &fee.denom, |fees| -> StdResult<Uint128> {
+        Ok(fees.unwrap_or_default() + fee.amount)
+    })?;
+    Ok(())
+}
+
+// the pending orders are processed in pages of this size every block
+pub fn check_process_order_limit(limit: u64) -> StdResult<u64> {
+    if limit == 0 {
+        return Err(StdError::generic_err(
+            "process_order_limit: The limit should be at least 1",
+        ));
+    }
+    Ok(limit)
+}
+
+pub fn check_expiration(expiration: &Option<Expiration>, block: &BlockInfo) -> StdResult<()> {
+    if let Some(expiration) = expiration {
+        if expiration.is_expired(block) {
+            return Err(StdError::generic_err(
+                "expiration: The order is already expired",
+            ));
+        }
+    }
+    Ok(())
+}
+
+// change the amount held for an order, the owner sends the difference or gets it refunded
+pub fn update_escrow(
+    info: &MessageInfo,
+    escrow: &Coin,
+    new_amount: Option<Uint128>,
+) -> Result<Option<BankMsg>, ContractError> {
+    let new_amount = match new_amount {
+        Some(new_amount) if new_amount != escrow.amount => new_amount,
+        _ => {
+            cw_utils::nonpayable(info)?;
+            return Ok(None);
+        }
+    };
+
+    if new_amount < escrow.amount {
+        cw_utils::nonpayable(info)?;
+        return Ok(Some(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: coins((escrow.amount - new_amount).u128(), &escrow.denom),
+        }));
+    }
+
+    let difference = new_amount - escrow.amount;
+    if cw_utils::must_pay(info, &escrow.denom)? != difference {
+        return Err(StdError::generic_err(format!(
+            "funds: The amount sent should be the difference with the current amount: {}{}",
+            difference, escrow.denom
+        ))
+        .into());
+    }
+
+    Ok(None)
+}
+
+pub fn link_spot_order(
+    storage: &mut dyn Storage,
+    sender: &Addr,
+    new_order: &mut SpotOrder,
+    linked_order_id: u64,
+) -> Result<(), ContractError> {
+    if new_order.order_type == SpotOrderType::MarketBuy {
+        return Err(ContractError::LinkMarketOrder);
+    }
+
+    let mut linked_order = match SPOT_ORDER.may_load(storage, linked_order_id)? {
+        Some(order) => order,
+        None => {
+            return Err(ContractError::OrderNotFound {
+                order_id: linked_order_id,
+            })
+        }
+    };
+
+    if linked_order.owner_address != *sender {
+        return Err(ContractError::Unauthorized {
+            sender: sender.to_owned(),
+        });
+    }
+
+    if !linked_order.is_pending() {
+        return Err(ContractError::LinkStatusError {
+            order_id: linked_order_id,
+            status: linked_order.status,
+        });
+    }
+
+    let group_id = linked_order.group_id.unwrap_or(linked_order.order_id);
+
+    if linked_order.group_id.is_none() {
+        linked_order.group_id = Some(group_id);
+        SPOT_ORDER.save(storage, linked_order.order_id, &linked_order)?;
+        PENDING_SPOT_ORDER.save(storage, linked_order.order_id, &linked_order)?;
+    }
+
+    new_order.group_id = Some(group_id);
+
+    Ok(())
+}
+
+pub fn link_perpetual_order(
+    storage: &mut dyn Storage,
+    sender: &Addr,
+    new_order: &mut PerpetualOrder,
+    linked_order_id: u64,
+) -> Result<(), ContractError> {
+    if new_order.order_type == PerpetualOrderType::MarketOpen
+        || new_order.order_type == PerpetualOrderType::MarketClose
+    {
+        return Err(ContractError::LinkMarketOrder);
+    }
+
+    let mut linked_order = match PERPETUAL_ORDER.may_load(storage, linked_order_id)? {
+        Some(order) => order,
+        None => {
+            return Err(ContractError::OrderNotFound {
+                order_id: linked_order_id,
+            })
+        }
+    };
+
+    if linked_order.owner != sender.as_str() {
+        return Err(ContractError::Unauthorized {
+            sender: sender.to_owned(),
+        });
+    }
+
+    if linked_order.status != Status::Pending || linked_order.order_id == new_order.order_id {
+        return Err(ContractError::LinkStatusError {
+            order_id: linked_order_id,
+            status: linked_order.status,
+        });
+    }
+
+    // only orders on the same position, or opening the same pair, can replace each other
+    if linked_order.position_id != new_order.position_id
+        || linked_order.trading_asset != new_order.trading_asset
+        || linked_order.collateral.denom != new_order.collateral.denom
+    {
+        return Err(ContractError::LinkPositionMismatch {
+            order_id: linked_order_id,
+        });
+    }
+
+    let group_id = linked_order.group_id.unwrap_or(linked_order.order_id);
+
+    if linked_order.group_id.is_none() {
+        linked_order.group_id = Some(group_id);
+        PERPETUAL_ORDER.save(storage, linked_order.order_id, &linked_order)?;
+        PENDING_PERPETUAL_ORDER.save(storage, linked_order.order_id, &linked_order)?;
+    }
+
+    new_order.group_id = Some(group_id);
+
+    Ok(())
+}
+
+// cancel the other pending orders of the group of an executed order and refund their escrow
+pub fn cancel_linked_spot_orders(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    order: &SpotOrder,
+) -> StdResult<(Vec<BankMsg>, Vec<Event>)> {
+    let group_id = match order.group_id {
+        Some(group_id) => group_id,
+        None => return Ok((vec![], vec![])),
+    };
+
+    let linked_orders = PENDING_SPOT_ORDER
+        .idx
+        .group
+        .prefix(group_id)
+        .range(storage, None, None, Order::Ascending)
+        .map(|res| res.map(|r| r.1))
+        .filter(|res| match res {
+            Ok(linked) => linked.group_id == Some(group_id) && linked.order_id != order.order_id,
+            Err(_) => true,
+        })
+        .collect::<StdResult<Vec<SpotOrder>>>()?;
+
+    let mut refund_msgs: Vec<BankMsg> = vec![];
+    let mut events: Vec<Event> = vec![];
+
+    for mut linked_order in linked_orders {
+        let reason = CancelReason::LinkedOrderExecuted {
+            order_id: order.order_id,
+        };
+        linked_order.cancel(reason.clone());
+        SPOT_ORDER.save(storage, linked_order.order_id, &linked_order)?;
+        PENDING_SPOT_ORDER.remove(storage, linked_order.order_id)?;
+        events.push(cancel_event(
+            spot_order_event("cancel_spot_order", &linked_order),
+            &reason,
+        )?);
+        log_spot_order(
+            storage,
+            linked_order.order_id,
+            block,
+            OrderEvent::Canceled { reason },
+        )?;
+        refund_msgs.push(BankMsg::Send {
+            to_address: linked_order.owner_address.to_string(),
+            amount: vec![linked_order.remaining_amount()],
+        });
+    }
+
+    Ok((refund_msgs, events))
+}
+
+// cancel the other pending orders of the group of an executed order and refund their escrow
+pub fn cancel_linked_perpetual_orders(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    order: &PerpetualOrder,
+) -> StdResult<(Vec<BankMsg>, Vec<Event>)> {
+    let group_id = match order.group_id {
+        Some(group_id) => group_id,
+        None => return Ok((vec![], vec![])),
+    };
+
+    let linked_orders = PENDING_PERPETUAL_ORDER
+        .idx
+        .group
+        .prefix(group_id)
+        .range(storage, None, None, Order::Ascending)
+        .map(|res| res.map(|r| r.1))
+        .filter(|res| match res {
+            Ok(linked) => linked.group_id == Some(group_id) && linked.order_id != order.order_id,
+            Err(_) => true,
+        })
+        .collect::<StdResult<Vec<PerpetualOrder>>>()?;
+
+    let mut refund_msgs: Vec<BankMsg> = vec![];
+    let mut events: Vec<Event> = vec![];
+
+    for mut linked_order in linked_orders {
+        let reason = CancelReason::LinkedOrderExecuted {
+            order_id: order.order_id,
+        };
+        linked_order.cancel(reason.clone());
+        PERPETUAL_ORDER.save(storage, linked_order.order_id, &linked_order)?;
+        PENDING_PERPETUAL_ORDER.remove(storage, linked_order.order_id)?;
+        events.push(cancel_event(
+            perpetual_order_event("cancel_perpetual_order", &linked_order),
+            &reason,
+        )?);
+        log_perpetual_order(
+            storage,
+            linked_order.order_id,
+            block,
+            OrderEvent::Canceled { reason },
+        )?;
+        if let Some(escrow) = linked_order.escrow() {
+            refund_msgs.push(BankMsg::Send {
+                to_address: linked_order.owner,
+                amount: vec![escrow],
+            });
+        }
+    }
+
+    Ok((refund_msgs, events))
+}
+
+pub fn next_perpetual_order_id(storage: &mut dyn Storage) -> StdResult<u64> {
+    let order_id = match PERPETUAL_ORDER_MAX_ID.may_load(storage)? {
+        Some(max_id) => match max_id.checked_add(1) {
+            Some(id) => id,
+            None => {
+                return Err(StdError::overflow(OverflowError::new(
+                    OverflowOperation::Add,
+                    "perpetual_order_max_id",
+                    "increment one",
+                )))
+            }
+        },
+        None => 0,
+    };
+    PERPETUAL_ORDER_MAX_ID.save(storage, &order_id)?;
+    Ok(order_id)
+}
+
+// pending close orders attached to an open order, read from the parent index
+fn get_pending_child_perpetual_orders(
+    storage: &dyn Storage,
+    parent_order_id: u64,
+) -> StdResult<Vec<PerpetualOrder>> {
+    PERPETUAL_ORDER
+        .idx
+        .parent
+        .prefix(parent_order_id)
+        .range(storage, None, None, Order::Ascending)
+        .map(|res| res.map(|r| r.1))
+        .filter(|res| match res {
+            Ok(child) => {
+                child.parent_order_id == Some(parent_order_id) && child.status == Status::Pending
+            }
+            Err(_) => true,
+        })
+        .collect()
+}
+
+// make the close orders attached to an open order processable once the position exists
+pub fn activate_child_perpetual_orders(
+    storage: &mut dyn Storage,
+    parent: &PerpetualOrder,
+) -> StdResult<()> {
+    let children = get_pending_child_perpetual_orders(storage, parent.order_id)?;
+
+    for mut child in children {
+        child.position_id = parent.position_id;
+        PERPETUAL_ORDER.save(storage, child.order_id, &child)?;
+        PENDING_PERPETUAL_ORDER.save(storage, child.order_id, &child)?;
+    }
+
+    Ok(())
+}
+
+pub fn cancel_child_perpetual_orders(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    parent_order_id: u64,
+) -> StdResult<Vec<Event>> {
+    let children = get_pending_child_perpetual_orders(storage, parent_order_id)?;
+
+    let mut events: Vec<Event> = vec![];
+
+    for mut child in children {
+        let reason = CancelReason::ParentOrderCanceled {
+            order_id: parent_order_id,
+        };
+        child.cancel(reason.clone());
+        PERPETUAL_ORDER.save(storage, child.order_id, &child)?;
+        PENDING_PERPETUAL_ORDER.remove(storage, child.order_id)?;
+        events.push(cancel_event(
+            perpetual_order_event("cancel_perpetual_order", &child),
+            &reason,
+        )?);
+        log_perpetual_order(
+            storage,
+            child.order_id,
+            block,
+            OrderEvent::Canceled { reason },
+        )?;
+    }
+
+    Ok(events)
+}
+
+// pending close orders of a position, in the order they were created, the health monitors
+// adding collateral are left out since they don't close anything
+pub fn get_pending_close_orders(
+    storage: &dyn Storage,
+    owner: &str,
+    position_id: u64,
+) -> StdResult<Vec<PerpetualOrder>> {
+    PERPETUAL_ORDER
+        .idx
+        .owner
+        .prefix(owner.to_string())
+        .range(storage, None, None, Order::Ascending)
+        .map(|res| res.map(|r| r.1))
+        .filter(|res| match res {
+            Ok(order) => {
+                order.position_id == Some(position_id)
+                    && order.status == Status::Pending
+                    && order.order_type != PerpetualOrderType::LimitOpen
+                    && order.escrowed_collateral.is_none()
+            }
+            Err(_) => true,
+        })
+        .collect()
+}
+
+// custody left in the position of a close order, none once the position is closed
+pub fn get_position_custody(querier: &ElysQuerier<'_>, order: &PerpetualOrder) -> Option<Int128> {
+    let position_id = order.position_id?;
+    querier
+        .mtp(order.owner.clone(), position_id)
+        .ok()
+        .and_then(|resp| resp.mtp)
+        .map(|mtp| mtp.custody)
+}
+
+// the pending close orders of the same type can never together close more than the custody
+pub fn check_close_size(
+    close_orders: &[PerpetualOrder],
+    order: &PerpetualOrder,
+    custody: Int128,
+) -> Result<(), ContractError> {
+    let used = close_orders
+        .iter()
+        .filter(|other| other.order_type == order.order_type && other.order_id != order.order_id)
+        .fold(Int128::zero(), |used, other| {
+            used + other.close_amount(custody)
+        });
+
+    let available = custody
+        .checked_sub(used)
+        .unwrap_or_default()
+        .max(Int128::zero());
+    if available.is_zero() || order.close_amount(custody) > available {
+        return Err(ContractError::CloseSizeTooLarge { available });
+    }
+    Ok(())
+}
+
+// keep the pending close orders of a position consistent once one of them is executed:
+// the orders of the same type keep closing the amount they would have closed before, the
+// other types keep their share of what is left, orders of a type are cut down to the custody
+// left and canceled when nothing is left, and all of them are canceled once the position is gone
+pub fn resize_close_orders(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    executed: &PerpetualOrder,
+    closed: Int128,
+    custody: Option<Int128>,
+) -> StdResult<Vec<Event>> {
+    let position_id = match executed.position_id {
+        Some(position_id) => position_id,
+        None => return Ok(vec![]),
+    };
+
+    let close_orders: Vec<PerpetualOrder> =
+        get_pending_close_orders(storage, &executed.owner, position_id)?
+            .into_iter()
+            .filter(|order| order.order_id != executed.order_id)
+            .collect();
+
+    let mut events: Vec<Event> = vec![];
+    let mut used: Vec<(PerpetualOrderType, Int128)> = vec![];
+
+    for mut order in close_orders {
+        let custody = match custody {
+            Some(custody) if !custody.is_zero() => custody,
+            _ => {
+                events.push(cancel_close_order(
+                    storage,
+                    block,
+                    &mut order,
+                    CancelReason::PositionNotFound,
+                )?);
+                continue;
+            }
+        };
+
+        // a percentage is turned into the amount it closed before the execution
+        let mut close_size = order.close_size.clone();
+        if order.order_type == executed.order_type {
+            if let Some(CloseSize::Percentage(_)) = close_size {
+                let amount = order.close_amount(custody + closed);
+                close_size = Some(CloseSize::Amount(Uint128::new(amount.i128() as u128)));
+            }
+        }
+
+        let index = match used.iter().position(|(ty, _)| ty == &order.order_type) {
+            Some(index) => index,
+            None => {
+                used.push((order.order_type.clone(), Int128::zero()));
+                used.len() - 1
+            }
+        };
+
+        let available = custody.checked_sub(used[index].1).unwrap_or_default();
+        if available <= Int128::zero() {
+            events.push(cancel_close_order(
+                storage,
+                block,
+                &mut order,
+                CancelReason::CloseSizeExceeded,
+            )?);
+            continue;
+        }
+
+        let amount = match &close_size {
+            Some(close_size) => close_size.amount(custody),
+            None => custody,
+        };
+        if amount > available {
+            close_size = Some(CloseSize::Amount(Uint128::new(available.i128() as u128)));
+        }
+        used[index].1 += amount.min(available);
+
+        if close_size == order.close_size {
+            continue;
+        }
+
+        order.close_size = close_size;
+        PERPETUAL_ORDER.save(storage, order.order_id, &order)?;
+        PENDING_PERPETUAL_ORDER.save(storage, order.order_id, &order)?;
+        log_perpetual_order(storage, order.order_id, block, OrderEvent::Updated)?;
+        events.push(
+            perpetual_order_event("resize_perpetual_order", &order)
+                .add_attribute("close_size", to_json_string(&order.close_size)?),
+        );
+    }
+
+    Ok(events)
+}
+
+fn cancel_close_order(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    order: &mut PerpetualOrder,
+    reason: CancelReason,
+) -> StdResult<Event> {
+    order.cancel(reason.clone());
+    PERPETUAL_ORDER.save(storage, order.order_id, order)?;
+    PENDING_PERPETUAL_ORDER.remove(storage, order.order_id)?;
+    log_perpetual_order(
+        storage,
+        order.order_id,
+        block,
+        OrderEvent::Canceled {
+            reason: reason.clone(),
+        },
+    )?;
+    cancel_event(
+        perpetual_order_event("cancel_perpetual_order", order),
+        &reason,
+    )
+}
+
+pub type EntryRange<'a, T> = Box<dyn Iterator<Item = StdResult<(u64, T)>> + 'a>;
+
+// every entry matching the filter, or a page of them when a pagination is given,
+// `range` ranges the entries within the bounds of the pagination
+pub fn page_entries<'a, T>(
+    pagination: Option<PageRequest>,
+    range: impl Fn(Option<&PageRequest>) -> StdResult<EntryRange<'a, T>>,
+    matches: impl Fn(&T) -> bool,
+) -> StdResult<(Vec<T>, Option<PageResponse>)> {
+    let pagination = match pagination {
+        Some(pagination) => pagination,
+        None => {
+            let entries = range(None)?
+                .map(|res| res.map(|r| r.1))
+                .filter(|res| match res {
+                    Ok(entry) => matches(entry),
+                    Err(_) => true,
+                })
+                .collect::<StdResult<Vec<T>>>()?;
+            return Ok((entries, None));
+        }
+    };
+
+    let total = if pagination.count_total {
+        Some(range(None)?.try_fold(0, |total, res| {
+            res.map(|(_, entry)| total + matches(&entry) as u64)
+        })?)
+    } else {
+        None
+    };
+
+    // only the entries of the page are read, the first unreadable one fails the query
+    let mut error = None;
+    let (entries, page_response) = pagination.page(
+        range(Some(&pagination))?
+            .filter(|res| match res {
+                Ok((_, entry)) => matches(entry),
+                Err(_) => true,
+            })
+            .map_while(|res| res.map_err(|err| error = Some(err)).ok()),
+        total,
+    )?;
+    if let Some(err) = error {
+        return Err(err);
+    }
+
+    Ok((entries, Some(page_response)))
+}
@@ -25,7 +25,7 @@ fn succesful_cancel_an_order() {
                 quote_denom: "usdc".to_string(),
                 rate: Decimal::from_str("20000.0").unwrap(),
             }),
-            &vec![],
+            0,
         )
         .unwrap()],
     };
@@ -24,7 +24,7 @@ fn unauthorize() {
                 quote_denom: "usdc".to_string(),
                 rate: Decimal::from_str("20000.0").unwrap(),
             }),
-            &vec![],
+            0,
         )
         .unwrap()],
     };
@@ -30,6 +30,14 @@ fn process_spot_order_processing() {
             height: 20,
             time: Timestamp::from_seconds(644),
         },
+        trailing_stop: None,
+        group_id: None,
+        expiration: None,
+        max_slippage: None,
+        filled_amount: None,
+        twap_schedule: None,
+        cancel_reason: None,
+        fee: None,
     };
 
     // Create a mock message to instantiate the contract with the dummy order.
@@ -1,4 +1,5 @@
 use super::*;
+use elys_bindings::trade_shield::msg::query_resp::GetSpotOrderResp;
 use get_order_id_from_events::get_order_id_from_events;
 
 // This test case verifies the successful cancellation of a created order in the contract.
@@ -53,6 +54,11 @@ fn successful_cancel_order_with_created_order() {
                 }),
                 order_source_denom: "eth".to_owned(),
                 order_target_denom: "btc".to_string(),
+                trailing_distance: None,
+                linked_order_id: None,
+                expiration: None,
+                max_slippage: None,
+                min_out: None,
             },
             &coins(45, "eth"),
         )
@@ -70,6 +76,15 @@ fn successful_cancel_order_with_created_order() {
     )
     .unwrap();
 
+    // Verify that the order records that it was canceled by its owner.
+    let order: GetSpotOrderResp = app
+        .wrap()
+        .query_wasm_smart(addr.clone(), &QueryMsg::GetSpotOrder { order_id: id })
+        .unwrap();
+
+    assert_eq!(order.order.status, Status::Canceled);
+    assert_eq!(order.order.cancel_reason, Some(CancelReason::User));
+
     // Verify that the "user" now has a balance of 150 ETH, and the contract address has 0 ETH.
     assert_eq!(
         app.wrap()
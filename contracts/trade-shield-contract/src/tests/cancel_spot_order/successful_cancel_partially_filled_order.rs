@@ -0,0 +1,128 @@
+use super::*;
+use cosmwasm_std::Coin;
+use elys_bindings::trade_shield::msg::query_resp::GetSpotOrderResp;
+
+use crate::tests::get_order_id_from_events::get_order_id_from_events;
+
+// This test case verifies that canceling a partially filled order only refunds the amount that was not swapped.
+// - The user sells 100 BTC at 30,000 USDC, a 4% slippage limits the first fill to 25 BTC.
+// - The user cancels the order and gets back the 75 remaining BTC.
+#[test]
+fn successful_cancel_partially_filled_order() {
+    let wallets: Vec<(&str, Vec<Coin>)> = vec![("user", coins(100, "btc"))];
+    let mut app = ElysApp::new_with_wallets(wallets);
+
+    let usdc = "ibc/2180E84E20F5679FCC760D8C165B60F42065DEF7F46A72B447CFF1B7DC6C0A65";
+    let prices = vec![
+        Price::new(
+            "btc",
+            Decimal::from_atomics(Uint128::new(30000), 0).unwrap(),
+        ),
+        Price::new(usdc, Decimal::one()),
+    ];
+
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices))
+        .unwrap();
+
+    let code = ContractWrapper::new(execute, instantiate, query)
+        .with_reply(reply)
+        .with_sudo(sudo);
+    let code_id = app.store_code(Box::new(code));
+
+    let instantiate_msg = InstantiateMockMsg {
+        account_history_address: None,
+        spot_orders: vec![],
+        perpetual_orders: vec![],
+    };
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg,
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let resp = app
+        .execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::CreateSpotOrder {
+                order_type: SpotOrderType::LimitSell,
+                order_source_denom: "btc".to_string(),
+                order_target_denom: usdc.to_string(),
+                order_price: Some(OrderPrice {
+                    base_denom: "btc".to_string(),
+                    quote_denom: usdc.to_string(),
+                    rate: Decimal::from_atomics(Uint128::new(30000), 0).unwrap(),
+                }),
+                trailing_distance: None,
+                linked_order_id: None,
+                expiration: None,
+                max_slippage: None,
+                min_out: None,
+            },
+            &coins(100, "btc"),
+        )
+        .unwrap();
+
+    let order_id = get_order_id_from_events(&resp.events).unwrap();
+    let sudo_msg = SudoMsg::ClockEndBlock {};
+
+    let get_order = |app: &ElysApp| -> SpotOrder {
+        let resp: GetSpotOrderResp = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetSpotOrder { order_id })
+            .unwrap();
+        resp.order
+    };
+
+    let usdc_balance = |app: &ElysApp| -> u128 {
+        app.wrap()
+            .query_balance("user", usdc)
+            .unwrap()
+            .amount
+            .u128()
+    };
+
+    app.init_modules(|router, _, store| {
+        router.custom.set_swap_slippage(store, Decimal::percent(4))
+    })
+    .unwrap();
+    app.wasm_sudo(addr.clone(), &sudo_msg).unwrap();
+
+    assert_eq!(get_order(&app).status, Status::PartiallyFilled);
+
+    app.execute_contract(
+        Addr::unchecked("user"),
+        addr.clone(),
+        &ExecuteMsg::CancelSpotOrder { order_id },
+        &[],
+    )
+    .unwrap();
+
+    let order = get_order(&app);
+    assert_eq!(order.status, Status::Canceled);
+    assert_eq!(order.filled_amount, Some(Uint128::new(25)));
+
+    assert_eq!(
+        app.wrap()
+            .query_balance("user", "btc")
+            .unwrap()
+            .amount
+            .u128(),
+        75
+    );
+    assert_eq!(usdc_balance(&app), 750000);
+    assert_eq!(
+        app.wrap()
+            .query_balance(&addr, "btc")
+            .unwrap()
+            .amount
+            .u128(),
+        0
+    );
+}
@@ -26,6 +26,14 @@ fn successfully_cancel_orders_ids() {
                 height: 20,
                 time: Timestamp::from_seconds(500),
             },
+            trailing_stop: None,
+            group_id: None,
+            expiration: None,
+            max_slippage: None,
+            filled_amount: None,
+            twap_schedule: None,
+            cancel_reason: None,
+            fee: None,
         },
         SpotOrder {
             order_type: SpotOrderType::LimitSell,
@@ -43,6 +51,14 @@ fn successfully_cancel_orders_ids() {
                 height: 20,
                 time: Timestamp::from_seconds(500),
             },
+            trailing_stop: None,
+            group_id: None,
+            expiration: None,
+            max_slippage: None,
+            filled_amount: None,
+            twap_schedule: None,
+            cancel_reason: None,
+            fee: None,
         },
         SpotOrder {
             order_type: SpotOrderType::StopLoss,
@@ -60,6 +76,14 @@ fn successfully_cancel_orders_ids() {
                 height: 20,
                 time: Timestamp::from_seconds(500),
             },
+            trailing_stop: None,
+            group_id: None,
+            expiration: None,
+            max_slippage: None,
+            filled_amount: None,
+            twap_schedule: None,
+            cancel_reason: None,
+            fee: None,
         },
         SpotOrder {
             order_type: SpotOrderType::StopLoss,
@@ -77,6 +101,14 @@ fn successfully_cancel_orders_ids() {
                 height: 20,
                 time: Timestamp::from_seconds(500),
             },
+            trailing_stop: None,
+            group_id: None,
+            expiration: None,
+            max_slippage: None,
+            filled_amount: None,
+            twap_schedule: None,
+            cancel_reason: None,
+            fee: None,
         },
     ];
 
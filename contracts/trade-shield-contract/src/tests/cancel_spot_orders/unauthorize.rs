@@ -26,6 +26,14 @@ fn unauthorize() {
                 height: 5,
                 time: Timestamp::from_seconds(15),
             },
+            trailing_stop: None,
+            group_id: None,
+            expiration: None,
+            max_slippage: None,
+            filled_amount: None,
+            twap_schedule: None,
+            cancel_reason: None,
+            fee: None,
         }],
         perpetual_orders: vec![],
     };
@@ -0,0 +1,111 @@
+use super::*;
+use crate::tests::get_order_id_from_events::get_attr_from_events;
+use cosmwasm_std::{Addr, Decimal, SignedDecimal};
+use elys_bindings::trade_shield::msg::query_resp::GetPerpetualOrderResp;
+
+// This test case verifies that the close orders of a bracket order are activated once the position is opened.
+// - The user opens a long position at market price (2) with a take profit at 3 and a stop loss at 1.5.
+// - Both child orders are attached to the new position and wait to be processed.
+// - The market price reaches 4, the position is closed by the take profit and the stop loss is canceled.
+#[test]
+fn successful_process_bracket_order() {
+    let usdc = "ibc/2180E84E20F5679FCC760D8C165B60F42065DEF7F46A72B447CFF1B7DC6C0A65";
+    let wallets = vec![("user", coins(100, usdc))];
+    let mut app = ElysApp::new_with_wallets(wallets);
+
+    let prices = |ubtc_price: &str| {
+        vec![
+            Price::new("ubtc", Decimal::from_str(ubtc_price).unwrap()),
+            Price::new(usdc, Decimal::one()),
+        ]
+    };
+
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices("0.5")))
+        .unwrap();
+
+    let code = ContractWrapper::new(execute, instantiate, query)
+        .with_reply(reply)
+        .with_sudo(sudo);
+    let code_id = app.store_code(Box::new(code));
+
+    let instantiate_msg = InstantiateMockMsg {
+        account_history_address: None,
+        spot_orders: vec![],
+        perpetual_orders: vec![],
+    };
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg,
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let order_price = |rate: &str| OrderPrice {
+        base_denom: usdc.to_string(),
+        quote_denom: "ubtc".to_string(),
+        rate: Decimal::from_str(rate).unwrap(),
+    };
+
+    let resp = app
+        .execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::CreatePerpetualBracketOrder {
+                position: PerpetualPosition::Long,
+                leverage: SignedDecimal::from_str("5").unwrap(),
+                trading_asset: "ubtc".to_string(),
+                take_profit_price: None,
+                order_type: PerpetualOrderType::MarketOpen,
+                trigger_price: None,
+                take_profit_trigger_price: order_price("3"),
+                stop_loss_trigger_price: order_price("1.5"),
+                expiration: None,
+            },
+            &coins(100, usdc),
+        )
+        .unwrap();
+
+    let get_id = |key: &str| -> u64 {
+        get_attr_from_events(&resp.events, key)
+            .unwrap()
+            .parse()
+            .unwrap()
+    };
+    let take_profit_id = get_id("take_profit_order_id");
+    let stop_loss_id = get_id("stop_loss_order_id");
+
+    let get_order = |app: &ElysApp, id: u64| -> PerpetualOrder {
+        let resp: GetPerpetualOrderResp = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetPerpetualOrder { id })
+            .unwrap();
+        resp.order
+    };
+
+    let take_profit = get_order(&app, take_profit_id);
+    let stop_loss = get_order(&app, stop_loss_id);
+
+    assert_eq!(take_profit.status, Status::Pending);
+    assert_eq!(take_profit.position_id, Some(0));
+    assert_eq!(stop_loss.status, Status::Pending);
+    assert_eq!(stop_loss.position_id, Some(0));
+    assert_eq!(take_profit.group_id, stop_loss.group_id);
+
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices("0.25")))
+        .unwrap();
+    app.wasm_sudo(addr.clone(), &SudoMsg::ClockEndBlock {})
+        .unwrap();
+
+    let last_module = app
+        .init_modules(|router, _, store| router.custom.get_last_module(store).unwrap())
+        .unwrap();
+
+    assert_eq!(last_module, "PerpetualClose");
+    assert_eq!(get_order(&app, take_profit_id).status, Status::Executed);
+    assert_eq!(get_order(&app, stop_loss_id).status, Status::Canceled);
+}
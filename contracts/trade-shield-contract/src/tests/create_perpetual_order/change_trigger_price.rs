@@ -93,6 +93,11 @@ fn successful_create_perpetual_order() {
                     rate: first_trigger_price.clone(),
                 }),
                 position_id: Some(mtp_id),
+                trailing_distance: None,
+                linked_order_id: None,
+                expiration: None,
+                close_size: None,
+                health_threshold: None,
             },
             &[],
         )
@@ -131,6 +136,11 @@ fn successful_create_perpetual_order() {
                     rate: second_trigger_price.clone(),
                 }),
                 position_id: Some(mtp_id),
+                trailing_distance: None,
+                linked_order_id: None,
+                expiration: None,
+                close_size: None,
+                health_threshold: None,
             },
             &[],
         )
@@ -46,6 +46,11 @@ fn coin_number() {
                 rate: Decimal::from_str("1.5").unwrap(),
             }),
             position_id: None,
+            trailing_distance: None,
+            linked_order_id: None,
+            expiration: None,
+            close_size: None,
+            health_threshold: None,
         },
         &[],
     )
@@ -0,0 +1,137 @@
+use super::*;
+use crate::tests::get_order_id_from_events::get_attr_from_events;
+use cosmwasm_std::{Int128, SignedDecimal, SignedDecimal256};
+
+// This test case verifies that a perpetual order can only be linked to an order of the same position.
+// - The user holds 2 long BTC positions and places a stop loss on the first one.
+// - A take profit on the second position linked to that stop loss is rejected.
+// - A take profit on the first position linked to that stop loss is accepted.
+#[test]
+fn link_other_position() {
+    let usdc = "ibc/2180E84E20F5679FCC760D8C165B60F42065DEF7F46A72B447CFF1B7DC6C0A65";
+
+    let mut app = ElysApp::new();
+
+    let prices = vec![
+        Price::new("ubtc", Decimal::from_str("0.5").unwrap()),
+        Price::new(usdc, Decimal::one()),
+    ];
+
+    let mtp = |id: u64| Mtp {
+        address: "user".to_string(),
+        liabilities: Int128::zero(),
+        take_profit_liabilities: Int128::zero(),
+        mtp_health: SignedDecimal::one(),
+        position: PerpetualPosition::Long as i32,
+        id,
+        amm_pool_id: 1,
+        consolidate_leverage: SignedDecimal::zero(),
+        sum_collateral: Int128::zero(),
+        take_profit_price: SignedDecimal256::from_str("1.2").unwrap(),
+        borrow_interest_paid_collateral: Int128::zero(),
+        borrow_interest_paid_custody: Int128::zero(),
+        borrow_interest_unpaid_collateral: Int128::zero(),
+        collateral_asset: usdc.to_string(),
+        collateral: Int128::new(100),
+        custody: Int128::new(400),
+        custody_asset: "ubtc".to_string(),
+        funding_fee_paid_collateral: Int128::zero(),
+        funding_fee_paid_custody: Int128::zero(),
+        funding_fee_received_collateral: Int128::zero(),
+        funding_fee_received_custody: Int128::zero(),
+        leverage: SignedDecimal::from_str("2").unwrap(),
+        liabilities_asset: usdc.to_string(),
+        open_price: SignedDecimal::one(),
+        take_profit_borrow_rate: SignedDecimal::one(),
+        take_profit_custody: Int128::zero(),
+        trading_asset: "ubtc".to_string(),
+    };
+
+    app.init_modules(|router, _, storage| router.custom.set_mtp(storage, &vec![mtp(1), mtp(2)]))
+        .unwrap();
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices))
+        .unwrap();
+
+    let code = ContractWrapper::new(execute, instantiate, query);
+    let code_id = app.store_code(Box::new(code));
+
+    let instantiate_msg = InstantiateMockMsg {
+        account_history_address: None,
+        spot_orders: vec![],
+        perpetual_orders: vec![],
+    };
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg,
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let create_order_msg = |order_type: PerpetualOrderType,
+                            rate: &str,
+                            position_id: u64,
+                            linked_order_id: Option<u64>| {
+        ExecuteMsg::CreatePerpetualOrder {
+            position: None,
+            leverage: None,
+            trading_asset: None,
+            take_profit_price: None,
+            order_type,
+            trigger_price: Some(OrderPrice {
+                base_denom: usdc.to_string(),
+                quote_denom: "ubtc".to_string(),
+                rate: Decimal::from_str(rate).unwrap(),
+            }),
+            position_id: Some(position_id),
+            trailing_distance: None,
+            linked_order_id,
+            expiration: None,
+            close_size: None,
+            health_threshold: None,
+        }
+    };
+
+    let stop_loss_id: u64 = get_attr_from_events(
+        &app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &create_order_msg(PerpetualOrderType::StopLoss, "0.1", 1, None),
+            &[],
+        )
+        .unwrap()
+        .events,
+        "perpetual_order_id",
+    )
+    .unwrap()
+    .parse()
+    .unwrap();
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &create_order_msg(PerpetualOrderType::LimitClose, "2", 2, Some(stop_loss_id)),
+            &[],
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        ContractError::LinkPositionMismatch {
+            order_id: stop_loss_id
+        },
+        err.downcast().unwrap()
+    );
+
+    app.execute_contract(
+        Addr::unchecked("user"),
+        addr.clone(),
+        &create_order_msg(PerpetualOrderType::LimitClose, "2", 1, Some(stop_loss_id)),
+        &[],
+    )
+    .unwrap();
+}
@@ -286,6 +286,11 @@ fn reproduce_testnet_issue_create_perpetual_market_open_order() {
             order_type: PerpetualOrderType::MarketOpen,
             trigger_price: None,
             position_id: None,
+            trailing_distance: None,
+            linked_order_id: None,
+            expiration: None,
+            close_size: None,
+            health_threshold: None,
         },
         &coins(
             100__000_000,
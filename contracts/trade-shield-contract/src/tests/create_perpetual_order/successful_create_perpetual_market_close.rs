@@ -80,6 +80,11 @@ fn successful_create_perpetual_market_open_order() {
             take_profit_price: None,
             order_type: PerpetualOrderType::MarketClose,
             trigger_price: None,
+            trailing_distance: None,
+            linked_order_id: None,
+            expiration: None,
+            close_size: None,
+            health_threshold: None,
         },
         &[],
     )
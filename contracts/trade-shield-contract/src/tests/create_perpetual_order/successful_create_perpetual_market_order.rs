@@ -51,6 +51,11 @@ fn successful_create_perpetual_market_open_order() {
                 rate: Decimal::from_str("1.7").unwrap(),
             }),
             position_id: None,
+            trailing_distance: None,
+            linked_order_id: None,
+            expiration: None,
+            close_size: None,
+            health_threshold: None,
         },
         &coins(10, "btc"), // User's BTC balance.
     )
@@ -63,6 +63,11 @@ fn successful_create_perpetual_order() {
                     rate: Decimal::from_str("40000.1").unwrap(),
                 }),
                 position_id: None,
+                trailing_distance: None,
+                linked_order_id: None,
+                expiration: None,
+                close_size: None,
+                health_threshold: None,
             },
             &coins(
                 30000,
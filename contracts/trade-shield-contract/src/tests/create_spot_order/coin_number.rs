@@ -45,6 +45,11 @@ fn coin_number() {
                 }),
                 order_source_denom: "eth".to_owned(),
                 order_target_denom: "btc".to_string(),
+                trailing_distance: None,
+                linked_order_id: None,
+                expiration: None,
+                max_slippage: None,
+                min_out: None,
             },
             &[],
         )
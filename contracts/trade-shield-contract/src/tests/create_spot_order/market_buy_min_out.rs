@@ -0,0 +1,100 @@
+use super::*;
+
+// This test case verifies that a "market" order is reverted when the swap cannot provide the minimum output requested.
+// - The user sells 2 BTC at 30,000 USDC and asks for at least 60,001 USDC, the order fails.
+// - A minimum output too large for the swap message is rejected instead of wrapping around.
+// - The user asks for at least 60,000 USDC, the order is executed.
+#[test]
+fn market_buy_min_out() {
+    let wallet = vec![("user", coins(2, "btc"))];
+
+    let prices = vec![
+        Price::new(
+            "btc",
+            Decimal::from_atomics(Uint128::new(30000), 0).unwrap(),
+        ),
+        Price::new("usdc", Decimal::from_atomics(Uint128::new(1), 0).unwrap()),
+    ];
+
+    let mut app = ElysApp::new_with_wallets(wallet);
+
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices))
+        .unwrap();
+
+    let instantiate_msg = InstantiateMockMsg {
+        account_history_address: None,
+        spot_orders: vec![],
+        perpetual_orders: vec![],
+    };
+
+    let code = ContractWrapper::new(execute, instantiate, query)
+        .with_reply(reply)
+        .with_sudo(sudo);
+    let code_id = app.store_code(Box::new(code));
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg,
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let create_msg = |min_out: u128| ExecuteMsg::CreateSpotOrder {
+        order_type: SpotOrderType::MarketBuy,
+        order_price: None,
+        order_source_denom: "btc".to_string(),
+        order_target_denom: "usdc".to_string(),
+        trailing_distance: None,
+        linked_order_id: None,
+        expiration: None,
+        max_slippage: None,
+        min_out: Some(Uint128::new(min_out)),
+    };
+
+    app.execute_contract(
+        Addr::unchecked("user"),
+        addr.clone(),
+        &create_msg(60001),
+        &coins(2, "btc"),
+    )
+    .unwrap_err();
+
+    // The failed order is reverted and the user keeps his BTC.
+    assert_eq!(
+        app.wrap()
+            .query_balance("user", "btc")
+            .unwrap()
+            .amount
+            .u128(),
+        2
+    );
+
+    app.execute_contract(
+        Addr::unchecked("user"),
+        addr.clone(),
+        &create_msg(u128::MAX),
+        &coins(2, "btc"),
+    )
+    .unwrap_err();
+
+    app.execute_contract(
+        Addr::unchecked("user"),
+        addr.clone(),
+        &create_msg(60000),
+        &coins(2, "btc"),
+    )
+    .unwrap();
+
+    assert_eq!(
+        app.wrap()
+            .query_balance("user", "usdc")
+            .unwrap()
+            .amount
+            .u128(),
+        60000
+    );
+}
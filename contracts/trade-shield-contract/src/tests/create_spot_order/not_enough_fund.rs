@@ -29,6 +29,11 @@ fn not_enough_fund() {
 
         order_source_denom: "eth".to_string(),
         order_target_denom: "btc".to_string(),
+        trailing_distance: None,
+        linked_order_id: None,
+        expiration: None,
+        max_slippage: None,
+        min_out: None,
     };
 
     // Create a contract wrapper and store its code.
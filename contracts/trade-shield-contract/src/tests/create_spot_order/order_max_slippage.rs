@@ -0,0 +1,107 @@
+use super::*;
+use cosmwasm_std::StdError;
+
+// This test case verifies that the slippage parameters are validated when creating a spot order.
+#[test]
+fn order_max_slippage() {
+    let mut app = ElysApp::new_with_wallets(vec![("user", coins(2, "btc"))]);
+
+    let prices = vec![
+        Price::new(
+            "btc",
+            Decimal::from_atomics(Uint128::new(30000), 0).unwrap(),
+        ),
+        Price::new("usdc", Decimal::one()),
+    ];
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices))
+        .unwrap();
+
+    let instantiate_msg = InstantiateMockMsg {
+        account_history_address: None,
+        spot_orders: vec![],
+        perpetual_orders: vec![],
+    };
+
+    let code = ContractWrapper::new(execute, instantiate, query);
+    let code_id = app.store_code(Box::new(code));
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg,
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let create_msg =
+        |max_slippage: Option<Decimal>, min_out: Option<Uint128>| ExecuteMsg::CreateSpotOrder {
+            order_type: SpotOrderType::LimitSell,
+            order_source_denom: "btc".to_string(),
+            order_target_denom: "usdc".to_string(),
+            order_price: Some(OrderPrice {
+                base_denom: "btc".to_string(),
+                quote_denom: "usdc".to_string(),
+                rate: Decimal::from_atomics(Uint128::new(40000), 0).unwrap(),
+            }),
+            trailing_distance: None,
+            linked_order_id: None,
+            expiration: None,
+            max_slippage,
+            min_out,
+        };
+
+    // A slippage of 100% would accept any output.
+    let err = app
+        .execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &create_msg(Some(Decimal::one()), None),
+            &coins(2, "btc"),
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        ContractError::StdError(StdError::generic_err(
+            "max_slippage: The slippage should be lower than 1"
+        )),
+        err.downcast().unwrap()
+    );
+
+    // min_out is reserved to market orders.
+    let err = app
+        .execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &create_msg(None, Some(Uint128::new(80000))),
+            &coins(2, "btc"),
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        ContractError::StdError(StdError::generic_err(
+            "min_out: Only used by MarketBuy orders"
+        )),
+        err.downcast().unwrap()
+    );
+
+    let resp = app
+        .execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &create_msg(Some(Decimal::percent(2)), None),
+            &coins(2, "btc"),
+        )
+        .unwrap();
+
+    let order_id =
+        crate::tests::get_order_id_from_events::get_order_id_from_events(&resp.events).unwrap();
+    let resp: crate::msg::query_resp::GetSpotOrderResp = app
+        .wrap()
+        .query_wasm_smart(&addr, &QueryMsg::GetSpotOrder { order_id })
+        .unwrap();
+
+    assert_eq!(resp.order.max_slippage, Some(Decimal::percent(2)));
+}
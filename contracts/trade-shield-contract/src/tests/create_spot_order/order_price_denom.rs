@@ -28,6 +28,11 @@ fn order_price_denom() {
 
         order_source_denom: "eth".to_string(),
         order_target_denom: "btc".to_string(),
+        trailing_distance: None,
+        linked_order_id: None,
+        expiration: None,
+        max_slippage: None,
+        min_out: None,
     };
 
     let code = ContractWrapper::new(execute, instantiate, query);
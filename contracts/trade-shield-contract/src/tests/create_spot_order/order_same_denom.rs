@@ -24,6 +24,11 @@ fn order_same_denom() {
 
         order_source_denom: "eth".to_string(),
         order_target_denom: "eth".to_string(), // Same denomination for base and quote tokens.
+        trailing_distance: None,
+        linked_order_id: None,
+        expiration: None,
+        max_slippage: None,
+        min_out: None,
     };
 
     let code = ContractWrapper::new(execute, instantiate, query);
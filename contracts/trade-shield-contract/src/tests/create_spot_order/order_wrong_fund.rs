@@ -25,6 +25,11 @@ fn order_wrong_fund() {
 
         order_source_denom: "usdc".to_string(), // Incorrect source denomination.
         order_target_denom: "btc".to_string(),
+        trailing_distance: None,
+        linked_order_id: None,
+        expiration: None,
+        max_slippage: None,
+        min_out: None,
     };
 
     let code = ContractWrapper::new(execute, instantiate, query);
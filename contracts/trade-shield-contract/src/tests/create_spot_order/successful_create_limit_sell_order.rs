@@ -56,6 +56,11 @@ fn successful_create_limit_sell_order() {
 
                 order_source_denom: "btc".to_string(),
                 order_target_denom: "usdc".to_string(),
+                trailing_distance: None,
+                linked_order_id: None,
+                expiration: None,
+                max_slippage: None,
+                min_out: None,
             },
             &coins(2, "btc"), // User's BTC balance.
         )
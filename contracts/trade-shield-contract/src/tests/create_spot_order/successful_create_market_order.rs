@@ -59,6 +59,11 @@ fn successful_create_market_buy_order() {
             order_price: None,
             order_source_denom: "btc".to_string(),
             order_target_denom: "usdc".to_string(),
+            trailing_distance: None,
+            linked_order_id: None,
+            expiration: None,
+            max_slippage: None,
+            min_out: None,
         },
         &coins(2, "btc"), // User's BTC balance.
     )
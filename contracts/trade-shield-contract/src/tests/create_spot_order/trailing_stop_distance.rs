@@ -0,0 +1,98 @@
+use super::*;
+use elys_bindings::trade_shield::msg::query_resp::GetSpotOrderResp;
+
+use crate::tests::get_order_id_from_events::get_order_id_from_events;
+
+// This test case verifies that a "trailing-stop" order computes its trigger price from the market price.
+// - The BTC price is 30,000 USDC, the user sells 2 BTC without an order price.
+// - An absolute distance of 30,000 USDC or more is rejected since the stop price would be zero.
+// - An absolute distance of 5,000 USDC gives a trigger price of 25,000 USDC.
+#[test]
+fn trailing_stop_distance() {
+    let wallet = vec![("user", coins(2, "btc"))];
+    let usdc = "ibc/2180E84E20F5679FCC760D8C165B60F42065DEF7F46A72B447CFF1B7DC6C0A65";
+
+    let prices = vec![
+        Price::new(
+            "btc",
+            Decimal::from_atomics(Uint128::new(30000), 0).unwrap(),
+        ),
+        Price::new(usdc, Decimal::one()),
+    ];
+
+    let mut app = ElysApp::new_with_wallets(wallet);
+
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices))
+        .unwrap();
+
+    let instantiate_msg = InstantiateMockMsg {
+        account_history_address: None,
+        spot_orders: vec![],
+        perpetual_orders: vec![],
+    };
+
+    let code = ContractWrapper::new(execute, instantiate, query);
+    let code_id = app.store_code(Box::new(code));
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg,
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let create_msg = |offset: u128| ExecuteMsg::CreateSpotOrder {
+        order_type: SpotOrderType::TrailingStop,
+        order_price: None,
+        order_source_denom: "btc".to_string(),
+        order_target_denom: usdc.to_string(),
+        trailing_distance: Some(TrailingDistance::Absolute(
+            Decimal::from_atomics(Uint128::new(offset), 0).unwrap(),
+        )),
+        linked_order_id: None,
+        expiration: None,
+        max_slippage: None,
+        min_out: None,
+    };
+
+    for offset in [30000, 30001] {
+        let err = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &create_msg(offset),
+                &coins(2, "btc"),
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.root_cause().to_string(),
+            "Generic error: trailing_distance: The offset should be lower than the market price"
+        );
+    }
+
+    let resp = app
+        .execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &create_msg(5000),
+            &coins(2, "btc"),
+        )
+        .unwrap();
+
+    let order_id = get_order_id_from_events(&resp.events).unwrap();
+    let resp: GetSpotOrderResp = app
+        .wrap()
+        .query_wasm_smart(addr.clone(), &QueryMsg::GetSpotOrder { order_id })
+        .unwrap();
+
+    assert_eq!(resp.order.order_price.base_denom, "btc");
+    assert_eq!(resp.order.order_price.quote_denom, usdc);
+    assert_eq!(
+        resp.order.order_price.rate,
+        Decimal::from_atomics(Uint128::new(25000), 0).unwrap()
+    );
+}
@@ -0,0 +1,188 @@
+use super::*;
+use cosmwasm_std::Coin;
+
+// This test case verifies that orders on assets the contract does not support are rejected at creation.
+// - The admin turns on the asset validation, atom is not registered in the oracle and eth has no price.
+// - Limit buys of atom and eth are rejected and the user keeps their USDC.
+// - A limit buy of btc goes through until the admin restricts the allowed denoms to usdc and eth.
+#[test]
+fn unsupported_asset() {
+    let wallets: Vec<(&str, Vec<Coin>)> = vec![("user", coins(400, "usdc"))];
+    let mut app = ElysApp::new_with_wallets(wallets);
+
+    let prices = vec![
+        Price::new("btc", Decimal::from_str("30000.0").unwrap()),
+        Price::new("usdc", Decimal::one()),
+    ];
+    let asset_infos = vec![
+        OracleAssetInfo::new(
+            "usdc".to_string(),
+            "USDC".to_string(),
+            "USDC".to_string(),
+            "USDC".to_string(),
+            6,
+        ),
+        OracleAssetInfo::new(
+            "btc".to_string(),
+            "BTC".to_string(),
+            "BTC".to_string(),
+            "BTC".to_string(),
+            6,
+        ),
+        OracleAssetInfo::new(
+            "eth".to_string(),
+            "ETH".to_string(),
+            "ETH".to_string(),
+            "ETH".to_string(),
+            6,
+        ),
+    ];
+
+    app.init_modules(|router, _, store| {
+        router.custom.set_prices(store, &prices).unwrap();
+        router.custom.set_asset_infos(store, &asset_infos).unwrap();
+    });
+
+    let code = ContractWrapper::new(execute, instantiate, query);
+    let code_id = app.store_code(Box::new(code));
+
+    let instantiate_msg = InstantiateMockMsg {
+        account_history_address: None,
+        spot_orders: vec![],
+        perpetual_orders: vec![],
+    };
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg,
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let update_config_msg = |allowed_denoms: Option<Vec<String>>, validate_assets: Option<bool>| {
+        ExecuteMsg::UpdateConfig {
+            admin: None,
+            account_history_address: None,
+            allowed_pairs: None,
+            min_order_amounts: None,
+            max_order_amounts: None,
+            max_leverage: None,
+            pause: None,
+            fee_bps: None,
+            fee_collector: None,
+            allowed_denoms,
+            validate_assets,
+            process_order_limit: None,
+        }
+    };
+
+    let limit_buy_msg = |target_denom: &str| ExecuteMsg::CreateSpotOrder {
+        order_type: SpotOrderType::LimitBuy,
+        order_source_denom: "usdc".to_string(),
+        order_target_denom: target_denom.to_string(),
+        order_price: Some(OrderPrice {
+            base_denom: "usdc".to_string(),
+            quote_denom: target_denom.to_string(),
+            rate: Decimal::from_str("0.00003").unwrap(),
+        }),
+        trailing_distance: None,
+        linked_order_id: None,
+        expiration: None,
+        max_slippage: None,
+        min_out: None,
+    };
+
+    app.execute_contract(
+        Addr::unchecked("owner"),
+        addr.clone(),
+        &update_config_msg(None, Some(true)),
+        &[],
+    )
+    .unwrap();
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &limit_buy_msg("atom"),
+            &coins(100, "usdc"),
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        ContractError::AssetNotRegistered {
+            denom: "atom".to_string()
+        },
+        err.downcast().unwrap()
+    );
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &limit_buy_msg("eth"),
+            &coins(100, "usdc"),
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        ContractError::PriceUnavailable {
+            denom: "eth".to_string()
+        },
+        err.downcast().unwrap()
+    );
+
+    assert_eq!(
+        app.wrap()
+            .query_balance("user", "usdc")
+            .unwrap()
+            .amount
+            .u128(),
+        400
+    );
+
+    app.execute_contract(
+        Addr::unchecked("user"),
+        addr.clone(),
+        &limit_buy_msg("btc"),
+        &coins(100, "usdc"),
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("owner"),
+        addr.clone(),
+        &update_config_msg(Some(vec!["usdc".to_string(), "eth".to_string()]), None),
+        &[],
+    )
+    .unwrap();
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &limit_buy_msg("btc"),
+            &coins(100, "usdc"),
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        ContractError::DenomNotAllowed {
+            denom: "btc".to_string()
+        },
+        err.downcast().unwrap()
+    );
+
+    assert_eq!(
+        app.wrap()
+            .query_balance("user", "usdc")
+            .unwrap()
+            .amount
+            .u128(),
+        300
+    );
+}
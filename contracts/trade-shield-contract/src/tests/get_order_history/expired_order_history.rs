@@ -0,0 +1,124 @@
+use super::*;
+use cosmwasm_std::{Coin, StdError};
+use cw_utils::Expiration;
+use elys_bindings::trade_shield::msg::query_resp::GetOrderHistoryResp;
+
+use crate::tests::get_order_id_from_events::get_order_id_from_events;
+
+// This test case verifies that the history of an expired order records why it was canceled.
+// - The user places a limit sell of 2 BTC at 40,000 USDC which expires 10 blocks later.
+// - Once expired, the order is canceled by the processing of the orders.
+// - The history holds the creation and the cancellation with the expired reason.
+// - The history of an unknown order can't be queried.
+#[test]
+fn expired_order_history() {
+    let wallets: Vec<(&str, Vec<Coin>)> = vec![("user", coins(2, "btc"))];
+    let mut app = ElysApp::new_with_wallets(wallets);
+
+    let usdc = "ibc/2180E84E20F5679FCC760D8C165B60F42065DEF7F46A72B447CFF1B7DC6C0A65";
+    let prices = vec![
+        Price::new(
+            "btc",
+            Decimal::from_atomics(Uint128::new(30000), 0).unwrap(),
+        ),
+        Price::new(usdc, Decimal::one()),
+    ];
+
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices))
+        .unwrap();
+
+    let code = ContractWrapper::new(execute, instantiate, query)
+        .with_reply(reply)
+        .with_sudo(sudo);
+    let code_id = app.store_code(Box::new(code));
+
+    let instantiate_msg = InstantiateMockMsg {
+        account_history_address: None,
+        spot_orders: vec![],
+        perpetual_orders: vec![],
+    };
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg,
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let expiration_height = app.block_info().height + 10;
+
+    let resp = app
+        .execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::CreateSpotOrder {
+                order_type: SpotOrderType::LimitSell,
+                order_source_denom: "btc".to_string(),
+                order_target_denom: usdc.to_string(),
+                order_price: Some(OrderPrice {
+                    base_denom: "btc".to_string(),
+                    quote_denom: usdc.to_string(),
+                    rate: Decimal::from_atomics(Uint128::new(40000), 0).unwrap(),
+                }),
+                trailing_distance: None,
+                linked_order_id: None,
+                expiration: Some(Expiration::AtHeight(expiration_height)),
+                max_slippage: None,
+                min_out: None,
+            },
+            &coins(2, "btc"),
+        )
+        .unwrap();
+
+    let order_id = get_order_id_from_events(&resp.events).unwrap();
+
+    app.update_block(|block| block.height = expiration_height);
+    app.wasm_sudo(addr.clone(), &SudoMsg::ClockEndBlock {})
+        .unwrap();
+
+    let resp: GetOrderHistoryResp = app
+        .wrap()
+        .query_wasm_smart(
+            addr.clone(),
+            &QueryMsg::GetOrderHistory {
+                order_id,
+                order_kind: OrderKind::Spot,
+                pagination: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(resp.history.len(), 2);
+    assert_eq!(resp.history[0].event, OrderEvent::Created);
+    assert_eq!(
+        resp.history[1].event,
+        OrderEvent::Canceled {
+            reason: CancelReason::Expired,
+        }
+    );
+    assert_eq!(resp.history[1].date.height, expiration_height);
+
+    let err = app
+        .wrap()
+        .query_wasm_smart::<GetOrderHistoryResp>(
+            addr.clone(),
+            &QueryMsg::GetOrderHistory {
+                order_id,
+                order_kind: OrderKind::Perpetual,
+                pagination: None,
+            },
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        err,
+        StdError::generic_err(format!(
+            "Querier contract error: {}",
+            ContractError::OrderNotFound { order_id }
+        ))
+    );
+}
@@ -0,0 +1,153 @@
+use super::*;
+use cosmwasm_std::Coin;
+use elys_bindings::trade_shield::msg::query_resp::GetOrderHistoryResp;
+use elys_bindings::types::PageRequest;
+
+use crate::tests::get_order_id_from_events::get_order_id_from_events;
+
+// This test case verifies that the history of a spot order follows its lifecycle.
+// - The user places a limit sell of 2 BTC at 30,000 USDC while the BTC price is 20,000 USDC.
+// - The price reaches 30,000 USDC, the order is triggered and executed in the same block.
+// - The history holds the creation, the trigger at the market price and the execution.
+// - Read 2 entries at a time, the history is returned in the same order over 2 pages.
+#[test]
+fn successful_query_spot_order_history() {
+    let wallets: Vec<(&str, Vec<Coin>)> = vec![("user", coins(2, "btc"))];
+    let mut app = ElysApp::new_with_wallets(wallets);
+
+    let usdc = "ibc/2180E84E20F5679FCC760D8C165B60F42065DEF7F46A72B447CFF1B7DC6C0A65";
+    let prices_at_t0 = vec![
+        Price::new(
+            "btc",
+            Decimal::from_atomics(Uint128::new(20000), 0).unwrap(),
+        ),
+        Price::new(usdc, Decimal::one()),
+    ];
+    let prices_at_t1 = vec![
+        Price::new(
+            "btc",
+            Decimal::from_atomics(Uint128::new(30000), 0).unwrap(),
+        ),
+        Price::new(usdc, Decimal::one()),
+    ];
+
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices_at_t0))
+        .unwrap();
+
+    let code = ContractWrapper::new(execute, instantiate, query)
+        .with_reply(reply)
+        .with_sudo(sudo);
+    let code_id = app.store_code(Box::new(code));
+
+    let instantiate_msg = InstantiateMockMsg {
+        account_history_address: None,
+        spot_orders: vec![],
+        perpetual_orders: vec![],
+    };
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg,
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let resp = app
+        .execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::CreateSpotOrder {
+                order_type: SpotOrderType::LimitSell,
+                order_source_denom: "btc".to_string(),
+                order_target_denom: usdc.to_string(),
+                order_price: Some(OrderPrice {
+                    base_denom: "btc".to_string(),
+                    quote_denom: usdc.to_string(),
+                    rate: Decimal::from_atomics(Uint128::new(30000), 0).unwrap(),
+                }),
+                trailing_distance: None,
+                linked_order_id: None,
+                expiration: None,
+                max_slippage: None,
+                min_out: None,
+            },
+            &coins(2, "btc"),
+        )
+        .unwrap();
+
+    let order_id = get_order_id_from_events(&resp.events).unwrap();
+    let created_height = app.block_info().height;
+
+    app.update_block(|block| block.height += 1);
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices_at_t1))
+        .unwrap();
+    app.wasm_sudo(addr.clone(), &SudoMsg::ClockEndBlock {})
+        .unwrap();
+
+    let resp: GetOrderHistoryResp = app
+        .wrap()
+        .query_wasm_smart(
+            addr.clone(),
+            &QueryMsg::GetOrderHistory {
+                order_id,
+                order_kind: OrderKind::Spot,
+                pagination: None,
+            },
+        )
+        .unwrap();
+
+    let events: Vec<OrderEvent> = resp
+        .history
+        .iter()
+        .map(|entry| entry.event.clone())
+        .collect();
+
+    assert_eq!(resp.history.len(), 3);
+    assert_eq!(resp.history[0].date.height, created_height);
+    assert_eq!(resp.history[1].date.height, created_height + 1);
+    assert_eq!(events[0], OrderEvent::Created);
+    assert_eq!(
+        events[1],
+        OrderEvent::Triggered {
+            market_price: Decimal::from_atomics(Uint128::new(30000), 0).unwrap(),
+        }
+    );
+    match &events[2] {
+        OrderEvent::Executed { fill_amount, data } => {
+            assert_eq!(fill_amount, &Some(coin(2, "btc")));
+            assert!(data.is_some());
+        }
+        event => panic!("unexpected event {:?}", event),
+    }
+
+    let get_page = |key| -> GetOrderHistoryResp {
+        let mut pagination = PageRequest::new(2);
+        pagination.key = key;
+        pagination.count_total = true;
+        app.wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetOrderHistory {
+                    order_id,
+                    order_kind: OrderKind::Spot,
+                    pagination: Some(pagination),
+                },
+            )
+            .unwrap()
+    };
+
+    let first_page = get_page(None);
+    let first_page_response = first_page.page_response.unwrap();
+
+    assert_eq!(first_page.history, resp.history[..2].to_vec());
+    assert_eq!(first_page_response.total, Some(3));
+
+    let second_page = get_page(first_page_response.next_key);
+
+    assert_eq!(second_page.history, resp.history[2..].to_vec());
+    assert_eq!(second_page.page_response.unwrap().next_key, None);
+}
@@ -21,7 +21,7 @@ fn successful_query_message() {
             quote_denom: "usdc".to_string(),
             rate: Decimal::one(),
         }),
-        &vec![],
+        0,
     )
     .unwrap();
 
@@ -0,0 +1,115 @@
+use cosmwasm_std::SignedDecimal;
+
+use crate::msg::query_resp::GetPerpetualOrdersResp;
+
+use super::*;
+
+#[test]
+fn get_perpetual_orders_by_owner() {
+    let perpetual_orders: Vec<PerpetualOrder> = vec![
+        create_order(0, "userA", Status::Pending),
+        create_order(1, "userB", Status::Pending),
+        create_order(2, "userA", Status::Executed),
+        create_order(3, "userA", Status::Pending),
+        create_order(4, "userB", Status::Canceled),
+        create_order(5, "userA", Status::Pending),
+    ];
+    let mut app = ElysApp::new();
+
+    let instantiate_msg = InstantiateMockMsg {
+        account_history_address: None,
+        spot_orders: vec![],
+        perpetual_orders: perpetual_orders.clone(),
+    };
+
+    let code = ContractWrapper::new(execute, instantiate, query);
+    let code_id = app.store_code(Box::new(code));
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg,
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let mut page_req = PageRequest::new(2);
+    page_req.count_total = true;
+
+    let resp: GetPerpetualOrdersResp = app
+        .wrap()
+        .query_wasm_smart(
+            &addr,
+            &QueryMsg::GetPerpetualOrders {
+                pagination: Some(page_req.clone()),
+                order_owner: Some("userA".to_string()),
+                order_type: None,
+                order_status: Some(Status::Pending),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        resp.orders,
+        vec![perpetual_orders[0].clone(), perpetual_orders[3].clone()]
+    );
+    let page_response = resp.page_response.unwrap();
+    assert_eq!(page_response.total, Some(3));
+
+    page_req.update(page_response.next_key);
+
+    let resp: GetPerpetualOrdersResp = app
+        .wrap()
+        .query_wasm_smart(
+            &addr,
+            &QueryMsg::GetPerpetualOrders {
+                pagination: Some(page_req),
+                order_owner: Some("userA".to_string()),
+                order_type: None,
+                order_status: Some(Status::Pending),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(resp.orders, vec![perpetual_orders[5].clone()]);
+    assert_eq!(resp.page_response.unwrap().next_key, None);
+
+    let resp: GetPerpetualOrdersResp = app
+        .wrap()
+        .query_wasm_smart(
+            &addr,
+            &QueryMsg::GetPerpetualOrders {
+                pagination: None,
+                order_owner: None,
+                order_type: None,
+                order_status: Some(Status::Canceled),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(resp.orders, vec![perpetual_orders[4].clone()]);
+}
+
+fn create_order(order_id: u64, owner: &str, status: Status) -> PerpetualOrder {
+    let mut order = PerpetualOrder::new_open(
+        owner,
+        &PerpetualPosition::Long,
+        &PerpetualOrderType::LimitOpen,
+        &coin(255, "usdc"),
+        "btc",
+        &SignedDecimal::from_str("5").unwrap(),
+        &None,
+        &Some(OrderPrice {
+            base_denom: "usdc".to_owned(),
+            quote_denom: "btc".to_owned(),
+            rate: Decimal::from_atomics(Uint128::new(25), 1).unwrap(),
+        }),
+        order_id,
+    )
+    .unwrap();
+    order.status = status;
+    order
+}
@@ -0,0 +1,155 @@
+use super::*;
+use cosmwasm_std::{Int128, SignedDecimal};
+use elys_bindings::trade_shield::msg::query_resp::GetPerpetualPositionResp;
+
+// This test case verifies that the pnl of a position is valued at the oracle price, with its fees broken down.
+// - The user holds a long position opened without take profit with 100 USDC of collateral and 400 USDC borrowed,
+//   its custody is 25 BTC bought at 20 USDC.
+// - The position paid 3 USDC of borrow interest and owes 2 more, it paid 0.1 BTC and received 1 USDC of funding fees.
+// - At a BTC price of 24, the custody is worth 600 USDC and the unrealized pnl is 98 USDC.
+// - The liquidation price is 16 USDC, the custody being scaled with the BTC decimals.
+// - The fees paid and received add up to -4.4 USDC.
+// - A second position on an asset without info or price has no pnl nor liquidation price.
+#[test]
+fn pnl_breakdown() {
+    let mut app = ElysApp::new();
+
+    let usdc = "ibc/2180E84E20F5679FCC760D8C165B60F42065DEF7F46A72B447CFF1B7DC6C0A65";
+    let prices = vec![
+        Price::new("ubtc", Decimal::from_str("24").unwrap()),
+        Price::new(usdc, Decimal::one()),
+    ];
+    let asset_infos = vec![
+        OracleAssetInfo::new(
+            usdc.to_string(),
+            "USDC".to_string(),
+            "USDC".to_string(),
+            "USDC".to_string(),
+            6,
+        ),
+        OracleAssetInfo::new(
+            "ubtc".to_string(),
+            "BTC".to_string(),
+            "BTC".to_string(),
+            "BTC".to_string(),
+            6,
+        ),
+    ];
+
+    let mtp = Mtp {
+        address: "user".to_string(),
+        liabilities: Int128::new(400_000_000),
+        take_profit_liabilities: Int128::zero(),
+        mtp_health: SignedDecimal::one(),
+        position: PerpetualPosition::Long as i32,
+        id: 1,
+        amm_pool_id: 1,
+        consolidate_leverage: SignedDecimal::zero(),
+        sum_collateral: Int128::zero(),
+        take_profit_price: default_take_profit_price(),
+        borrow_interest_paid_collateral: Int128::new(3_000_000),
+        borrow_interest_paid_custody: Int128::zero(),
+        borrow_interest_unpaid_collateral: Int128::new(2_000_000),
+        collateral_asset: usdc.to_string(),
+        collateral: Int128::new(100_000_000),
+        custody: Int128::new(25_000_000),
+        custody_asset: "ubtc".to_string(),
+        funding_fee_paid_collateral: Int128::zero(),
+        funding_fee_paid_custody: Int128::new(100_000),
+        funding_fee_received_collateral: Int128::new(1_000_000),
+        funding_fee_received_custody: Int128::zero(),
+        leverage: SignedDecimal::from_str("5").unwrap(),
+        liabilities_asset: usdc.to_string(),
+        open_price: SignedDecimal::from_str("20").unwrap(),
+        take_profit_borrow_rate: SignedDecimal::one(),
+        take_profit_custody: Int128::zero(),
+        trading_asset: "ubtc".to_string(),
+    };
+
+    app.init_modules(|router, _, storage| {
+        router.custom.set_prices(storage, &prices).unwrap();
+        router
+            .custom
+            .set_asset_infos(storage, &asset_infos)
+            .unwrap();
+        router.custom.set_mtp(
+            storage,
+            &vec![
+                mtp.clone(),
+                Mtp {
+                    id: 2,
+                    custody_asset: "uatom".to_string(),
+                    trading_asset: "uatom".to_string(),
+                    ..mtp
+                },
+            ],
+        )
+    })
+    .unwrap();
+
+    let code = ContractWrapper::new(execute, instantiate, query);
+    let code_id = app.store_code(Box::new(code));
+
+    let instantiate_msg = InstantiateMockMsg {
+        account_history_address: None,
+        spot_orders: vec![],
+        perpetual_orders: vec![],
+    };
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg,
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let get_position = |id: u64| -> GetPerpetualPositionResp {
+        app.wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetPerpetualPosition {
+                    id,
+                    address: "user".to_string(),
+                },
+            )
+            .unwrap()
+    };
+
+    let resp = get_position(1);
+
+    assert_eq!(
+        resp.mtp.liquidation_price,
+        Some(SignedDecimal::from_str("16").unwrap())
+    );
+
+    let pnl = resp.mtp.pnl.unwrap();
+
+    assert_eq!(
+        pnl.unrealized_pnl_usd,
+        SignedDecimal::from_str("98").unwrap()
+    );
+    assert_eq!(
+        pnl.borrow_interest_paid,
+        SignedDecimal::from_str("3").unwrap()
+    );
+    assert_eq!(
+        pnl.borrow_interest_unpaid,
+        SignedDecimal::from_str("2").unwrap()
+    );
+    assert_eq!(
+        pnl.funding_fee_paid,
+        SignedDecimal::from_str("2.4").unwrap()
+    );
+    assert_eq!(pnl.funding_fee_received, SignedDecimal::one());
+    assert_eq!(pnl.net_fees, SignedDecimal::from_str("-4.4").unwrap());
+
+    // The position on an asset without info or price is still returned, without pnl and liquidation price.
+    let resp = get_position(2);
+
+    assert_eq!(resp.mtp.pnl, None);
+    assert_eq!(resp.mtp.liquidation_price, None);
+}
@@ -0,0 +1,121 @@
+use super::*;
+use cosmwasm_std::{Int128, SignedDecimal};
+use elys_bindings::trade_shield::msg::query_resp::GetPerpetualPositionResp;
+
+// This test case verifies that the stop loss price of a position is the one of its pending stop loss which triggers first.
+// - The user holds a long position with two pending stop losses at 17 and 18 USDC.
+// - A canceled stop loss of the position, a stop loss of another user and a stop loss of another position
+//   have higher prices and are ignored.
+// - The stop loss price of the position is 18 USDC.
+#[test]
+fn stop_loss_price() {
+    let mut app = ElysApp::new();
+
+    let usdc = "ibc/2180E84E20F5679FCC760D8C165B60F42065DEF7F46A72B447CFF1B7DC6C0A65";
+    let prices = vec![
+        Price::new("ubtc", Decimal::from_str("24").unwrap()),
+        Price::new(usdc, Decimal::one()),
+    ];
+
+    app.init_modules(|router, _, storage| {
+        router.custom.set_prices(storage, &prices).unwrap();
+        router.custom.set_mtp(
+            storage,
+            &vec![Mtp {
+                address: "user".to_string(),
+                liabilities: Int128::new(400_000_000),
+                take_profit_liabilities: Int128::zero(),
+                mtp_health: SignedDecimal::one(),
+                position: PerpetualPosition::Long as i32,
+                id: 1,
+                amm_pool_id: 1,
+                consolidate_leverage: SignedDecimal::zero(),
+                sum_collateral: Int128::zero(),
+                take_profit_price: default_take_profit_price(),
+                borrow_interest_paid_collateral: Int128::zero(),
+                borrow_interest_paid_custody: Int128::zero(),
+                borrow_interest_unpaid_collateral: Int128::zero(),
+                collateral_asset: usdc.to_string(),
+                collateral: Int128::new(100_000_000),
+                custody: Int128::new(25_000_000),
+                custody_asset: "ubtc".to_string(),
+                funding_fee_paid_collateral: Int128::zero(),
+                funding_fee_paid_custody: Int128::zero(),
+                funding_fee_received_collateral: Int128::zero(),
+                funding_fee_received_custody: Int128::zero(),
+                leverage: SignedDecimal::from_str("5").unwrap(),
+                liabilities_asset: usdc.to_string(),
+                open_price: SignedDecimal::from_str("20").unwrap(),
+                take_profit_borrow_rate: SignedDecimal::one(),
+                take_profit_custody: Int128::zero(),
+                trading_asset: "ubtc".to_string(),
+            }],
+        )
+    })
+    .unwrap();
+
+    let stop_loss = |order_id: u64, owner: &str, position_id: u64, rate: &str| {
+        PerpetualOrder::new_close(
+            owner,
+            PerpetualPosition::Long as i32,
+            &PerpetualOrderType::StopLoss,
+            &coin(0, usdc),
+            "ubtc",
+            &SignedDecimal::from_str("5").unwrap(),
+            position_id,
+            &Some(OrderPrice {
+                base_denom: usdc.to_string(),
+                quote_denom: "ubtc".to_string(),
+                rate: Decimal::from_str(rate).unwrap(),
+            }),
+            &None,
+            order_id,
+        )
+        .unwrap()
+    };
+
+    let mut canceled_stop_loss = stop_loss(3, "user", 1, "19");
+    canceled_stop_loss.status = Status::Canceled;
+
+    let code = ContractWrapper::new(execute, instantiate, query);
+    let code_id = app.store_code(Box::new(code));
+
+    let instantiate_msg = InstantiateMockMsg {
+        account_history_address: None,
+        spot_orders: vec![],
+        perpetual_orders: vec![
+            stop_loss(1, "user", 1, "17"),
+            stop_loss(2, "user", 1, "18"),
+            canceled_stop_loss,
+            stop_loss(4, "other_user", 1, "19.5"),
+            stop_loss(5, "user", 2, "19.8"),
+        ],
+    };
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg,
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let resp: GetPerpetualPositionResp = app
+        .wrap()
+        .query_wasm_smart(
+            addr,
+            &QueryMsg::GetPerpetualPosition {
+                id: 1,
+                address: "user".to_string(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        resp.mtp.stop_loss_price.unwrap().rate,
+        Decimal::from_str("18").unwrap()
+    );
+}
@@ -103,6 +103,14 @@ fn create_orders() -> Vec<SpotOrder> {
                 height: 20,
                 time: Timestamp::from_seconds(600),
             },
+            trailing_stop: None,
+            group_id: None,
+            expiration: None,
+            max_slippage: None,
+            filled_amount: None,
+            twap_schedule: None,
+            cancel_reason: None,
+            fee: None,
         },
         SpotOrder {
             order_type: SpotOrderType::LimitSell,
@@ -120,6 +128,14 @@ fn create_orders() -> Vec<SpotOrder> {
                 height: 20,
                 time: Timestamp::from_seconds(600),
             },
+            trailing_stop: None,
+            group_id: None,
+            expiration: None,
+            max_slippage: None,
+            filled_amount: None,
+            twap_schedule: None,
+            cancel_reason: None,
+            fee: None,
         },
         SpotOrder {
             order_type: SpotOrderType::StopLoss,
@@ -137,6 +153,14 @@ fn create_orders() -> Vec<SpotOrder> {
                 height: 20,
                 time: Timestamp::from_seconds(600),
             },
+            trailing_stop: None,
+            group_id: None,
+            expiration: None,
+            max_slippage: None,
+            filled_amount: None,
+            twap_schedule: None,
+            cancel_reason: None,
+            fee: None,
         },
         SpotOrder {
             order_type: SpotOrderType::StopLoss,
@@ -154,6 +178,14 @@ fn create_orders() -> Vec<SpotOrder> {
                 height: 20,
                 time: Timestamp::from_seconds(600),
             },
+            trailing_stop: None,
+            group_id: None,
+            expiration: None,
+            max_slippage: None,
+            filled_amount: None,
+            twap_schedule: None,
+            cancel_reason: None,
+            fee: None,
         },
         SpotOrder {
             order_type: SpotOrderType::LimitBuy,
@@ -171,6 +203,14 @@ fn create_orders() -> Vec<SpotOrder> {
                 height: 20,
                 time: Timestamp::from_seconds(600),
             },
+            trailing_stop: None,
+            group_id: None,
+            expiration: None,
+            max_slippage: None,
+            filled_amount: None,
+            twap_schedule: None,
+            cancel_reason: None,
+            fee: None,
         },
     ]
 }
@@ -0,0 +1,121 @@
+use cosmwasm_std::Timestamp;
+
+use crate::msg::query_resp::GetSpotOrdersResp;
+
+use super::*;
+
+#[test]
+fn get_spot_orders_by_owner() {
+    let spot_orders: Vec<SpotOrder> = vec![
+        create_order(0, "userA", Status::Pending),
+        create_order(1, "userB", Status::Pending),
+        create_order(2, "userA", Status::Executed),
+        create_order(3, "userA", Status::Pending),
+        create_order(4, "userB", Status::Canceled),
+        create_order(5, "userA", Status::Pending),
+    ];
+    let mut app = ElysApp::new();
+
+    let instantiate_msg = InstantiateMockMsg {
+        account_history_address: None,
+        spot_orders: spot_orders.clone(),
+        perpetual_orders: vec![],
+    };
+
+    let code = ContractWrapper::new(execute, instantiate, query);
+    let code_id = app.store_code(Box::new(code));
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg,
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let mut page_req = PageRequest::new(2);
+    page_req.count_total = true;
+
+    let resp: GetSpotOrdersResp = app
+        .wrap()
+        .query_wasm_smart(
+            &addr,
+            &QueryMsg::GetSpotOrders {
+                pagination: Some(page_req.clone()),
+                order_owner: Some("userA".to_string()),
+                order_type: None,
+                order_status: Some(Status::Pending),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        resp.orders,
+        vec![spot_orders[0].clone(), spot_orders[3].clone()]
+    );
+    let page_response = resp.page_response.unwrap();
+    assert_eq!(page_response.total, Some(3));
+
+    page_req.update(page_response.next_key);
+
+    let resp: GetSpotOrdersResp = app
+        .wrap()
+        .query_wasm_smart(
+            &addr,
+            &QueryMsg::GetSpotOrders {
+                pagination: Some(page_req),
+                order_owner: Some("userA".to_string()),
+                order_type: None,
+                order_status: Some(Status::Pending),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(resp.orders, vec![spot_orders[5].clone()]);
+
+    let resp: GetSpotOrdersResp = app
+        .wrap()
+        .query_wasm_smart(
+            &addr,
+            &QueryMsg::GetSpotOrders {
+                pagination: None,
+                order_owner: None,
+                order_type: None,
+                order_status: Some(Status::Canceled),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(resp.orders, vec![spot_orders[4].clone()]);
+}
+
+fn create_order(order_id: u64, owner: &str, status: Status) -> SpotOrder {
+    SpotOrder {
+        order_type: SpotOrderType::LimitBuy,
+        order_id,
+        order_price: OrderPrice {
+            base_denom: "btc".to_owned(),
+            quote_denom: "usdc".to_owned(),
+            rate: Decimal::from_atomics(Uint128::new(25), 1).unwrap(),
+        },
+        order_amount: coin(255, "usdc"),
+        owner_address: Addr::unchecked(owner),
+        order_target_denom: "btc".to_owned(),
+        status,
+        date: Date {
+            height: 20,
+            time: Timestamp::from_seconds(600),
+        },
+        trailing_stop: None,
+        group_id: None,
+        expiration: None,
+        max_slippage: None,
+        filled_amount: None,
+        twap_schedule: None,
+        cancel_reason: None,
+        fee: None,
+    }
+}
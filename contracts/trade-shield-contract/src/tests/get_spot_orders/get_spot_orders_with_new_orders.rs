@@ -0,0 +1,102 @@
+use crate::msg::query_resp::GetSpotOrdersResp;
+
+use super::*;
+
+// Orders created between two page requests must neither be repeated nor shift the next page.
+#[test]
+fn get_spot_orders_with_new_orders() {
+    let wallet = vec![("user", coins(10, "btc")), ("user2", coins(10, "btc"))];
+    let mut app = ElysApp::new_with_wallets(wallet);
+
+    let prices = vec![
+        Price::new("btc", Decimal::from_str("30000.0").unwrap()),
+        Price::new("usdc", Decimal::from_str("1.0").unwrap()),
+    ];
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices).unwrap());
+
+    let instantiate_msg = InstantiateMockMsg {
+        account_history_address: None,
+        spot_orders: vec![],
+        perpetual_orders: vec![],
+    };
+
+    let code = ContractWrapper::new(execute, instantiate, query);
+    let code_id = app.store_code(Box::new(code));
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg,
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let create_order = |app: &mut ElysApp, user: &str| {
+        app.execute_contract(
+            Addr::unchecked(user),
+            addr.clone(),
+            &ExecuteMsg::CreateSpotOrder {
+                order_type: SpotOrderType::LimitSell,
+                order_price: Some(OrderPrice {
+                    base_denom: "btc".to_string(),
+                    quote_denom: "usdc".to_string(),
+                    rate: Decimal::from_atomics(Uint128::new(40000), 0).unwrap(),
+                }),
+                order_source_denom: "btc".to_string(),
+                order_target_denom: "usdc".to_string(),
+                trailing_distance: None,
+                linked_order_id: None,
+                expiration: None,
+                max_slippage: None,
+                min_out: None,
+            },
+            &coins(1, "btc"),
+        )
+        .unwrap();
+    };
+
+    create_order(&mut app, "user");
+    create_order(&mut app, "user2");
+    create_order(&mut app, "user");
+    create_order(&mut app, "user2");
+    create_order(&mut app, "user");
+
+    let mut page_req = PageRequest::new(2);
+    page_req.count_total = true;
+
+    let query_msg = |page_req: &PageRequest| QueryMsg::GetSpotOrders {
+        pagination: Some(page_req.clone()),
+        order_owner: Some("user".to_string()),
+        order_type: None,
+        order_status: None,
+    };
+
+    let resp: GetSpotOrdersResp = app
+        .wrap()
+        .query_wasm_smart(&addr, &query_msg(&page_req))
+        .unwrap();
+
+    let order_ids: Vec<u64> = resp.orders.iter().map(|order| order.order_id).collect();
+    assert_eq!(order_ids, vec![1, 3]);
+    let page_response = resp.page_response.unwrap();
+    assert_eq!(page_response.total, Some(3));
+
+    create_order(&mut app, "user2");
+    create_order(&mut app, "user");
+
+    page_req.update(page_response.next_key);
+
+    let resp: GetSpotOrdersResp = app
+        .wrap()
+        .query_wasm_smart(&addr, &query_msg(&page_req))
+        .unwrap();
+
+    let order_ids: Vec<u64> = resp.orders.iter().map(|order| order.order_id).collect();
+    assert_eq!(order_ids, vec![5, 7]);
+    let page_response = resp.page_response.unwrap();
+    assert_eq!(page_response.total, Some(4));
+    assert_eq!(page_response.next_key, None);
+}
@@ -0,0 +1,133 @@
+use super::*;
+use cosmwasm_std::{Coin, Int128, SignedDecimal, SignedDecimal256};
+use cw_utils::PaymentError;
+
+// This test case verifies that the owner of a position can lower its leverage and move its take profit
+// without closing it.
+// - The user holds a long position with a collateral of 100 USDC and a leverage of 5.
+// - Collateral in another denom than the position collateral is rejected.
+// - The user adds 150 USDC of collateral, the leverage of the position goes down to 2.
+// - The user moves the take profit price of the position to 3.
+#[test]
+fn successful_manage_perpetual_position() {
+    let usdc = "ibc/2180E84E20F5679FCC760D8C165B60F42065DEF7F46A72B447CFF1B7DC6C0A65";
+    let wallets: Vec<(&str, Vec<Coin>)> = vec![("user", vec![coin(150, usdc), coin(10, "ubtc")])];
+    let mut app = ElysApp::new_with_wallets(wallets);
+
+    app.init_modules(|router, _, storage| {
+        router.custom.set_mtp(
+            storage,
+            &vec![Mtp {
+                address: "user".to_string(),
+                liabilities: Int128::zero(),
+                take_profit_liabilities: Int128::zero(),
+                mtp_health: SignedDecimal::one(),
+                position: PerpetualPosition::Long as i32,
+                id: 1,
+                amm_pool_id: 1,
+                consolidate_leverage: SignedDecimal::zero(),
+                sum_collateral: Int128::zero(),
+                take_profit_price: SignedDecimal256::from_str("1.2").unwrap(),
+                borrow_interest_paid_collateral: Int128::zero(),
+                borrow_interest_paid_custody: Int128::zero(),
+                borrow_interest_unpaid_collateral: Int128::zero(),
+                collateral_asset: usdc.to_string(),
+                collateral: Int128::new(100),
+                custody: Int128::new(500),
+                custody_asset: "ubtc".to_string(),
+                funding_fee_paid_collateral: Int128::zero(),
+                funding_fee_paid_custody: Int128::zero(),
+                funding_fee_received_collateral: Int128::zero(),
+                funding_fee_received_custody: Int128::zero(),
+                leverage: SignedDecimal::from_str("5").unwrap(),
+                liabilities_asset: usdc.to_string(),
+                open_price: SignedDecimal::one(),
+                take_profit_borrow_rate: SignedDecimal::one(),
+                take_profit_custody: Int128::zero(),
+                trading_asset: "ubtc".to_string(),
+            }],
+        )
+    })
+    .unwrap();
+
+    let code = ContractWrapper::new(execute, instantiate, query)
+        .with_reply(reply)
+        .with_sudo(sudo);
+    let code_id = app.store_code(Box::new(code));
+
+    let instantiate_msg = InstantiateMockMsg {
+        account_history_address: None,
+        spot_orders: vec![],
+        perpetual_orders: vec![],
+    };
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg,
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let get_mtp = |app: &mut ElysApp| -> Mtp {
+        app.init_modules(|_, _, store| PERPETUAL_OPENED_POSITION.load(store).unwrap())
+            .first()
+            .cloned()
+            .unwrap()
+    };
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::AddPerpetualPositionCollateral { id: 1 },
+            &coins(10, "ubtc"),
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        ContractError::Payment(PaymentError::MissingDenom(usdc.to_string())),
+        err.downcast().unwrap()
+    );
+
+    app.execute_contract(
+        Addr::unchecked("user"),
+        addr.clone(),
+        &ExecuteMsg::AddPerpetualPositionCollateral { id: 1 },
+        &coins(150, usdc),
+    )
+    .unwrap();
+
+    let mtp = get_mtp(&mut app);
+
+    assert_eq!(mtp.collateral, Int128::new(250));
+    assert_eq!(mtp.leverage, SignedDecimal::from_str("2").unwrap());
+    assert_eq!(mtp.custody, Int128::new(500));
+    assert_eq!(
+        app.wrap()
+            .query_balance("user", usdc)
+            .unwrap()
+            .amount
+            .u128(),
+        0
+    );
+
+    app.execute_contract(
+        Addr::unchecked("user"),
+        addr.clone(),
+        &ExecuteMsg::UpdatePerpetualPositionTakeProfitPrice {
+            id: 1,
+            take_profit_price: SignedDecimal256::from_str("3").unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(
+        get_mtp(&mut app).take_profit_price,
+        SignedDecimal256::from_str("3").unwrap()
+    );
+}
@@ -1,10 +1,10 @@
 use crate::{
     states::*,
-    types::{PerpetualOrder, SpotOrder, Status},
+    types::{Config, PerpetualOrder, SpotOrder, Status},
 };
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{DepsMut, Env, MessageInfo, Response, StdResult};
-use elys_bindings::{ElysMsg, ElysQuery};
+use elys_bindings::{types::PageRequest, ElysMsg, ElysQuery};
 
 #[cw_serde]
 pub struct InstantiateMockMsg {
@@ -16,7 +16,7 @@ pub struct InstantiateMockMsg {
 pub fn instantiate(
     deps: DepsMut<ElysQuery>,
     _env: Env,
-    _info: MessageInfo,
+    info: MessageInfo,
     msg: InstantiateMockMsg,
 ) -> StdResult<Response<ElysMsg>> {
     for order in msg.spot_orders.iter() {
@@ -31,9 +31,23 @@ pub fn instantiate(
             PENDING_PERPETUAL_ORDER.save(deps.storage, order.order_id, order)?;
         }
     }
+    if let Some(max_id) = msg
+        .perpetual_orders
+        .iter()
+        .map(|order| order.order_id)
+        .max()
+    {
+        PERPETUAL_ORDER_MAX_ID.save(deps.storage, &max_id)?;
+    }
     MAX_REPLY_ID.save(deps.storage, &0)?;
     SPOT_ORDER_MAX_ID.save(deps.storage, &0)?;
-    ACCOUNT_HISTORY_ADDRESS.save(deps.storage, &msg.account_history_address)?;
+    ORDER_INDEX_VERSION.save(deps.storage, &LATEST_ORDER_INDEX_VERSION)?;
+    CONFIG.save(
+        deps.storage,
+        &Config::new(info.sender, msg.account_history_address),
+    )?;
+    PROCESS_SPOT_ORDER_PAGINATION.save(deps.storage, &PageRequest::new(100))?;
+    PROCESS_PERPETUAL_ORDER_PAGINATION.save(deps.storage, &PageRequest::new(100))?;
 
     Ok(Response::new())
 }
@@ -16,7 +16,9 @@ use std::str::FromStr;
 mod create_spot_order {
     use super::*;
     mod coin_number;
+    mod market_buy_min_out;
     mod not_enough_fund;
+    mod order_max_slippage;
     mod order_price_denom;
     mod order_same_denom;
     mod order_wrong_fund;
@@ -24,6 +26,29 @@ mod create_spot_order {
     mod successful_create_limit_sell_order;
     mod successful_create_market_order;
     mod successful_create_stop_loss_order;
+    mod trailing_stop_distance;
+    mod unsupported_asset;
+}
+
+mod protocol_fee {
+    use super::*;
+    mod successful_withdraw_protocol_fees;
+}
+
+mod update_config {
+    use super::*;
+    mod exit_messages_while_paused;
+    mod invalid_order_limits;
+    mod paused_features;
+    mod process_order_limit;
+    mod successful_update_config;
+    mod unauthorized;
+}
+
+mod update_spot_order {
+    use super::*;
+    mod successful_update_spot_order;
+    mod unauthorized;
 }
 
 mod cancel_spot_order {
@@ -32,6 +57,7 @@ mod cancel_spot_order {
     mod process_spot_order_processing;
     mod successful_cancel_order_with_created_order;
     mod successful_cancel_order_with_dummy_order;
+    mod successful_cancel_partially_filled_order;
     mod unauthorized;
 }
 
@@ -50,32 +76,66 @@ mod get_spot_order {
     mod successful_query_message;
 }
 
+mod get_perpetual_orders {
+    use super::*;
+    mod get_perpetual_orders_by_owner;
+}
+
 mod get_spot_orders {
     use super::*;
     mod get_spot_orders;
+    mod get_spot_orders_by_owner;
+    mod get_spot_orders_with_new_orders;
+}
+
+mod get_order_history {
+    use super::*;
+    mod expired_order_history;
+    mod successful_query_spot_order_history;
 }
 
 mod process_spot_order {
     use super::*;
     mod pending_limit_buy_order_with_price_not_met;
+    mod process_expired_spot_order;
     mod process_limit_buy_order_with_executed_status;
     mod process_limit_buy_order_with_executed_status_scenario_2;
     mod process_limit_buy_order_with_pending_status;
+    mod process_order_events;
+    mod process_order_with_denom_mismatch;
+    mod process_orders_off_page;
     mod successful_process_5_of_10_orders;
+    mod successful_process_crossed_orders;
     mod successful_process_limit_sell_order;
+    mod successful_process_oco_orders;
+    mod successful_process_orders_with_limit;
+    mod successful_process_partial_fill;
     mod successful_process_stop_loss_order;
+    mod successful_process_trailing_stop_order;
+    mod successful_process_twap_order;
 }
 
 mod create_perpetual_order {
     use super::*;
     mod change_trigger_price;
     mod coin_number;
+    mod link_other_position;
     mod reproduce_testnet_issue_create_perpetual_market_open_order;
     mod successful_create_perpetual_market_close;
     mod successful_create_perpetual_market_order;
     mod successful_create_perpetual_order;
 }
 
+mod create_perpetual_bracket_order {
+    use super::*;
+    mod successful_process_bracket_order;
+}
+
+mod update_perpetual_order {
+    use super::*;
+    mod successful_update_perpetual_order;
+}
+
 mod cancel_perpetual_order {
     use super::*;
     mod not_found;
@@ -83,10 +143,27 @@ mod cancel_perpetual_order {
     mod unauthorize;
 }
 
+mod manage_perpetual_position {
+    use super::*;
+    mod successful_manage_perpetual_position;
+}
+
 mod process_perpetual_order {
     use super::*;
+    mod process_expired_limit_open;
+    mod process_health_monitor;
+    mod process_health_monitor_add_collateral;
     mod process_limit_open;
     mod process_order_close;
+    mod process_partial_close;
+    mod process_take_profit_ladder;
+    mod process_trailing_stop;
+}
+
+mod get_perpetual_position {
+    use super::*;
+    mod pnl_breakdown;
+    mod stop_loss_price;
 }
 
 mod get_perpetual_order {
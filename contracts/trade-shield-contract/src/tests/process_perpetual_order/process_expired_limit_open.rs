@@ -0,0 +1,117 @@
+use super::*;
+use crate::tests::get_order_id_from_events::get_attr_from_events;
+use cosmwasm_std::{Int64, SignedDecimal, SignedDecimal256};
+use cw_utils::Expiration;
+use elys_bindings::trade_shield::msg::query_resp::GetPerpetualOrderResp;
+
+// This test case verifies that the processing of the orders refunds the collateral of an expired limit open order.
+// - The user places a long limit open with 30,000 USDC at a rate of 0.00001 BTC per USDC which expires
+//   10 blocks later, the BTC price stays at 50,000 USDC so the rate is never reached.
+// - Before its expiration, the order stays pending and the contract keeps the collateral.
+// - Once expired, the order is canceled with the expired reason and the user gets the 30,000 USDC back.
+#[test]
+fn process_expired_limit_open() {
+    let usdc = "ibc/2180E84E20F5679FCC760D8C165B60F42065DEF7F46A72B447CFF1B7DC6C0A65";
+
+    let wallet = vec![("user", coins(30000, usdc))];
+    let mut app = ElysApp::new_with_wallets(wallet);
+
+    let prices = vec![
+        Price::new("btc", Decimal::from_str("50000").unwrap()),
+        Price::new(usdc, Decimal::one()),
+    ];
+
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices))
+        .unwrap();
+
+    let instantiate_msg = InstantiateMockMsg {
+        account_history_address: None,
+        spot_orders: vec![],
+        perpetual_orders: vec![],
+    };
+
+    let code = ContractWrapper::new(execute, instantiate, query)
+        .with_reply(reply)
+        .with_sudo(sudo);
+    let code_id = app.store_code(Box::new(code));
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg,
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let expiration_height = app.block_info().height + 10;
+
+    let order_id: u64 = get_attr_from_events(
+        &app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::CreatePerpetualOrder {
+                position: Some(PerpetualPosition::Long),
+                leverage: Some(SignedDecimal::from_atomics(Int64::new(2), 0).unwrap()),
+                trading_asset: Some("btc".to_string()),
+                take_profit_price: Some(SignedDecimal256::from_str("60000").unwrap()),
+                order_type: PerpetualOrderType::LimitOpen,
+                trigger_price: Some(OrderPrice {
+                    base_denom: usdc.to_string(),
+                    quote_denom: "btc".to_string(),
+                    rate: Decimal::from_str("0.00001").unwrap(),
+                }),
+                position_id: None,
+                trailing_distance: None,
+                linked_order_id: None,
+                expiration: Some(Expiration::AtHeight(expiration_height)),
+                close_size: None,
+                health_threshold: None,
+            },
+            &coins(30000, usdc),
+        )
+        .unwrap()
+        .events,
+        "perpetual_order_id",
+    )
+    .unwrap()
+    .parse()
+    .unwrap();
+
+    let get_order = |app: &ElysApp| -> PerpetualOrder {
+        let resp: GetPerpetualOrderResp = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetPerpetualOrder { id: order_id })
+            .unwrap();
+        resp.order
+    };
+
+    let balance = |app: &ElysApp, address: &str| -> u128 {
+        app.wrap()
+            .query_balance(address, usdc)
+            .unwrap()
+            .amount
+            .u128()
+    };
+
+    app.update_block(|block| block.height = expiration_height - 1);
+    app.wasm_sudo(addr.clone(), &SudoMsg::ClockEndBlock {})
+        .unwrap();
+
+    assert_eq!(get_order(&app).status, Status::Pending);
+    assert_eq!(balance(&app, "user"), 0);
+    assert_eq!(balance(&app, addr.as_str()), 30000);
+
+    app.update_block(|block| block.height = expiration_height);
+    app.wasm_sudo(addr.clone(), &SudoMsg::ClockEndBlock {})
+        .unwrap();
+
+    let order = get_order(&app);
+
+    assert_eq!(order.status, Status::Canceled);
+    assert_eq!(order.cancel_reason, Some(CancelReason::Expired));
+    assert_eq!(balance(&app, "user"), 30000);
+    assert_eq!(balance(&app, addr.as_str()), 0);
+}
@@ -0,0 +1,156 @@
+use super::*;
+use crate::tests::get_order_id_from_events::get_attr_from_events;
+use cosmwasm_std::{Addr, Decimal, Int128, SignedDecimal, SignedDecimal256};
+use elys_bindings::trade_shield::msg::query_resp::GetPerpetualOrderResp;
+
+// This test case verifies that "health monitor" orders deleverage a position before it gets liquidated.
+// - The user holds a long position with a custody of 400 ubtc and a health of 1.5.
+// - A first monitor closes half of the position below a health of 1.2, a second one the other half below 1.05.
+// - The health drops to 1.1, 200 ubtc are closed and the second monitor keeps watching the position.
+// - The health drops to 1, the rest of the position is closed.
+#[test]
+fn successful_process_health_monitor() {
+    let mut app = ElysApp::new();
+
+    let usdc = "ibc/2180E84E20F5679FCC760D8C165B60F42065DEF7F46A72B447CFF1B7DC6C0A65";
+    let prices = |ubtc_price: &str| {
+        vec![
+            Price::new("ubtc", Decimal::from_str(ubtc_price).unwrap()),
+            Price::new(usdc, Decimal::one()),
+        ]
+    };
+
+    let mtp = |custody: i128, mtp_health: &str| Mtp {
+        address: "user".to_string(),
+        liabilities: Int128::zero(),
+        take_profit_liabilities: Int128::zero(),
+        mtp_health: SignedDecimal::from_str(mtp_health).unwrap(),
+        position: PerpetualPosition::Long as i32,
+        id: 1,
+        amm_pool_id: 1,
+        consolidate_leverage: SignedDecimal::zero(),
+        sum_collateral: Int128::zero(),
+        take_profit_price: SignedDecimal256::from_str("1.2").unwrap(),
+        borrow_interest_paid_collateral: Int128::zero(),
+        borrow_interest_paid_custody: Int128::zero(),
+        borrow_interest_unpaid_collateral: Int128::zero(),
+        collateral_asset: usdc.to_string(),
+        collateral: Int128::new(100),
+        custody: Int128::new(custody),
+        custody_asset: "ubtc".to_string(),
+        funding_fee_paid_collateral: Int128::zero(),
+        funding_fee_paid_custody: Int128::zero(),
+        funding_fee_received_collateral: Int128::zero(),
+        funding_fee_received_custody: Int128::zero(),
+        leverage: SignedDecimal::from_str("5").unwrap(),
+        liabilities_asset: usdc.to_string(),
+        open_price: SignedDecimal::one(),
+        take_profit_borrow_rate: SignedDecimal::one(),
+        take_profit_custody: Int128::zero(),
+        trading_asset: "ubtc".to_string(),
+    };
+
+    app.init_modules(|router, _, storage| router.custom.set_mtp(storage, &vec![mtp(400, "1.5")]))
+        .unwrap();
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices("0.5")))
+        .unwrap();
+
+    let code = ContractWrapper::new(execute, instantiate, query)
+        .with_reply(reply)
+        .with_sudo(sudo);
+    let code_id = app.store_code(Box::new(code));
+
+    let instantiate_msg = InstantiateMockMsg {
+        account_history_address: None,
+        spot_orders: vec![],
+        perpetual_orders: vec![],
+    };
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg,
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let mut create_health_monitor = |threshold: &str, close_size: Option<CloseSize>| -> u64 {
+        let resp = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::CreatePerpetualOrder {
+                    position: None,
+                    leverage: None,
+                    trading_asset: None,
+                    take_profit_price: None,
+                    order_type: PerpetualOrderType::HealthMonitor,
+                    trigger_price: None,
+                    position_id: Some(1),
+                    trailing_distance: None,
+                    linked_order_id: None,
+                    expiration: None,
+                    close_size,
+                    health_threshold: Some(SignedDecimal::from_str(threshold).unwrap()),
+                },
+                &[],
+            )
+            .unwrap();
+
+        get_attr_from_events(&resp.events, "perpetual_order_id")
+            .unwrap()
+            .parse()
+            .unwrap()
+    };
+
+    let first_id = create_health_monitor(
+        "1.2",
+        Some(CloseSize::Percentage(Decimal::from_str("0.5").unwrap())),
+    );
+    let second_id = create_health_monitor(
+        "1.05",
+        Some(CloseSize::Percentage(Decimal::from_str("0.5").unwrap())),
+    );
+
+    let get_order = |app: &ElysApp, id: u64| -> PerpetualOrder {
+        let resp: GetPerpetualOrderResp = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetPerpetualOrder { id })
+            .unwrap();
+        resp.order
+    };
+
+    let get_custody = |app: &mut ElysApp| -> Option<Int128> {
+        app.init_modules(|_, _, store| PERPETUAL_OPENED_POSITION.load(store).unwrap())
+            .first()
+            .map(|mtp| mtp.custody)
+    };
+
+    let sudo_msg = SudoMsg::ClockEndBlock {};
+
+    // The position is healthy, nothing happens.
+    app.wasm_sudo(addr.clone(), &sudo_msg).unwrap();
+
+    assert_eq!(get_order(&app, first_id).status, Status::Pending);
+    assert_eq!(get_custody(&mut app), Some(Int128::new(400)));
+
+    // The health drops to 1.1, half of the position is closed.
+    app.init_modules(|router, _, storage| router.custom.set_mtp(storage, &vec![mtp(400, "1.1")]))
+        .unwrap();
+    app.wasm_sudo(addr.clone(), &sudo_msg).unwrap();
+
+    assert_eq!(get_order(&app, first_id).status, Status::Executed);
+    assert_eq!(get_order(&app, second_id).status, Status::Pending);
+    assert_eq!(get_custody(&mut app), Some(Int128::new(200)));
+
+    // The health drops to 1, the rest of the position is closed.
+    app.init_modules(|router, _, storage| router.custom.set_mtp(storage, &vec![mtp(200, "1")]))
+        .unwrap();
+    app.wasm_sudo(addr.clone(), &sudo_msg).unwrap();
+
+    assert_eq!(get_order(&app, second_id).status, Status::Executed);
+    assert_eq!(get_custody(&mut app), None);
+}
@@ -0,0 +1,229 @@
+use super::*;
+use crate::entry_point::instantiate as contract_instantiate;
+use crate::tests::get_order_id_from_events::get_attr_from_events;
+use cosmwasm_std::{Coin, Int128, SignedDecimal, SignedDecimal256};
+use elys_bindings::trade_shield::msg::query_resp::GetPerpetualOrderResp;
+
+// This test case verifies that "health monitor" orders holding collateral add it to the position instead of closing it.
+// - The contract checks 1 pending order per block, the user holds a long position with 100 USDC of collateral
+//   and a health of 1.5.
+// - The user sends 50 USDC with a monitor triggered below a health of 1.2 and places a stop loss far below the market.
+// - Sending collateral with a monitor closing a part of the position is rejected.
+// - The health drops to 1.1 while the stop loss is on the page, the monitor waits for its turn on the page
+//   in the next block, then it is triggered and the 50 USDC are added to the position.
+// - A second monitor holding 50 USDC is canceled by the user who gets the collateral back.
+#[test]
+fn process_health_monitor_add_collateral() {
+    let usdc = "ibc/2180E84E20F5679FCC760D8C165B60F42065DEF7F46A72B447CFF1B7DC6C0A65";
+
+    let wallets: Vec<(&str, Vec<Coin>)> = vec![("user", coins(100, usdc))];
+    let mut app = ElysApp::new_with_wallets(wallets);
+
+    let prices = vec![
+        Price::new("ubtc", Decimal::from_str("0.5").unwrap()),
+        Price::new(usdc, Decimal::one()),
+    ];
+
+    let mtp = |mtp_health: &str| Mtp {
+        address: "user".to_string(),
+        liabilities: Int128::zero(),
+        take_profit_liabilities: Int128::zero(),
+        mtp_health: SignedDecimal::from_str(mtp_health).unwrap(),
+        position: PerpetualPosition::Long as i32,
+        id: 1,
+        amm_pool_id: 1,
+        consolidate_leverage: SignedDecimal::zero(),
+        sum_collateral: Int128::zero(),
+        take_profit_price: SignedDecimal256::from_str("1.2").unwrap(),
+        borrow_interest_paid_collateral: Int128::zero(),
+        borrow_interest_paid_custody: Int128::zero(),
+        borrow_interest_unpaid_collateral: Int128::zero(),
+        collateral_asset: usdc.to_string(),
+        collateral: Int128::new(100),
+        custody: Int128::new(400),
+        custody_asset: "ubtc".to_string(),
+        funding_fee_paid_collateral: Int128::zero(),
+        funding_fee_paid_custody: Int128::zero(),
+        funding_fee_received_collateral: Int128::zero(),
+        funding_fee_received_custody: Int128::zero(),
+        leverage: SignedDecimal::from_str("6").unwrap(),
+        liabilities_asset: usdc.to_string(),
+        open_price: SignedDecimal::one(),
+        take_profit_borrow_rate: SignedDecimal::one(),
+        take_profit_custody: Int128::zero(),
+        trading_asset: "ubtc".to_string(),
+    };
+
+    app.init_modules(|router, _, storage| router.custom.set_mtp(storage, &vec![mtp("1.5")]))
+        .unwrap();
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices))
+        .unwrap();
+
+    let code = ContractWrapper::new(execute, contract_instantiate, query)
+        .with_reply(reply)
+        .with_sudo(sudo);
+    let code_id = app.store_code(Box::new(code));
+
+    let instantiate_msg = InstantiateMsg {
+        admin: None,
+        account_history_address: None,
+        process_order_limit: Some(1),
+    };
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg,
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let create_order_msg = |order_type: PerpetualOrderType,
+                            trigger_price: Option<OrderPrice>,
+                            close_size: Option<CloseSize>,
+                            health_threshold: Option<&str>| {
+        ExecuteMsg::CreatePerpetualOrder {
+            position: None,
+            leverage: None,
+            trading_asset: None,
+            take_profit_price: None,
+            order_type,
+            trigger_price,
+            position_id: Some(1),
+            trailing_distance: None,
+            linked_order_id: None,
+            expiration: None,
+            close_size,
+            health_threshold: health_threshold
+                .map(|threshold| SignedDecimal::from_str(threshold).unwrap()),
+        }
+    };
+
+    let monitor_id: u64 = get_attr_from_events(
+        &app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &create_order_msg(PerpetualOrderType::HealthMonitor, None, None, Some("1.2")),
+            &coins(50, usdc),
+        )
+        .unwrap()
+        .events,
+        "perpetual_order_id",
+    )
+    .unwrap()
+    .parse()
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("user"),
+        addr.clone(),
+        &create_order_msg(
+            PerpetualOrderType::StopLoss,
+            Some(OrderPrice {
+                base_denom: usdc.to_string(),
+                quote_denom: "ubtc".to_string(),
+                rate: Decimal::from_str("0.0001").unwrap(),
+            }),
+            None,
+            None,
+        ),
+        &[],
+    )
+    .unwrap();
+
+    assert!(app
+        .execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &create_order_msg(
+                PerpetualOrderType::HealthMonitor,
+                None,
+                Some(CloseSize::Percentage(Decimal::from_str("0.5").unwrap())),
+                Some("1.1"),
+            ),
+            &coins(50, usdc),
+        )
+        .is_err());
+
+    let get_order = |app: &ElysApp, id: u64| -> PerpetualOrder {
+        let resp: GetPerpetualOrderResp = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetPerpetualOrder { id })
+            .unwrap();
+        resp.order
+    };
+
+    let get_collateral = |app: &mut ElysApp| -> Option<Int128> {
+        app.init_modules(|_, _, store| PERPETUAL_OPENED_POSITION.load(store).unwrap())
+            .first()
+            .map(|mtp| mtp.collateral)
+    };
+
+    let balance = |app: &ElysApp, address: &str| -> u128 {
+        app.wrap()
+            .query_balance(address, usdc)
+            .unwrap()
+            .amount
+            .u128()
+    };
+
+    assert_eq!(balance(&app, "user"), 50);
+    assert_eq!(balance(&app, addr.as_str()), 50);
+
+    let sudo_msg = SudoMsg::ClockEndBlock {};
+
+    // The monitor is on the page and the position is healthy, nothing happens.
+    app.wasm_sudo(addr.clone(), &sudo_msg).unwrap();
+
+    assert_eq!(get_order(&app, monitor_id).status, Status::Pending);
+    assert_eq!(get_collateral(&mut app), Some(Int128::new(100)));
+
+    // The health drops to 1.1 while the stop loss is on the page, the monitor is not checked.
+    app.init_modules(|router, _, storage| router.custom.set_mtp(storage, &vec![mtp("1.1")]))
+        .unwrap();
+    app.wasm_sudo(addr.clone(), &sudo_msg).unwrap();
+
+    assert_eq!(get_order(&app, monitor_id).status, Status::Pending);
+    assert_eq!(get_collateral(&mut app), Some(Int128::new(100)));
+
+    // The monitor is back on the page, the collateral is added.
+    app.wasm_sudo(addr.clone(), &sudo_msg).unwrap();
+
+    assert_eq!(get_order(&app, monitor_id).status, Status::Executed);
+    assert_eq!(get_collateral(&mut app), Some(Int128::new(150)));
+    assert_eq!(balance(&app, addr.as_str()), 0);
+
+    // A monitor that was never triggered gives its collateral back once canceled.
+    let second_monitor_id: u64 = get_attr_from_events(
+        &app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &create_order_msg(PerpetualOrderType::HealthMonitor, None, None, Some("1.05")),
+            &coins(50, usdc),
+        )
+        .unwrap()
+        .events,
+        "perpetual_order_id",
+    )
+    .unwrap()
+    .parse()
+    .unwrap();
+
+    assert_eq!(balance(&app, "user"), 0);
+
+    app.execute_contract(
+        Addr::unchecked("user"),
+        addr.clone(),
+        &ExecuteMsg::CancelPerpetualOrder {
+            order_id: second_monitor_id,
+        },
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(balance(&app, "user"), 50);
+    assert_eq!(balance(&app, addr.as_str()), 0);
+}
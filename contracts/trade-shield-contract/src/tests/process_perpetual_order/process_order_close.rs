@@ -49,7 +49,7 @@ fn successful_process_limit_buy_order() {
                 .unwrap(), // Rate at which ubtc will be bought (38 ibc/2180E84E20F5679FCC760D8C165B60F42065DEF7F46A72B447CFF1B7DC6C0A65 per ubtc).
         }),
         &Some(SignedDecimal256::from_str("1.1").unwrap()),
-        &vec![],
+        0,
     )
     .unwrap();
 
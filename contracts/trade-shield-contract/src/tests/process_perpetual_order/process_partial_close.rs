@@ -0,0 +1,166 @@
+use super::*;
+use crate::tests::get_order_id_from_events::get_attr_from_events;
+use cosmwasm_std::{Addr, Decimal, Int128, SignedDecimal, SignedDecimal256};
+use elys_bindings::trade_shield::msg::query_resp::GetPerpetualOrderResp;
+
+// This test case verifies that a perpetual close order can close only a part of the position.
+// - The user holds a long position with a custody of 500 ubtc, the market price is 2.
+// - A "limit close" order closes 25% of the position at 3, a linked "stop loss" order closes the rest at 1.5.
+// - The market price goes up to 4, 125 ubtc are closed and the stop loss keeps protecting the 375 left.
+// - The market price falls to 1, the stop loss closes the rest of the position.
+#[test]
+fn successful_process_partial_close() {
+    let mut app = ElysApp::new();
+
+    let usdc = "ibc/2180E84E20F5679FCC760D8C165B60F42065DEF7F46A72B447CFF1B7DC6C0A65";
+    let prices = |ubtc_price: &str| {
+        vec![
+            Price::new("ubtc", Decimal::from_str(ubtc_price).unwrap()),
+            Price::new(usdc, Decimal::one()),
+        ]
+    };
+
+    app.init_modules(|router, _, storage| {
+        router.custom.set_mtp(
+            storage,
+            &vec![Mtp {
+                address: "user".to_string(),
+                liabilities: Int128::zero(),
+                take_profit_liabilities: Int128::zero(),
+                mtp_health: SignedDecimal::one(),
+                position: PerpetualPosition::Long as i32,
+                id: 1,
+                amm_pool_id: 1,
+                consolidate_leverage: SignedDecimal::zero(),
+                sum_collateral: Int128::zero(),
+                take_profit_price: SignedDecimal256::from_str("1.2").unwrap(),
+                borrow_interest_paid_collateral: Int128::zero(),
+                borrow_interest_paid_custody: Int128::zero(),
+                borrow_interest_unpaid_collateral: Int128::zero(),
+                collateral_asset: usdc.to_string(),
+                collateral: Int128::new(100),
+                custody: Int128::new(500),
+                custody_asset: "ubtc".to_string(),
+                funding_fee_paid_collateral: Int128::zero(),
+                funding_fee_paid_custody: Int128::zero(),
+                funding_fee_received_collateral: Int128::zero(),
+                funding_fee_received_custody: Int128::zero(),
+                leverage: SignedDecimal::from_str("5").unwrap(),
+                liabilities_asset: usdc.to_string(),
+                open_price: SignedDecimal::one(),
+                take_profit_borrow_rate: SignedDecimal::one(),
+                take_profit_custody: Int128::zero(),
+                trading_asset: "ubtc".to_string(),
+            }],
+        )
+    })
+    .unwrap();
+
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices("0.5")))
+        .unwrap();
+
+    let code = ContractWrapper::new(execute, instantiate, query)
+        .with_reply(reply)
+        .with_sudo(sudo);
+    let code_id = app.store_code(Box::new(code));
+
+    let instantiate_msg = InstantiateMockMsg {
+        account_history_address: None,
+        spot_orders: vec![],
+        perpetual_orders: vec![],
+    };
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg,
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let mut create_close_order = |order_type: PerpetualOrderType,
+                                  rate: &str,
+                                  linked_order_id: Option<u64>,
+                                  close_size: Option<CloseSize>|
+     -> u64 {
+        let resp = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::CreatePerpetualOrder {
+                    position: None,
+                    leverage: None,
+                    trading_asset: None,
+                    take_profit_price: None,
+                    order_type,
+                    trigger_price: Some(OrderPrice {
+                        base_denom: usdc.to_string(),
+                        quote_denom: "ubtc".to_string(),
+                        rate: Decimal::from_str(rate).unwrap(),
+                    }),
+                    position_id: Some(1),
+                    trailing_distance: None,
+                    linked_order_id,
+                    expiration: None,
+                    close_size,
+                    health_threshold: None,
+                },
+                &[],
+            )
+            .unwrap();
+
+        get_attr_from_events(&resp.events, "perpetual_order_id")
+            .unwrap()
+            .parse()
+            .unwrap()
+    };
+
+    let take_profit_id = create_close_order(
+        PerpetualOrderType::LimitClose,
+        "3",
+        None,
+        Some(CloseSize::Percentage(Decimal::from_str("0.25").unwrap())),
+    );
+    let stop_loss_id = create_close_order(
+        PerpetualOrderType::StopLoss,
+        "1.5",
+        Some(take_profit_id),
+        None,
+    );
+
+    let get_order = |app: &ElysApp, id: u64| -> PerpetualOrder {
+        let resp: GetPerpetualOrderResp = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetPerpetualOrder { id })
+            .unwrap();
+        resp.order
+    };
+
+    let get_custody = |app: &mut ElysApp| -> Option<Int128> {
+        app.init_modules(|_, _, store| PERPETUAL_OPENED_POSITION.load(store).unwrap())
+            .first()
+            .map(|mtp| mtp.custody)
+    };
+
+    let sudo_msg = SudoMsg::ClockEndBlock {};
+
+    // The market price goes up to 4, a quarter of the position is closed.
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices("0.25")))
+        .unwrap();
+    app.wasm_sudo(addr.clone(), &sudo_msg).unwrap();
+
+    assert_eq!(get_order(&app, take_profit_id).status, Status::Executed);
+    assert_eq!(get_order(&app, stop_loss_id).status, Status::Pending);
+    assert_eq!(get_custody(&mut app), Some(Int128::new(375)));
+
+    // The market price falls to 1, the stop loss closes the rest of the position.
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices("1")))
+        .unwrap();
+    app.wasm_sudo(addr.clone(), &sudo_msg).unwrap();
+
+    assert_eq!(get_order(&app, stop_loss_id).status, Status::Executed);
+    assert_eq!(get_custody(&mut app), None);
+}
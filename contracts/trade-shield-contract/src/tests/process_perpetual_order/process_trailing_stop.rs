@@ -0,0 +1,150 @@
+use super::*;
+use crate::tests::get_order_id_from_events::get_attr_from_events;
+use cosmwasm_std::{Addr, Decimal, Int128, SignedDecimal, SignedDecimal256};
+use elys_bindings::trade_shield::msg::query_resp::GetPerpetualOrderResp;
+
+// This test case verifies that a perpetual "trailing-stop" order follows the best price reached by a long position.
+// - The market price starts at 2, with a trailing distance of 10% the trigger price is 1.8.
+// - The market price goes up to 4, the trigger price follows and becomes 3.6.
+// - The market price pulls back to 3.2, the position is closed even though the initial trigger price was never reached.
+#[test]
+fn successful_process_trailing_stop() {
+    let mut app = ElysApp::new();
+
+    let usdc = "ibc/2180E84E20F5679FCC760D8C165B60F42065DEF7F46A72B447CFF1B7DC6C0A65";
+    let prices = |ubtc_price: &str| {
+        vec![
+            Price::new("ubtc", Decimal::from_str(ubtc_price).unwrap()),
+            Price::new(usdc, Decimal::one()),
+        ]
+    };
+
+    app.init_modules(|router, _, storage| {
+        router.custom.set_mtp(
+            storage,
+            &vec![Mtp {
+                address: "user".to_string(),
+                liabilities: Int128::zero(),
+                take_profit_liabilities: Int128::zero(),
+                mtp_health: SignedDecimal::one(),
+                position: PerpetualPosition::Long as i32,
+                id: 1,
+                amm_pool_id: 1,
+                consolidate_leverage: SignedDecimal::zero(),
+                sum_collateral: Int128::zero(),
+                take_profit_price: SignedDecimal256::from_str("1.2").unwrap(),
+                borrow_interest_paid_collateral: Int128::zero(),
+                borrow_interest_paid_custody: Int128::zero(),
+                borrow_interest_unpaid_collateral: Int128::zero(),
+                collateral_asset: usdc.to_string(),
+                collateral: Int128::new(100),
+                custody: Int128::new(500),
+                custody_asset: "ubtc".to_string(),
+                funding_fee_paid_collateral: Int128::zero(),
+                funding_fee_paid_custody: Int128::zero(),
+                funding_fee_received_collateral: Int128::zero(),
+                funding_fee_received_custody: Int128::zero(),
+                leverage: SignedDecimal::from_str("5").unwrap(),
+                liabilities_asset: usdc.to_string(),
+                open_price: SignedDecimal::one(),
+                take_profit_borrow_rate: SignedDecimal::one(),
+                take_profit_custody: Int128::zero(),
+                trading_asset: "ubtc".to_string(),
+            }],
+        )
+    })
+    .unwrap();
+
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices("0.5")))
+        .unwrap();
+
+    let code = ContractWrapper::new(execute, instantiate, query)
+        .with_reply(reply)
+        .with_sudo(sudo);
+    let code_id = app.store_code(Box::new(code));
+
+    let instantiate_msg = InstantiateMockMsg {
+        account_history_address: None,
+        spot_orders: vec![],
+        perpetual_orders: vec![],
+    };
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg,
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let resp = app
+        .execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::CreatePerpetualOrder {
+                position: None,
+                leverage: None,
+                trading_asset: None,
+                take_profit_price: None,
+                order_type: PerpetualOrderType::TrailingStop,
+                trigger_price: None,
+                position_id: Some(1),
+                trailing_distance: Some(TrailingDistance::Percentage(
+                    Decimal::from_str("0.1").unwrap(),
+                )),
+                linked_order_id: None,
+                expiration: None,
+                close_size: None,
+                health_threshold: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    let order_id: u64 = get_attr_from_events(&resp.events, "perpetual_order_id")
+        .unwrap()
+        .parse()
+        .unwrap();
+
+    let get_order = |app: &ElysApp| -> PerpetualOrder {
+        let resp: GetPerpetualOrderResp = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetPerpetualOrder { id: order_id })
+            .unwrap();
+        resp.order
+    };
+
+    assert_eq!(
+        get_order(&app).trigger_price.unwrap().rate,
+        Decimal::from_str("1.8").unwrap()
+    );
+
+    let sudo_msg = SudoMsg::ClockEndBlock {};
+
+    // The market price goes up to 4, the trigger price follows.
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices("0.25")))
+        .unwrap();
+    app.wasm_sudo(addr.clone(), &sudo_msg).unwrap();
+
+    let order = get_order(&app);
+    assert_eq!(order.status, Status::Pending);
+    assert_eq!(
+        order.trigger_price.unwrap().rate,
+        Decimal::from_str("3.6").unwrap()
+    );
+
+    // The market price pulls back to 3.2 which is below the trigger price.
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices("0.3125")))
+        .unwrap();
+    app.wasm_sudo(addr.clone(), &sudo_msg).unwrap();
+
+    let last_module = app
+        .init_modules(|router, _, store| router.custom.get_last_module(store).unwrap())
+        .unwrap();
+
+    assert_eq!(last_module, "PerpetualClose");
+    assert_eq!(get_order(&app).status, Status::Executed);
+}
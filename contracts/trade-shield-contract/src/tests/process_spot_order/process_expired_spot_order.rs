@@ -0,0 +1,113 @@
+use super::*;
+use cosmwasm_std::Coin;
+use cw_utils::Expiration;
+use elys_bindings::trade_shield::msg::query_resp::GetSpotOrderResp;
+
+use crate::tests::get_order_id_from_events::get_order_id_from_events;
+
+// This test case verifies that an expired order is canceled and refunded.
+// - The user places a limit sell of 2 BTC at 40,000 USDC which expires 10 blocks later.
+// - The price is never reached, the order stays pending until its expiration.
+// - Once expired, the order is canceled and the user gets their 2 BTC back.
+#[test]
+fn process_expired_spot_order() {
+    let wallets: Vec<(&str, Vec<Coin>)> = vec![("user", coins(2, "btc"))];
+    let mut app = ElysApp::new_with_wallets(wallets);
+
+    let usdc = "ibc/2180E84E20F5679FCC760D8C165B60F42065DEF7F46A72B447CFF1B7DC6C0A65";
+    let prices = vec![
+        Price::new(
+            "btc",
+            Decimal::from_atomics(Uint128::new(30000), 0).unwrap(),
+        ),
+        Price::new(usdc, Decimal::one()),
+    ];
+
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices))
+        .unwrap();
+
+    let code = ContractWrapper::new(execute, instantiate, query)
+        .with_reply(reply)
+        .with_sudo(sudo);
+    let code_id = app.store_code(Box::new(code));
+
+    let instantiate_msg = InstantiateMockMsg {
+        account_history_address: None,
+        spot_orders: vec![],
+        perpetual_orders: vec![],
+    };
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg,
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let expiration_height = app.block_info().height + 10;
+
+    let resp = app
+        .execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::CreateSpotOrder {
+                order_type: SpotOrderType::LimitSell,
+                order_source_denom: "btc".to_string(),
+                order_target_denom: usdc.to_string(),
+                order_price: Some(OrderPrice {
+                    base_denom: "btc".to_string(),
+                    quote_denom: usdc.to_string(),
+                    rate: Decimal::from_atomics(Uint128::new(40000), 0).unwrap(),
+                }),
+                trailing_distance: None,
+                linked_order_id: None,
+                expiration: Some(Expiration::AtHeight(expiration_height)),
+                max_slippage: None,
+                min_out: None,
+            },
+            &coins(2, "btc"),
+        )
+        .unwrap();
+
+    let order_id = get_order_id_from_events(&resp.events).unwrap();
+    let sudo_msg = SudoMsg::ClockEndBlock {};
+
+    let get_order = |app: &ElysApp| -> SpotOrder {
+        let resp: GetSpotOrderResp = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetSpotOrder { order_id })
+            .unwrap();
+        resp.order
+    };
+
+    app.update_block(|block| block.height = expiration_height - 1);
+    app.wasm_sudo(addr.clone(), &sudo_msg).unwrap();
+
+    assert_eq!(get_order(&app).status, Status::Pending);
+
+    app.update_block(|block| block.height = expiration_height);
+    app.wasm_sudo(addr.clone(), &sudo_msg).unwrap();
+
+    assert_eq!(get_order(&app).status, Status::Canceled);
+    assert_eq!(get_order(&app).cancel_reason, Some(CancelReason::Expired));
+    assert_eq!(
+        app.wrap()
+            .query_balance(&addr, "btc")
+            .unwrap()
+            .amount
+            .u128(),
+        0
+    );
+    assert_eq!(
+        app.wrap()
+            .query_balance("user", "btc")
+            .unwrap()
+            .amount
+            .u128(),
+        2
+    );
+}
@@ -0,0 +1,129 @@
+use super::*;
+use cosmwasm_std::Coin;
+use cw_utils::Expiration;
+
+use crate::tests::get_order_id_from_events::get_order_id_from_events;
+
+// This test case verifies the events emitted while the orders are processed.
+// - The user places a limit sell of 2 BTC at 30,000 USDC and a limit sell of 1 BTC at 40,000 USDC expiring next block.
+// - The BTC price reaches 30,000 USDC, the first order is triggered and executed, the second one is expired.
+// - The end blocker emits a triggered event with the market price and a canceled event with the reason.
+// - The reply emits an executed event with the same order attributes.
+#[test]
+fn process_order_events() {
+    let wallets: Vec<(&str, Vec<Coin>)> = vec![("user", coins(3, "btc"))];
+    let mut app = ElysApp::new_with_wallets(wallets);
+
+    let usdc = "ibc/2180E84E20F5679FCC760D8C165B60F42065DEF7F46A72B447CFF1B7DC6C0A65";
+    let prices = vec![
+        Price::new(
+            "btc",
+            Decimal::from_atomics(Uint128::new(30000), 0).unwrap(),
+        ),
+        Price::new(usdc, Decimal::one()),
+    ];
+
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices))
+        .unwrap();
+
+    let code = ContractWrapper::new(execute, instantiate, query)
+        .with_reply(reply)
+        .with_sudo(sudo);
+    let code_id = app.store_code(Box::new(code));
+
+    let instantiate_msg = InstantiateMockMsg {
+        account_history_address: None,
+        spot_orders: vec![],
+        perpetual_orders: vec![],
+    };
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg,
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let create_order = |app: &mut ElysApp, amount: u128, rate: u128, expiration| {
+        let resp = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::CreateSpotOrder {
+                    order_type: SpotOrderType::LimitSell,
+                    order_source_denom: "btc".to_string(),
+                    order_target_denom: usdc.to_string(),
+                    order_price: Some(OrderPrice {
+                        base_denom: "btc".to_string(),
+                        quote_denom: usdc.to_string(),
+                        rate: Decimal::from_atomics(Uint128::new(rate), 0).unwrap(),
+                    }),
+                    trailing_distance: None,
+                    linked_order_id: None,
+                    expiration,
+                    max_slippage: None,
+                    min_out: None,
+                },
+                &coins(amount, "btc"),
+            )
+            .unwrap();
+        get_order_id_from_events(&resp.events).unwrap()
+    };
+
+    let expiration_height = app.block_info().height + 1;
+    let triggered_id = create_order(&mut app, 2, 30000, None);
+    let expired_id = create_order(
+        &mut app,
+        1,
+        40000,
+        Some(Expiration::AtHeight(expiration_height)),
+    );
+
+    app.update_block(|block| block.height = expiration_height);
+    let resp = app
+        .wasm_sudo(addr.clone(), &SudoMsg::ClockEndBlock {})
+        .unwrap();
+
+    let find_event = |ty: &str, order_id: u64| -> Event {
+        resp.events
+            .iter()
+            .find(|event| {
+                event.ty == ty
+                    && event
+                        .attributes
+                        .iter()
+                        .any(|attr| attr.key == "order_id" && attr.value == order_id.to_string())
+            })
+            .cloned()
+            .unwrap()
+    };
+    let attr = |event: &Event, key: &str| -> String {
+        event
+            .attributes
+            .iter()
+            .find(|attr| attr.key == key)
+            .map(|attr| attr.value.clone())
+            .unwrap()
+    };
+
+    let triggered = find_event("wasm-process_spot_order", triggered_id);
+    assert_eq!(attr(&triggered, "result"), "triggered");
+    assert_eq!(attr(&triggered, "owner"), "user");
+    assert_eq!(attr(&triggered, "order_type"), "LimitSell");
+    assert_eq!(attr(&triggered, "pair"), format!("btc/{}", usdc));
+    assert_eq!(attr(&triggered, "trigger_rate"), "30000");
+    assert_eq!(attr(&triggered, "market_price"), "30000");
+
+    let canceled = find_event("wasm-process_spot_order", expired_id);
+    assert_eq!(attr(&canceled, "result"), "canceled");
+    assert_eq!(attr(&canceled, "reason"), "\"expired\"");
+
+    let executed = find_event("wasm-reply_to_spot_order", triggered_id);
+    assert_eq!(attr(&executed, "result"), "executed");
+    assert_eq!(attr(&executed, "fill_amount"), "2");
+    assert_eq!(attr(&executed, "amount_out"), "60000");
+}
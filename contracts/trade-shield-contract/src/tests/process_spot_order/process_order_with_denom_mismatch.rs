@@ -0,0 +1,84 @@
+use super::*;
+use cosmwasm_std::{BlockInfo, Coin, Timestamp};
+use elys_bindings::trade_shield::msg::query_resp::GetSpotOrderResp;
+
+// This test case verifies that an order whose price doesn't match its denoms is canceled with the reason.
+// - The user holds a limit sell of 2 BTC for USDC priced in ETH.
+// - The processing of the orders cancels it and refunds the 2 BTC.
+// - The order records the denom mismatch as the reason of its cancellation.
+#[test]
+fn process_order_with_denom_mismatch() {
+    let wallets: Vec<(&str, Vec<Coin>)> = vec![("owner", coins(2, "btc"))];
+    let mut app = ElysApp::new_with_wallets(wallets);
+
+    let usdc = "ibc/2180E84E20F5679FCC760D8C165B60F42065DEF7F46A72B447CFF1B7DC6C0A65";
+    let prices = vec![
+        Price::new(
+            "btc",
+            Decimal::from_atomics(Uint128::new(30000), 0).unwrap(),
+        ),
+        Price::new(usdc, Decimal::one()),
+    ];
+
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices))
+        .unwrap();
+
+    let code = ContractWrapper::new(execute, instantiate, query)
+        .with_reply(reply)
+        .with_sudo(sudo);
+    let code_id = app.store_code(Box::new(code));
+
+    let order = SpotOrder::new(
+        0,
+        SpotOrderType::LimitSell,
+        Some(OrderPrice {
+            base_denom: "eth".to_string(),
+            quote_denom: usdc.to_string(),
+            rate: Decimal::from_atomics(Uint128::new(40000), 0).unwrap(),
+        }),
+        coin(2, "btc"),
+        Addr::unchecked("user"),
+        usdc.to_string(),
+        &BlockInfo {
+            height: 50,
+            time: Timestamp::from_seconds(600),
+            chain_id: "elys-app".to_string(),
+        },
+    );
+
+    let instantiate_msg = InstantiateMockMsg {
+        account_history_address: None,
+        spot_orders: vec![order],
+        perpetual_orders: vec![],
+    };
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg,
+            &coins(2, "btc"),
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    app.wasm_sudo(addr.clone(), &SudoMsg::ClockEndBlock {})
+        .unwrap();
+
+    let resp: GetSpotOrderResp = app
+        .wrap()
+        .query_wasm_smart(addr.clone(), &QueryMsg::GetSpotOrder { order_id: 0 })
+        .unwrap();
+
+    assert_eq!(resp.order.status, Status::Canceled);
+    assert_eq!(resp.order.cancel_reason, Some(CancelReason::DenomMismatch));
+    assert_eq!(
+        app.wrap()
+            .query_balance("user", "btc")
+            .unwrap()
+            .amount
+            .u128(),
+        2
+    );
+}
@@ -0,0 +1,174 @@
+use super::*;
+use crate::entry_point::instantiate as contract_instantiate;
+use cosmwasm_std::Coin;
+use cw_utils::Expiration;
+use elys_bindings::trade_shield::msg::query_resp::GetSpotOrderResp;
+
+use crate::tests::get_order_id_from_events::get_order_id_from_events;
+
+// This test case verifies that the trailing stops follow the market with the page of orders checked in the block
+// and that the crossed orders off that page are still checked for their expiration.
+// - The contract checks 1 pending order per block, the BTC price is 30,000 USDC.
+// - The user places a limit sell at 100,000 USDC, a trailing stop selling 1 BTC 10% behind the market
+//   and a stop loss at 35,000 USDC which expires 5 blocks later.
+// - The BTC price rises to 40,000 USDC while the limit sell is on the page, the trailing stop stays at 27,000 USDC.
+// - In the next block the trailing stop is on the page and follows the market up to 36,000 USDC.
+// - 10 blocks later the BTC price falls to 35,000 USDC while the limit sell is on the page again,
+//   the trailing stop is executed and the expired stop loss is canceled and refunded instead of executed.
+#[test]
+fn process_orders_off_page() {
+    let wallets: Vec<(&str, Vec<Coin>)> = vec![("user", coins(3, "btc"))];
+    let mut app = ElysApp::new_with_wallets(wallets);
+
+    let usdc = "ibc/2180E84E20F5679FCC760D8C165B60F42065DEF7F46A72B447CFF1B7DC6C0A65";
+    let prices = |btc_price: u128| {
+        vec![
+            Price::new(
+                "btc",
+                Decimal::from_atomics(Uint128::new(btc_price), 0).unwrap(),
+            ),
+            Price::new(usdc, Decimal::one()),
+        ]
+    };
+
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices(30000)))
+        .unwrap();
+
+    let code = ContractWrapper::new(execute, contract_instantiate, query)
+        .with_reply(reply)
+        .with_sudo(sudo);
+    let code_id = app.store_code(Box::new(code));
+
+    let instantiate_msg = InstantiateMsg {
+        admin: None,
+        account_history_address: None,
+        process_order_limit: Some(1),
+    };
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg,
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let expiration_height = app.block_info().height + 5;
+
+    let mut create_order = |order_type: SpotOrderType,
+                            rate: Option<u128>,
+                            trailing_distance: Option<TrailingDistance>,
+                            expiration: Option<Expiration>|
+     -> u64 {
+        let resp = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::CreateSpotOrder {
+                    order_type,
+                    order_source_denom: "btc".to_string(),
+                    order_target_denom: usdc.to_string(),
+                    order_price: rate.map(|rate| OrderPrice {
+                        base_denom: "btc".to_string(),
+                        quote_denom: usdc.to_string(),
+                        rate: Decimal::from_atomics(Uint128::new(rate), 0).unwrap(),
+                    }),
+                    trailing_distance,
+                    linked_order_id: None,
+                    expiration,
+                    max_slippage: None,
+                    min_out: None,
+                },
+                &coins(1, "btc"),
+            )
+            .unwrap();
+        get_order_id_from_events(&resp.events).unwrap()
+    };
+
+    let far_limit_sell_id = create_order(SpotOrderType::LimitSell, Some(100000), None, None);
+    let trailing_stop_id = create_order(
+        SpotOrderType::TrailingStop,
+        None,
+        Some(TrailingDistance::Percentage(
+            Decimal::from_str("0.1").unwrap(),
+        )),
+        None,
+    );
+    let expiring_stop_loss_id = create_order(
+        SpotOrderType::StopLoss,
+        Some(35000),
+        None,
+        Some(Expiration::AtHeight(expiration_height)),
+    );
+
+    let get_order = |app: &ElysApp, order_id: u64| -> SpotOrder {
+        let resp: GetSpotOrderResp = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetSpotOrder { order_id })
+            .unwrap();
+        resp.order
+    };
+
+    let sudo_msg = SudoMsg::ClockEndBlock {};
+
+    let trailing_stop_rate = |app: &ElysApp| get_order(app, trailing_stop_id).order_price.rate;
+
+    // The limit sell at 100,000 USDC is on the page, the trailing stop is left behind.
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices(40000)))
+        .unwrap();
+    app.wasm_sudo(addr.clone(), &sudo_msg).unwrap();
+
+    assert_eq!(
+        trailing_stop_rate(&app),
+        Decimal::from_atomics(Uint128::new(27000), 0).unwrap()
+    );
+
+    // The trailing stop is on the page and follows the market.
+    app.wasm_sudo(addr.clone(), &sudo_msg).unwrap();
+
+    assert_eq!(
+        trailing_stop_rate(&app),
+        Decimal::from_atomics(Uint128::new(36000), 0).unwrap()
+    );
+
+    // The stop loss is on the page and not expired yet.
+    app.wasm_sudo(addr.clone(), &sudo_msg).unwrap();
+
+    assert_eq!(
+        get_order(&app, expiring_stop_loss_id).status,
+        Status::Pending
+    );
+
+    app.update_block(|block| block.height += 10);
+
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices(35000)))
+        .unwrap();
+    app.wasm_sudo(addr.clone(), &sudo_msg).unwrap();
+
+    assert_eq!(get_order(&app, far_limit_sell_id).status, Status::Pending);
+    assert_eq!(get_order(&app, trailing_stop_id).status, Status::Executed);
+
+    let expired_stop_loss = get_order(&app, expiring_stop_loss_id);
+    assert_eq!(expired_stop_loss.status, Status::Canceled);
+    assert_eq!(expired_stop_loss.cancel_reason, Some(CancelReason::Expired));
+
+    assert_eq!(
+        app.wrap()
+            .query_balance("user", "btc")
+            .unwrap()
+            .amount
+            .u128(),
+        1
+    );
+    assert_eq!(
+        app.wrap()
+            .query_balance("user", usdc)
+            .unwrap()
+            .amount
+            .u128(),
+        35000
+    );
+}
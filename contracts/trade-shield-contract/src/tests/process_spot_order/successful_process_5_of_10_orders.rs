@@ -272,6 +272,14 @@ fn create_dummy_orders() -> Vec<SpotOrder> {
                 height: 20,
                 time: Timestamp::from_seconds(500),
             },
+            trailing_stop: None,
+            group_id: None,
+            expiration: None,
+            max_slippage: None,
+            filled_amount: None,
+            twap_schedule: None,
+            cancel_reason: None,
+            fee: None,
         },
         SpotOrder {
             order_type: SpotOrderType::StopLoss,
@@ -291,6 +299,14 @@ fn create_dummy_orders() -> Vec<SpotOrder> {
                 height: 20,
                 time: Timestamp::from_seconds(500),
             },
+            trailing_stop: None,
+            group_id: None,
+            expiration: None,
+            max_slippage: None,
+            filled_amount: None,
+            twap_schedule: None,
+            cancel_reason: None,
+            fee: None,
         },
         SpotOrder {
             order_type: SpotOrderType::StopLoss,
@@ -310,6 +326,14 @@ fn create_dummy_orders() -> Vec<SpotOrder> {
                 height: 20,
                 time: Timestamp::from_seconds(500),
             },
+            trailing_stop: None,
+            group_id: None,
+            expiration: None,
+            max_slippage: None,
+            filled_amount: None,
+            twap_schedule: None,
+            cancel_reason: None,
+            fee: None,
         },
         SpotOrder {
             order_type: SpotOrderType::StopLoss,
@@ -329,6 +353,14 @@ fn create_dummy_orders() -> Vec<SpotOrder> {
                 height: 20,
                 time: Timestamp::from_seconds(500),
             },
+            trailing_stop: None,
+            group_id: None,
+            expiration: None,
+            max_slippage: None,
+            filled_amount: None,
+            twap_schedule: None,
+            cancel_reason: None,
+            fee: None,
         },
         SpotOrder {
             order_type: SpotOrderType::StopLoss,
@@ -348,6 +380,14 @@ fn create_dummy_orders() -> Vec<SpotOrder> {
                 height: 20,
                 time: Timestamp::from_seconds(500),
             },
+            trailing_stop: None,
+            group_id: None,
+            expiration: None,
+            max_slippage: None,
+            filled_amount: None,
+            twap_schedule: None,
+            cancel_reason: None,
+            fee: None,
         },
         SpotOrder {
             order_type: SpotOrderType::LimitSell,
@@ -367,6 +407,14 @@ fn create_dummy_orders() -> Vec<SpotOrder> {
                 height: 20,
                 time: Timestamp::from_seconds(500),
             },
+            trailing_stop: None,
+            group_id: None,
+            expiration: None,
+            max_slippage: None,
+            filled_amount: None,
+            twap_schedule: None,
+            cancel_reason: None,
+            fee: None,
         },
         SpotOrder {
             order_type: SpotOrderType::LimitSell,
@@ -386,6 +434,14 @@ fn create_dummy_orders() -> Vec<SpotOrder> {
                 height: 20,
                 time: Timestamp::from_seconds(500),
             },
+            trailing_stop: None,
+            group_id: None,
+            expiration: None,
+            max_slippage: None,
+            filled_amount: None,
+            twap_schedule: None,
+            cancel_reason: None,
+            fee: None,
         },
         SpotOrder {
             order_type: SpotOrderType::LimitSell,
@@ -405,6 +461,14 @@ fn create_dummy_orders() -> Vec<SpotOrder> {
                 height: 20,
                 time: Timestamp::from_seconds(500),
             },
+            trailing_stop: None,
+            group_id: None,
+            expiration: None,
+            max_slippage: None,
+            filled_amount: None,
+            twap_schedule: None,
+            cancel_reason: None,
+            fee: None,
         },
         SpotOrder {
             order_type: SpotOrderType::LimitSell,
@@ -424,6 +488,14 @@ fn create_dummy_orders() -> Vec<SpotOrder> {
                 height: 20,
                 time: Timestamp::from_seconds(500),
             },
+            trailing_stop: None,
+            group_id: None,
+            expiration: None,
+            max_slippage: None,
+            filled_amount: None,
+            twap_schedule: None,
+            cancel_reason: None,
+            fee: None,
         },
         SpotOrder {
             order_type: SpotOrderType::LimitSell,
@@ -443,6 +515,14 @@ fn create_dummy_orders() -> Vec<SpotOrder> {
                 height: 20,
                 time: Timestamp::from_seconds(500),
             },
+            trailing_stop: None,
+            group_id: None,
+            expiration: None,
+            max_slippage: None,
+            filled_amount: None,
+            twap_schedule: None,
+            cancel_reason: None,
+            fee: None,
         },
     ]
 }
@@ -0,0 +1,105 @@
+use super::*;
+use cosmwasm_std::Coin;
+use elys_bindings::trade_shield::msg::query_resp::GetSpotOrderResp;
+
+use crate::tests::get_order_id_from_events::get_order_id_from_events;
+
+// This test case verifies that only the orders crossed by the market price are executed.
+// - The user places limit sells of 1 BTC at 25,000, 45,000 and 35,000 USDC and a stop loss at 20,000 USDC.
+// - The BTC price is 40,000 USDC, the limit sells at 25,000 and 35,000 USDC are executed.
+// - The limit sell at 45,000 USDC and the stop loss stay in the order book.
+#[test]
+fn successful_process_crossed_orders() {
+    let wallets: Vec<(&str, Vec<Coin>)> = vec![("user", coins(4, "btc"))];
+    let mut app = ElysApp::new_with_wallets(wallets);
+
+    let usdc = "ibc/2180E84E20F5679FCC760D8C165B60F42065DEF7F46A72B447CFF1B7DC6C0A65";
+    let prices = vec![
+        Price::new(
+            "btc",
+            Decimal::from_atomics(Uint128::new(40000), 0).unwrap(),
+        ),
+        Price::new(usdc, Decimal::one()),
+    ];
+
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices))
+        .unwrap();
+
+    let code = ContractWrapper::new(execute, instantiate, query)
+        .with_reply(reply)
+        .with_sudo(sudo);
+    let code_id = app.store_code(Box::new(code));
+
+    let instantiate_msg = InstantiateMockMsg {
+        account_history_address: None,
+        spot_orders: vec![],
+        perpetual_orders: vec![],
+    };
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg,
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let mut create_order = |order_type: SpotOrderType, rate: u128| -> u64 {
+        let resp = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::CreateSpotOrder {
+                    order_type,
+                    order_source_denom: "btc".to_string(),
+                    order_target_denom: usdc.to_string(),
+                    order_price: Some(OrderPrice {
+                        base_denom: "btc".to_string(),
+                        quote_denom: usdc.to_string(),
+                        rate: Decimal::from_atomics(Uint128::new(rate), 0).unwrap(),
+                    }),
+                    trailing_distance: None,
+                    linked_order_id: None,
+                    expiration: None,
+                    max_slippage: None,
+                    min_out: None,
+                },
+                &coins(1, "btc"),
+            )
+            .unwrap();
+        get_order_id_from_events(&resp.events).unwrap()
+    };
+
+    let low_limit_sell = create_order(SpotOrderType::LimitSell, 25000);
+    let high_limit_sell = create_order(SpotOrderType::LimitSell, 45000);
+    let mid_limit_sell = create_order(SpotOrderType::LimitSell, 35000);
+    let stop_loss = create_order(SpotOrderType::StopLoss, 20000);
+
+    app.wasm_sudo(addr.clone(), &SudoMsg::ClockEndBlock {})
+        .unwrap();
+
+    let get_status = |order_id: u64| -> Status {
+        let resp: GetSpotOrderResp = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetSpotOrder { order_id })
+            .unwrap();
+        resp.order.status
+    };
+
+    assert_eq!(get_status(low_limit_sell), Status::Executed);
+    assert_eq!(get_status(mid_limit_sell), Status::Executed);
+    assert_eq!(get_status(high_limit_sell), Status::Pending);
+    assert_eq!(get_status(stop_loss), Status::Pending);
+
+    assert_eq!(
+        app.wrap()
+            .query_balance("user", usdc)
+            .unwrap()
+            .amount
+            .u128(),
+        80000
+    );
+}
@@ -0,0 +1,139 @@
+use super::*;
+use cosmwasm_std::Coin;
+use elys_bindings::trade_shield::msg::query_resp::GetSpotOrderResp;
+
+use crate::tests::get_order_id_from_events::get_order_id_from_events;
+
+// This test case verifies that linked orders form a "one-cancels-other" group.
+// - The user places a stop-loss at 27,000 USDC and a limit sell at 40,000 USDC linked to it, both selling 2 BTC.
+// - The BTC price rises to 40,000 USDC, the limit sell is executed and the user receives 80,000 USDC.
+// - The stop-loss is canceled and its 2 BTC are refunded to the user.
+#[test]
+fn successful_process_oco_orders() {
+    let wallets: Vec<(&str, Vec<Coin>)> = vec![("user", coins(4, "btc"))];
+    let mut app = ElysApp::new_with_wallets(wallets);
+
+    let usdc = "ibc/2180E84E20F5679FCC760D8C165B60F42065DEF7F46A72B447CFF1B7DC6C0A65";
+    let prices = |btc_price: u128| {
+        vec![
+            Price::new(
+                "btc",
+                Decimal::from_atomics(Uint128::new(btc_price), 0).unwrap(),
+            ),
+            Price::new(usdc, Decimal::one()),
+        ]
+    };
+
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices(30000)))
+        .unwrap();
+
+    let code = ContractWrapper::new(execute, instantiate, query)
+        .with_reply(reply)
+        .with_sudo(sudo);
+    let code_id = app.store_code(Box::new(code));
+
+    let instantiate_msg = InstantiateMockMsg {
+        account_history_address: None,
+        spot_orders: vec![],
+        perpetual_orders: vec![],
+    };
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg,
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let create_order = |app: &mut ElysApp,
+                        order_type: SpotOrderType,
+                        rate: u128,
+                        linked_order_id: Option<u64>|
+     -> u64 {
+        let resp = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::CreateSpotOrder {
+                    order_type,
+                    order_source_denom: "btc".to_string(),
+                    order_target_denom: usdc.to_string(),
+                    order_price: Some(OrderPrice {
+                        base_denom: "btc".to_string(),
+                        quote_denom: usdc.to_string(),
+                        rate: Decimal::from_atomics(Uint128::new(rate), 0).unwrap(),
+                    }),
+                    trailing_distance: None,
+                    linked_order_id,
+                    expiration: None,
+                    max_slippage: None,
+                    min_out: None,
+                },
+                &coins(2, "btc"),
+            )
+            .unwrap();
+        get_order_id_from_events(&resp.events).unwrap()
+    };
+
+    let stop_loss_id = create_order(&mut app, SpotOrderType::StopLoss, 27000, None);
+    let limit_sell_id = create_order(
+        &mut app,
+        SpotOrderType::LimitSell,
+        40000,
+        Some(stop_loss_id),
+    );
+
+    let get_order = |app: &ElysApp, order_id: u64| -> SpotOrder {
+        let resp: GetSpotOrderResp = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetSpotOrder { order_id })
+            .unwrap();
+        resp.order
+    };
+
+    assert_eq!(get_order(&app, stop_loss_id).group_id, Some(stop_loss_id));
+    assert_eq!(get_order(&app, limit_sell_id).group_id, Some(stop_loss_id));
+
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices(40000)))
+        .unwrap();
+    app.wasm_sudo(addr.clone(), &SudoMsg::ClockEndBlock {})
+        .unwrap();
+
+    assert_eq!(get_order(&app, limit_sell_id).status, Status::Executed);
+    assert_eq!(get_order(&app, stop_loss_id).status, Status::Canceled);
+    assert_eq!(
+        get_order(&app, stop_loss_id).cancel_reason,
+        Some(CancelReason::LinkedOrderExecuted {
+            order_id: limit_sell_id
+        })
+    );
+
+    assert_eq!(
+        app.wrap()
+            .query_balance(&addr, "btc")
+            .unwrap()
+            .amount
+            .u128(),
+        0
+    );
+    assert_eq!(
+        app.wrap()
+            .query_balance("user", "btc")
+            .unwrap()
+            .amount
+            .u128(),
+        2
+    );
+    assert_eq!(
+        app.wrap()
+            .query_balance("user", usdc)
+            .unwrap()
+            .amount
+            .u128(),
+        80000
+    );
+}
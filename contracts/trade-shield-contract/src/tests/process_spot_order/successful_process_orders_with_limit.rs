@@ -0,0 +1,106 @@
+use super::*;
+use crate::entry_point::instantiate as contract_instantiate;
+use cosmwasm_std::Coin;
+use elys_bindings::trade_shield::msg::query_resp::GetSpotOrderResp;
+
+use crate::tests::get_order_id_from_events::get_order_id_from_events;
+
+// This test case verifies that the number of orders processed per block is bounded.
+// - The contract processes at most 2 orders per block.
+// - The user places 3 limit sell orders of 1 BTC at 20,000 USDC while the BTC price is 30,000 USDC.
+// - The first block executes the first 2 orders, the next block resumes from the cursor and executes the last one.
+#[test]
+fn successful_process_orders_with_limit() {
+    let wallets: Vec<(&str, Vec<Coin>)> = vec![("user", coins(3, "btc"))];
+    let mut app = ElysApp::new_with_wallets(wallets);
+
+    let usdc = "ibc/2180E84E20F5679FCC760D8C165B60F42065DEF7F46A72B447CFF1B7DC6C0A65";
+    let prices = vec![
+        Price::new(
+            "btc",
+            Decimal::from_atomics(Uint128::new(30000), 0).unwrap(),
+        ),
+        Price::new(usdc, Decimal::one()),
+    ];
+
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices))
+        .unwrap();
+
+    let code = ContractWrapper::new(execute, contract_instantiate, query)
+        .with_reply(reply)
+        .with_sudo(sudo);
+    let code_id = app.store_code(Box::new(code));
+
+    let instantiate_msg = InstantiateMsg {
+        admin: None,
+        account_history_address: None,
+        process_order_limit: Some(2),
+    };
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg,
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let mut order_ids: Vec<u64> = vec![];
+
+    for _ in 0..3 {
+        let resp = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::CreateSpotOrder {
+                    order_type: SpotOrderType::LimitSell,
+                    order_source_denom: "btc".to_string(),
+                    order_target_denom: usdc.to_string(),
+                    order_price: Some(OrderPrice {
+                        base_denom: "btc".to_string(),
+                        quote_denom: usdc.to_string(),
+                        rate: Decimal::from_atomics(Uint128::new(20000), 0).unwrap(),
+                    }),
+                    trailing_distance: None,
+                    linked_order_id: None,
+                    expiration: None,
+                    max_slippage: None,
+                    min_out: None,
+                },
+                &coins(1, "btc"),
+            )
+            .unwrap();
+        order_ids.push(get_order_id_from_events(&resp.events).unwrap());
+    }
+
+    let get_status = |app: &ElysApp, order_id: u64| -> Status {
+        let resp: GetSpotOrderResp = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetSpotOrder { order_id })
+            .unwrap();
+        resp.order.status
+    };
+
+    let sudo_msg = SudoMsg::ClockEndBlock {};
+
+    app.wasm_sudo(addr.clone(), &sudo_msg).unwrap();
+
+    assert_eq!(get_status(&app, order_ids[0]), Status::Executed);
+    assert_eq!(get_status(&app, order_ids[1]), Status::Executed);
+    assert_eq!(get_status(&app, order_ids[2]), Status::Pending);
+
+    app.wasm_sudo(addr.clone(), &sudo_msg).unwrap();
+
+    assert_eq!(get_status(&app, order_ids[2]), Status::Executed);
+    assert_eq!(
+        app.wrap()
+            .query_balance("user", usdc)
+            .unwrap()
+            .amount
+            .u128(),
+        90000
+    );
+}
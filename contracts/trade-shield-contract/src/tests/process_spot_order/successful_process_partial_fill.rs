@@ -0,0 +1,132 @@
+use super::*;
+use cosmwasm_std::Coin;
+use elys_bindings::trade_shield::msg::query_resp::GetSpotOrderResp;
+
+use crate::tests::get_order_id_from_events::get_order_id_from_events;
+
+// This test case verifies that a "limit-sell" order is filled in slices when a full fill would slip too much.
+// - The user sells 100 BTC at 30,000 USDC with the default max slippage of 1%.
+// - Swapping the whole order would slip by 4%, only 25 BTC are sold and the order is partially filled.
+// - Swapping the 75 remaining BTC would slip by 2%, only 37 BTC are sold.
+// - The slippage is back to zero, the 38 remaining BTC are sold and the order is executed.
+#[test]
+fn successful_process_partial_fill() {
+    let wallets: Vec<(&str, Vec<Coin>)> = vec![("user", coins(100, "btc"))];
+    let mut app = ElysApp::new_with_wallets(wallets);
+
+    let usdc = "ibc/2180E84E20F5679FCC760D8C165B60F42065DEF7F46A72B447CFF1B7DC6C0A65";
+    let prices = vec![
+        Price::new(
+            "btc",
+            Decimal::from_atomics(Uint128::new(30000), 0).unwrap(),
+        ),
+        Price::new(usdc, Decimal::one()),
+    ];
+
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices))
+        .unwrap();
+
+    let code = ContractWrapper::new(execute, instantiate, query)
+        .with_reply(reply)
+        .with_sudo(sudo);
+    let code_id = app.store_code(Box::new(code));
+
+    let instantiate_msg = InstantiateMockMsg {
+        account_history_address: None,
+        spot_orders: vec![],
+        perpetual_orders: vec![],
+    };
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg,
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let resp = app
+        .execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::CreateSpotOrder {
+                order_type: SpotOrderType::LimitSell,
+                order_source_denom: "btc".to_string(),
+                order_target_denom: usdc.to_string(),
+                order_price: Some(OrderPrice {
+                    base_denom: "btc".to_string(),
+                    quote_denom: usdc.to_string(),
+                    rate: Decimal::from_atomics(Uint128::new(30000), 0).unwrap(),
+                }),
+                trailing_distance: None,
+                linked_order_id: None,
+                expiration: None,
+                max_slippage: None,
+                min_out: None,
+            },
+            &coins(100, "btc"),
+        )
+        .unwrap();
+
+    let order_id = get_order_id_from_events(&resp.events).unwrap();
+    let sudo_msg = SudoMsg::ClockEndBlock {};
+
+    let get_order = |app: &ElysApp| -> SpotOrder {
+        let resp: GetSpotOrderResp = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetSpotOrder { order_id })
+            .unwrap();
+        resp.order
+    };
+
+    let usdc_balance = |app: &ElysApp| -> u128 {
+        app.wrap()
+            .query_balance("user", usdc)
+            .unwrap()
+            .amount
+            .u128()
+    };
+
+    app.init_modules(|router, _, store| {
+        router.custom.set_swap_slippage(store, Decimal::percent(4))
+    })
+    .unwrap();
+    app.wasm_sudo(addr.clone(), &sudo_msg).unwrap();
+
+    let order = get_order(&app);
+    assert_eq!(order.status, Status::PartiallyFilled);
+    assert_eq!(order.filled_amount, Some(Uint128::new(25)));
+    assert_eq!(order.remaining_amount(), coin(75, "btc"));
+    assert_eq!(usdc_balance(&app), 750000);
+
+    app.init_modules(|router, _, store| {
+        router.custom.set_swap_slippage(store, Decimal::percent(2))
+    })
+    .unwrap();
+    app.wasm_sudo(addr.clone(), &sudo_msg).unwrap();
+
+    let order = get_order(&app);
+    assert_eq!(order.status, Status::PartiallyFilled);
+    assert_eq!(order.remaining_amount(), coin(38, "btc"));
+    assert_eq!(usdc_balance(&app), 1860000);
+
+    app.init_modules(|router, _, store| router.custom.set_swap_slippage(store, Decimal::zero()))
+        .unwrap();
+    app.wasm_sudo(addr.clone(), &sudo_msg).unwrap();
+
+    let order = get_order(&app);
+    assert_eq!(order.status, Status::Executed);
+    assert_eq!(order.filled_amount, Some(Uint128::new(100)));
+    assert_eq!(usdc_balance(&app), 3000000);
+    assert_eq!(
+        app.wrap()
+            .query_balance(&addr, "btc")
+            .unwrap()
+            .amount
+            .u128(),
+        0
+    );
+}
@@ -0,0 +1,123 @@
+use super::*;
+use cosmwasm_std::Coin;
+use elys_bindings::trade_shield::msg::query_resp::GetSpotOrderResp;
+
+use crate::tests::get_order_id_from_events::get_order_id_from_events;
+
+// This test case verifies that a "trailing-stop" order follows the market price up and triggers on the way down.
+// - The user sells 2 BTC with a trailing distance of 10%, the BTC price is 30,000 USDC so the trigger price is 27,000 USDC.
+// - The BTC price rises to 40,000 USDC, the trigger price follows and becomes 36,000 USDC.
+// - The BTC price falls to 35,000 USDC, the order is executed and the user receives 70,000 USDC.
+#[test]
+fn successful_process_trailing_stop_order() {
+    let wallets: Vec<(&str, Vec<Coin>)> = vec![("user", coins(2, "btc"))];
+    let mut app = ElysApp::new_with_wallets(wallets);
+
+    let usdc = "ibc/2180E84E20F5679FCC760D8C165B60F42065DEF7F46A72B447CFF1B7DC6C0A65";
+    let prices = |btc_price: u128| {
+        vec![
+            Price::new(
+                "btc",
+                Decimal::from_atomics(Uint128::new(btc_price), 0).unwrap(),
+            ),
+            Price::new(usdc, Decimal::one()),
+        ]
+    };
+
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices(30000)))
+        .unwrap();
+
+    let code = ContractWrapper::new(execute, instantiate, query)
+        .with_reply(reply)
+        .with_sudo(sudo);
+    let code_id = app.store_code(Box::new(code));
+
+    let instantiate_msg = InstantiateMockMsg {
+        account_history_address: None,
+        spot_orders: vec![],
+        perpetual_orders: vec![],
+    };
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg,
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let resp = app
+        .execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::CreateSpotOrder {
+                order_type: SpotOrderType::TrailingStop,
+                order_source_denom: "btc".to_string(),
+                order_target_denom: usdc.to_string(),
+                order_price: None,
+                trailing_distance: Some(TrailingDistance::Percentage(
+                    Decimal::from_str("0.1").unwrap(),
+                )),
+                linked_order_id: None,
+                expiration: None,
+                max_slippage: None,
+                min_out: None,
+            },
+            &coins(2, "btc"),
+        )
+        .unwrap();
+
+    let order_id = get_order_id_from_events(&resp.events).unwrap();
+    let sudo_msg = SudoMsg::ClockEndBlock {};
+
+    let get_order = |app: &ElysApp| -> SpotOrder {
+        let resp: GetSpotOrderResp = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetSpotOrder { order_id })
+            .unwrap();
+        resp.order
+    };
+
+    let order = get_order(&app);
+    assert_eq!(order.order_price.rate, Decimal::from_str("27000").unwrap());
+
+    // The BTC price goes up, the trigger price follows.
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices(40000)))
+        .unwrap();
+    app.wasm_sudo(addr.clone(), &sudo_msg).unwrap();
+
+    let order = get_order(&app);
+    assert_eq!(order.status, Status::Pending);
+    assert_eq!(order.order_price.rate, Decimal::from_str("36000").unwrap());
+    assert_eq!(
+        order.trailing_stop.unwrap().best_price,
+        Decimal::from_str("40000").unwrap()
+    );
+
+    // The BTC price falls below the new trigger price but above the initial one.
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices(35000)))
+        .unwrap();
+    app.wasm_sudo(addr.clone(), &sudo_msg).unwrap();
+
+    assert_eq!(get_order(&app).status, Status::Executed);
+
+    assert_eq!(
+        app.wrap()
+            .query_balance(&addr, "btc")
+            .unwrap()
+            .amount
+            .u128(),
+        0
+    );
+    assert_eq!(
+        app.wrap()
+            .query_balance("user", usdc)
+            .unwrap()
+            .amount
+            .u128(),
+        70000
+    );
+}
@@ -0,0 +1,131 @@
+use super::*;
+use cosmwasm_std::Coin;
+use cw_utils::Duration;
+use elys_bindings::trade_shield::msg::query_resp::GetSpotOrderResp;
+
+// This test case verifies that a "twap" order splits its amount into slices swapped at a regular interval.
+// - The user sells 100 BTC in 3 slices, one every 10 blocks.
+// - The first slice of 33 BTC is sold at 30,000 USDC right after the order is created.
+// - Nothing is sold until 10 blocks have passed.
+// - The BTC price rises to 33,000 USDC, the second slice of 33 BTC and the last slice of 34 BTC are sold at this price.
+#[test]
+fn successful_process_twap_order() {
+    let wallets: Vec<(&str, Vec<Coin>)> = vec![("user", coins(100, "btc"))];
+    let mut app = ElysApp::new_with_wallets(wallets);
+
+    let usdc = "ibc/2180E84E20F5679FCC760D8C165B60F42065DEF7F46A72B447CFF1B7DC6C0A65";
+    let prices = |btc_price: u128| {
+        vec![
+            Price::new(
+                "btc",
+                Decimal::from_atomics(Uint128::new(btc_price), 0).unwrap(),
+            ),
+            Price::new(usdc, Decimal::one()),
+        ]
+    };
+
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices(30000)))
+        .unwrap();
+
+    let code = ContractWrapper::new(execute, instantiate, query)
+        .with_reply(reply)
+        .with_sudo(sudo);
+    let code_id = app.store_code(Box::new(code));
+
+    let instantiate_msg = InstantiateMockMsg {
+        account_history_address: None,
+        spot_orders: vec![],
+        perpetual_orders: vec![],
+    };
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg,
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("user"),
+        addr.clone(),
+        &ExecuteMsg::CreateTwapOrder {
+            order_source_denom: "btc".to_string(),
+            order_target_denom: usdc.to_string(),
+            slices: 3,
+            interval: Duration::Height(10),
+            max_slippage: None,
+            expiration: None,
+        },
+        &coins(100, "btc"),
+    )
+    .unwrap();
+
+    let order_id = 1;
+    let sudo_msg = SudoMsg::ClockEndBlock {};
+
+    let get_order = |app: &ElysApp| -> SpotOrder {
+        let resp: GetSpotOrderResp = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetSpotOrder { order_id })
+            .unwrap();
+        resp.order
+    };
+
+    let usdc_balance = |app: &ElysApp| -> u128 {
+        app.wrap()
+            .query_balance("user", usdc)
+            .unwrap()
+            .amount
+            .u128()
+    };
+
+    app.wasm_sudo(addr.clone(), &sudo_msg).unwrap();
+
+    let order = get_order(&app);
+    assert_eq!(order.status, Status::PartiallyFilled);
+    assert_eq!(order.remaining_amount(), coin(67, "btc"));
+    assert_eq!(order.twap_schedule.as_ref().unwrap().executed_slices, 1);
+    assert_eq!(usdc_balance(&app), 990000);
+
+    // The next slice is not due yet.
+    app.update_block(|block| block.height += 5);
+    app.wasm_sudo(addr.clone(), &sudo_msg).unwrap();
+
+    assert_eq!(get_order(&app).remaining_amount(), coin(67, "btc"));
+
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices(33000)))
+        .unwrap();
+
+    app.update_block(|block| block.height += 5);
+    app.wasm_sudo(addr.clone(), &sudo_msg).unwrap();
+
+    assert_eq!(get_order(&app).remaining_amount(), coin(34, "btc"));
+    assert_eq!(usdc_balance(&app), 2079000);
+
+    app.update_block(|block| block.height += 10);
+    app.wasm_sudo(addr.clone(), &sudo_msg).unwrap();
+
+    let order = get_order(&app);
+    let twap_schedule = order.twap_schedule.unwrap();
+    assert_eq!(order.status, Status::Executed);
+    assert_eq!(order.filled_amount, Some(Uint128::new(100)));
+    assert_eq!(twap_schedule.executed_slices, 3);
+    assert_eq!(twap_schedule.received_amount, Uint128::new(3201000));
+    assert_eq!(
+        twap_schedule.average_price,
+        Some(Decimal::from_str("32010").unwrap())
+    );
+    assert_eq!(usdc_balance(&app), 3201000);
+    assert_eq!(
+        app.wrap()
+            .query_balance(&addr, "btc")
+            .unwrap()
+            .amount
+            .u128(),
+        0
+    );
+}
@@ -0,0 +1,170 @@
+use super::*;
+use cosmwasm_std::{coins, BlockInfo, Coin, Timestamp};
+use elys_bindings::trade_shield::msg::query_resp::{GetProtocolFeesResp, GetSpotOrderResp};
+
+// This test case verifies that a protocol fee is taken from an executed order and withdrawn by the fee collector.
+// - The fee is set at 100 basis points and a limit sell of 200 BTC at 30,000 USDC is executed.
+// - 2 BTC are kept as fee, the user receives 198 * 30,000 USDC.
+// - Only the fee collector can withdraw the 2 BTC.
+#[test]
+fn successful_withdraw_protocol_fees() {
+    let usdc = "ibc/2180E84E20F5679FCC760D8C165B60F42065DEF7F46A72B447CFF1B7DC6C0A65";
+    let wallets: Vec<(&str, Vec<Coin>)> = vec![("owner", coins(200, "btc"))];
+    let mut app = ElysApp::new_with_wallets(wallets);
+
+    let prices = vec![
+        Price::new(
+            "btc",
+            Decimal::from_atomics(Uint128::new(30000), 0).unwrap(),
+        ),
+        Price::new(usdc, Decimal::one()),
+    ];
+
+    let code = ContractWrapper::new(execute, instantiate, query)
+        .with_reply(reply)
+        .with_sudo(sudo);
+    let code_id = app.store_code(Box::new(code));
+
+    let order = SpotOrder::new(
+        0,
+        SpotOrderType::LimitSell,
+        Some(OrderPrice {
+            base_denom: "btc".to_string(),
+            quote_denom: usdc.to_string(),
+            rate: Decimal::from_str("30000").unwrap(),
+        }),
+        coin(200, "btc"),
+        Addr::unchecked("user"),
+        usdc.to_string(),
+        &BlockInfo {
+            height: 50,
+            time: Timestamp::from_seconds(600),
+            chain_id: "elys-app".to_string(),
+        },
+    );
+
+    let instantiate_msg = InstantiateMockMsg {
+        account_history_address: None,
+        spot_orders: vec![order],
+        perpetual_orders: vec![],
+    };
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg,
+            &coins(200, "btc"),
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices))
+        .unwrap();
+
+    let update_config_msg = |fee_collector: Option<String>| ExecuteMsg::UpdateConfig {
+        admin: None,
+        account_history_address: None,
+        allowed_pairs: None,
+        min_order_amounts: None,
+        max_order_amounts: None,
+        max_leverage: None,
+        pause: None,
+        fee_bps: Some(100),
+        fee_collector,
+        allowed_denoms: None,
+        validate_assets: None,
+        process_order_limit: None,
+    };
+
+    app.execute_contract(
+        Addr::unchecked("owner"),
+        addr.clone(),
+        &update_config_msg(None),
+        &[],
+    )
+    .unwrap();
+
+    app.wasm_sudo(addr.clone(), &SudoMsg::ClockEndBlock {})
+        .unwrap();
+
+    let resp: GetSpotOrderResp = app
+        .wrap()
+        .query_wasm_smart(addr.clone(), &QueryMsg::GetSpotOrder { order_id: 0 })
+        .unwrap();
+
+    assert_eq!(resp.order.status, Status::Executed);
+    assert_eq!(resp.order.fee, Some(coin(2, "btc")));
+    assert_eq!(
+        app.wrap()
+            .query_balance("user", usdc)
+            .unwrap()
+            .amount
+            .u128(),
+        198 * 30000
+    );
+
+    let resp: GetProtocolFeesResp = app
+        .wrap()
+        .query_wasm_smart(addr.clone(), &QueryMsg::GetProtocolFees {})
+        .unwrap();
+
+    assert_eq!(resp.fees, coins(2, "btc"));
+
+    app.execute_contract(
+        Addr::unchecked("owner"),
+        addr.clone(),
+        &update_config_msg(Some("collector".to_string())),
+        &[],
+    )
+    .unwrap();
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::WithdrawProtocolFees {},
+            &[],
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        ContractError::FeeCollectorUnauthorized {
+            sender: Addr::unchecked("owner")
+        },
+        err.downcast().unwrap()
+    );
+
+    app.execute_contract(
+        Addr::unchecked("collector"),
+        addr.clone(),
+        &ExecuteMsg::WithdrawProtocolFees {},
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(
+        app.wrap()
+            .query_balance("collector", "btc")
+            .unwrap()
+            .amount
+            .u128(),
+        2
+    );
+    assert_eq!(
+        app.wrap()
+            .query_balance(&addr, "btc")
+            .unwrap()
+            .amount
+            .u128(),
+        0
+    );
+
+    let resp: GetProtocolFeesResp = app
+        .wrap()
+        .query_wasm_smart(addr, &QueryMsg::GetProtocolFees {})
+        .unwrap();
+
+    assert!(resp.fees.is_empty());
+}
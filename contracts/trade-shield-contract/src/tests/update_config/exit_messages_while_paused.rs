@@ -0,0 +1,121 @@
+use super::*;
+use cosmwasm_std::Int128;
+use elys_bindings::types::EarnType;
+
+// This test case verifies that users can still get their funds out while every feature is paused.
+// - The admin pauses every feature, staking and joining a pool are rejected.
+// - Unstaking, canceling an unstake or a vesting, claiming rewards or commission, exiting a pool
+//   and closing a leveraged LP position still go through.
+#[test]
+fn exit_messages_while_paused() {
+    let mut app = ElysApp::new();
+
+    let code = ContractWrapper::new(execute, instantiate, query);
+    let code_id = app.store_code(Box::new(code));
+
+    let instantiate_msg = InstantiateMockMsg {
+        account_history_address: None,
+        spot_orders: vec![],
+        perpetual_orders: vec![],
+    };
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg,
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("owner"),
+        addr.clone(),
+        &ExecuteMsg::UpdateConfig {
+            admin: None,
+            account_history_address: None,
+            allowed_pairs: None,
+            min_order_amounts: None,
+            max_order_amounts: None,
+            max_leverage: None,
+            pause: Some(PauseFlags::all()),
+            fee_bps: None,
+            fee_collector: None,
+            allowed_denoms: None,
+            validate_assets: None,
+            process_order_limit: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let paused_msgs = vec![
+        (
+            ExecuteMsg::StakeRequest {
+                amount: 10,
+                asset: "uusdc".to_string(),
+                validator_address: None,
+            },
+            "staking messages",
+        ),
+        (
+            ExecuteMsg::AmmJoinPoolRequest {
+                pool_id: 1,
+                max_amounts_in: vec![coin(10, "uusdc")],
+                share_amount_out: Uint128::new(10),
+                no_remaining: false,
+            },
+            "liquidity messages",
+        ),
+    ];
+
+    for (msg, feature) in paused_msgs {
+        let err = app
+            .execute_contract(Addr::unchecked("user"), addr.clone(), &msg, &[])
+            .unwrap_err();
+
+        assert_eq!(
+            ContractError::Paused {
+                feature: feature.to_string()
+            },
+            err.downcast().unwrap()
+        );
+    }
+
+    let exit_msgs = vec![
+        ExecuteMsg::UnstakeRequest {
+            amount: 10,
+            asset: "uusdc".to_string(),
+            validator_address: None,
+        },
+        ExecuteMsg::ElysCancelUnstakeRequest {
+            validator_address: "validator".to_string(),
+            amount: coin(10, "uelys"),
+            creation_height: 1,
+        },
+        ExecuteMsg::EdenCancelVestRequest { amount: 10 },
+        ExecuteMsg::ClaimRewardsRequest {
+            withdraw_type: EarnType::AllProgram,
+        },
+        ExecuteMsg::ClaimValidatorCommissionRequest {
+            validator_address: "validator".to_string(),
+        },
+        ExecuteMsg::AmmExitPoolRequest {
+            pool_id: 1,
+            min_amounts_out: vec![coin(10, "uusdc")],
+            share_amount_in: Uint128::new(10),
+            token_out_denom: "uusdc".to_string(),
+        },
+        ExecuteMsg::LeveragelpClose {
+            position_id: 1,
+            amount: Int128::new(10),
+        },
+    ];
+
+    for msg in exit_msgs {
+        app.execute_contract(Addr::unchecked("user"), addr.clone(), &msg, &[])
+            .unwrap();
+    }
+}
@@ -0,0 +1,95 @@
+use super::*;
+use cosmwasm_std::{Coin, SignedDecimal};
+
+// This test case verifies that the admin cannot set order limits that reject every order.
+// - The admin sets a minimum of 100 USDC and a maximum of 500 USDC.
+// - A maximum of 50 USDC below the stored minimum is rejected, so is a minimum of 1000 USDC
+//   above the stored maximum.
+// - A maximum leverage of 0 or below is rejected, a maximum leverage of 5 is accepted.
+#[test]
+fn invalid_order_limits() {
+    let mut app = ElysApp::new();
+
+    let instantiate_msg = InstantiateMockMsg {
+        account_history_address: None,
+        spot_orders: vec![],
+        perpetual_orders: vec![],
+    };
+
+    let code = ContractWrapper::new(execute, instantiate, query);
+    let code_id = app.store_code(Box::new(code));
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg,
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let update_config_msg =
+        |min_order_amounts: Option<Vec<Coin>>,
+         max_order_amounts: Option<Vec<Coin>>,
+         max_leverage: Option<&str>| ExecuteMsg::UpdateConfig {
+            admin: None,
+            account_history_address: None,
+            allowed_pairs: None,
+            min_order_amounts,
+            max_order_amounts,
+            max_leverage: max_leverage.map(|leverage| SignedDecimal::from_str(leverage).unwrap()),
+            pause: None,
+            fee_bps: None,
+            fee_collector: None,
+            allowed_denoms: None,
+            validate_assets: None,
+            process_order_limit: None,
+        };
+
+    let mut update_config = |msg: ExecuteMsg| -> Result<(), ContractError> {
+        app.execute_contract(Addr::unchecked("owner"), addr.clone(), &msg, &[])
+            .map(|_| ())
+            .map_err(|err| err.downcast().unwrap())
+    };
+
+    update_config(update_config_msg(
+        Some(coins(100, "usdc")),
+        Some(coins(500, "usdc")),
+        None,
+    ))
+    .unwrap();
+
+    assert_eq!(
+        update_config(update_config_msg(None, Some(coins(50, "usdc")), None)).unwrap_err(),
+        ContractError::OrderAmountRange {
+            min: coin(100, "usdc"),
+            max: coin(50, "usdc"),
+        }
+    );
+
+    assert_eq!(
+        update_config(update_config_msg(Some(coins(1000, "usdc")), None, None)).unwrap_err(),
+        ContractError::OrderAmountRange {
+            min: coin(1000, "usdc"),
+            max: coin(500, "usdc"),
+        }
+    );
+
+    assert_eq!(
+        update_config(update_config_msg(None, None, Some("0"))).unwrap_err(),
+        ContractError::MaxLeverageNotPositive {
+            max_leverage: SignedDecimal::zero(),
+        }
+    );
+
+    assert_eq!(
+        update_config(update_config_msg(None, None, Some("-2"))).unwrap_err(),
+        ContractError::MaxLeverageNotPositive {
+            max_leverage: SignedDecimal::from_str("-2").unwrap(),
+        }
+    );
+
+    update_config(update_config_msg(None, None, Some("5"))).unwrap();
+}
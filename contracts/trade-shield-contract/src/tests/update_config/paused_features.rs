@@ -0,0 +1,159 @@
+use super::*;
+use cosmwasm_std::Coin;
+use cw_utils::Expiration;
+use elys_bindings::trade_shield::msg::query_resp::GetSpotOrderResp;
+
+use crate::tests::get_order_id_from_events::get_order_id_from_events;
+
+// This test case verifies that paused features reject their messages while cancellations go through.
+// - The user places a limit sell of 2 BTC which expires 10 blocks later.
+// - The admin pauses every feature, the expired order is not processed and no order can be created.
+// - The user can still cancel the order and get their 2 BTC back.
+#[test]
+fn paused_features() {
+    let wallets: Vec<(&str, Vec<Coin>)> = vec![("user", coins(4, "btc"))];
+    let mut app = ElysApp::new_with_wallets(wallets);
+
+    let usdc = "ibc/2180E84E20F5679FCC760D8C165B60F42065DEF7F46A72B447CFF1B7DC6C0A65";
+    let prices = vec![
+        Price::new(
+            "btc",
+            Decimal::from_atomics(Uint128::new(30000), 0).unwrap(),
+        ),
+        Price::new(usdc, Decimal::one()),
+    ];
+
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices))
+        .unwrap();
+
+    let code = ContractWrapper::new(execute, instantiate, query)
+        .with_reply(reply)
+        .with_sudo(sudo);
+    let code_id = app.store_code(Box::new(code));
+
+    let instantiate_msg = InstantiateMockMsg {
+        account_history_address: None,
+        spot_orders: vec![],
+        perpetual_orders: vec![],
+    };
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg,
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let expiration_height = app.block_info().height + 10;
+
+    let create_order_msg = ExecuteMsg::CreateSpotOrder {
+        order_type: SpotOrderType::LimitSell,
+        order_source_denom: "btc".to_string(),
+        order_target_denom: usdc.to_string(),
+        order_price: Some(OrderPrice {
+            base_denom: "btc".to_string(),
+            quote_denom: usdc.to_string(),
+            rate: Decimal::from_atomics(Uint128::new(40000), 0).unwrap(),
+        }),
+        trailing_distance: None,
+        linked_order_id: None,
+        expiration: Some(Expiration::AtHeight(expiration_height)),
+        max_slippage: None,
+        min_out: None,
+    };
+
+    let resp = app
+        .execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &create_order_msg,
+            &coins(2, "btc"),
+        )
+        .unwrap();
+
+    let order_id = get_order_id_from_events(&resp.events).unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("owner"),
+        addr.clone(),
+        &ExecuteMsg::UpdateConfig {
+            admin: None,
+            account_history_address: None,
+            allowed_pairs: None,
+            min_order_amounts: None,
+            max_order_amounts: None,
+            max_leverage: None,
+            pause: Some(PauseFlags::all()),
+            fee_bps: None,
+            fee_collector: None,
+            allowed_denoms: None,
+            validate_assets: None,
+            process_order_limit: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.update_block(|block| block.height = expiration_height);
+    app.wasm_sudo(addr.clone(), &SudoMsg::ClockEndBlock {})
+        .unwrap();
+
+    let resp: GetSpotOrderResp = app
+        .wrap()
+        .query_wasm_smart(addr.clone(), &QueryMsg::GetSpotOrder { order_id })
+        .unwrap();
+    assert_eq!(resp.order.status, Status::Pending);
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &create_order_msg,
+            &coins(2, "btc"),
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        ContractError::Paused {
+            feature: "spot orders".to_string()
+        },
+        err.downcast().unwrap()
+    );
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::EdenVestRequest { amount: 1 },
+            &[],
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        ContractError::Paused {
+            feature: "staking messages".to_string()
+        },
+        err.downcast().unwrap()
+    );
+
+    app.execute_contract(
+        Addr::unchecked("user"),
+        addr.clone(),
+        &ExecuteMsg::CancelSpotOrder { order_id },
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(
+        app.wrap()
+            .query_balance("user", "btc")
+            .unwrap()
+            .amount
+            .u128(),
+        4
+    );
+}
@@ -0,0 +1,133 @@
+use super::*;
+use crate::entry_point::{instantiate as contract_instantiate, migrate};
+use cosmwasm_std::from_json;
+use elys_bindings::types::PageRequest;
+
+// This test case verifies that the number of orders processed per block is never zero and survives a migration.
+// - Instantiating the contract with a limit of 0 is rejected, the contract is instantiated with a limit of 2.
+// - The admin cannot set the limit to 0 but can set it to 5.
+// - A migration without a limit keeps the limit of 5, a migration with a limit of 0 is rejected
+//   and a migration with a limit of 10 sets it.
+#[test]
+fn process_order_limit() {
+    let mut app = ElysApp::new();
+
+    let code = ContractWrapper::new(execute, contract_instantiate, query).with_migrate(migrate);
+    let code_id = app.store_code(Box::new(code));
+
+    let instantiate_msg = |process_order_limit: u64| InstantiateMsg {
+        admin: None,
+        account_history_address: None,
+        process_order_limit: Some(process_order_limit),
+    };
+
+    assert!(app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg(0),
+            &[],
+            "Contract",
+            Some("owner".to_string()),
+        )
+        .is_err());
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg(2),
+            &[],
+            "Contract",
+            Some("owner".to_string()),
+        )
+        .unwrap();
+
+    let get_limits = |app: &ElysApp| -> (u64, u64) {
+        let limit = |key: &str| {
+            let page: PageRequest = from_json(
+                app.wrap()
+                    .query_wasm_raw(addr.clone(), key.as_bytes())
+                    .unwrap()
+                    .unwrap(),
+            )
+            .unwrap();
+            page.limit
+        };
+        (
+            limit("process spot order pagination"),
+            limit("process perpetual order pagination"),
+        )
+    };
+
+    assert_eq!(get_limits(&app), (2, 2));
+
+    let update_config_msg = |process_order_limit: u64| ExecuteMsg::UpdateConfig {
+        admin: None,
+        account_history_address: None,
+        allowed_pairs: None,
+        min_order_amounts: None,
+        max_order_amounts: None,
+        max_leverage: None,
+        pause: None,
+        fee_bps: None,
+        fee_collector: None,
+        allowed_denoms: None,
+        validate_assets: None,
+        process_order_limit: Some(process_order_limit),
+    };
+
+    assert!(app
+        .execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &update_config_msg(0),
+            &[],
+        )
+        .is_err());
+
+    app.execute_contract(
+        Addr::unchecked("owner"),
+        addr.clone(),
+        &update_config_msg(5),
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(get_limits(&app), (5, 5));
+
+    let migrate_msg = |process_order_limit: Option<u64>| MigrateMsg {
+        admin: None,
+        account_history_address: None,
+        process_order_limit,
+    };
+
+    app.migrate_contract(
+        Addr::unchecked("owner"),
+        addr.clone(),
+        &migrate_msg(None),
+        code_id,
+    )
+    .unwrap();
+
+    assert_eq!(get_limits(&app), (5, 5));
+
+    assert!(app
+        .migrate_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &migrate_msg(Some(0)),
+            code_id,
+        )
+        .is_err());
+
+    app.migrate_contract(
+        Addr::unchecked("owner"),
+        addr.clone(),
+        &migrate_msg(Some(10)),
+        code_id,
+    )
+    .unwrap();
+
+    assert_eq!(get_limits(&app), (10, 10));
+}
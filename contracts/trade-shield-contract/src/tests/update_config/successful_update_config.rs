@@ -0,0 +1,187 @@
+use crate::msg::query_resp::ConfigResp;
+
+use super::*;
+
+#[test]
+fn successful_update_config() {
+    let wallet = vec![("user", coins(1000, "usdc"))];
+    let mut app = ElysApp::new_with_wallets(wallet);
+
+    let prices = vec![
+        Price::new("btc", Decimal::from_str("30000.0").unwrap()),
+        Price::new("usdc", Decimal::from_str("1.0").unwrap()),
+    ];
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices).unwrap());
+
+    let instantiate_msg = InstantiateMockMsg {
+        account_history_address: None,
+        spot_orders: vec![],
+        perpetual_orders: vec![],
+    };
+
+    let code = ContractWrapper::new(execute, instantiate, query);
+    let code_id = app.store_code(Box::new(code));
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg,
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let allowed_pairs = vec![TradingPair {
+        base_denom: "btc".to_string(),
+        quote_denom: "usdc".to_string(),
+    }];
+
+    app.execute_contract(
+        Addr::unchecked("owner"),
+        addr.clone(),
+        &ExecuteMsg::UpdateConfig {
+            admin: None,
+            account_history_address: None,
+            allowed_pairs: Some(allowed_pairs.clone()),
+            min_order_amounts: Some(coins(100, "usdc")),
+            max_order_amounts: Some(coins(500, "usdc")),
+            max_leverage: None,
+            pause: Some(PauseFlags {
+                spot_orders: true,
+                ..Default::default()
+            }),
+            fee_bps: None,
+            fee_collector: None,
+            allowed_denoms: None,
+            validate_assets: None,
+            process_order_limit: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let resp: ConfigResp = app
+        .wrap()
+        .query_wasm_smart(&addr, &QueryMsg::Config {})
+        .unwrap();
+
+    assert_eq!(resp.config.admin, Addr::unchecked("owner"));
+    assert_eq!(resp.config.allowed_pairs, allowed_pairs);
+    assert_eq!(resp.config.min_order_amounts, coins(100, "usdc"));
+    assert_eq!(resp.config.max_order_amounts, coins(500, "usdc"));
+    assert!(resp.config.pause.spot_orders);
+
+    let create_order_msg = |order_target_denom: &str| ExecuteMsg::CreateSpotOrder {
+        order_type: SpotOrderType::LimitBuy,
+        order_source_denom: "usdc".to_string(),
+        order_target_denom: order_target_denom.to_string(),
+        order_price: Some(OrderPrice {
+            base_denom: "usdc".to_string(),
+            quote_denom: order_target_denom.to_string(),
+            rate: Decimal::one()
+                .checked_div(Decimal::from_str("30000").unwrap())
+                .unwrap(),
+        }),
+        trailing_distance: None,
+        linked_order_id: None,
+        expiration: None,
+        max_slippage: None,
+        min_out: None,
+    };
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &create_order_msg("btc"),
+            &coins(200, "usdc"),
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        ContractError::Paused {
+            feature: "spot orders".to_string()
+        },
+        err.downcast().unwrap()
+    );
+
+    app.execute_contract(
+        Addr::unchecked("owner"),
+        addr.clone(),
+        &ExecuteMsg::UpdateConfig {
+            admin: None,
+            account_history_address: None,
+            allowed_pairs: None,
+            min_order_amounts: None,
+            max_order_amounts: None,
+            max_leverage: None,
+            pause: Some(PauseFlags::default()),
+            fee_bps: None,
+            fee_collector: None,
+            allowed_denoms: None,
+            validate_assets: None,
+            process_order_limit: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &create_order_msg("eth"),
+            &coins(200, "usdc"),
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        ContractError::PairNotAllowed {
+            denom_a: "usdc".to_string(),
+            denom_b: "eth".to_string(),
+        },
+        err.downcast().unwrap()
+    );
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &create_order_msg("btc"),
+            &coins(50, "usdc"),
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        ContractError::OrderAmountTooLow {
+            min: coin(100, "usdc")
+        },
+        err.downcast().unwrap()
+    );
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &create_order_msg("btc"),
+            &coins(600, "usdc"),
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        ContractError::OrderAmountTooHigh {
+            max: coin(500, "usdc")
+        },
+        err.downcast().unwrap()
+    );
+
+    app.execute_contract(
+        Addr::unchecked("user"),
+        addr,
+        &create_order_msg("btc"),
+        &coins(200, "usdc"),
+    )
+    .unwrap();
+}
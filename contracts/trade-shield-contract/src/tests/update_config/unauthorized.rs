@@ -0,0 +1,55 @@
+use super::*;
+
+#[test]
+fn unauthorized() {
+    let mut app = ElysApp::new();
+
+    let instantiate_msg = InstantiateMockMsg {
+        account_history_address: None,
+        spot_orders: vec![],
+        perpetual_orders: vec![],
+    };
+
+    let code = ContractWrapper::new(execute, instantiate, query);
+    let code_id = app.store_code(Box::new(code));
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg,
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("user"),
+            addr,
+            &ExecuteMsg::UpdateConfig {
+                admin: Some("user".to_string()),
+                account_history_address: None,
+                allowed_pairs: None,
+                min_order_amounts: None,
+                max_order_amounts: None,
+                max_leverage: None,
+                pause: None,
+                fee_bps: None,
+                fee_collector: None,
+                allowed_denoms: None,
+                validate_assets: None,
+                process_order_limit: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        ContractError::AdminUnauthorized {
+            sender: Addr::unchecked("user")
+        },
+        err.downcast().unwrap()
+    );
+}
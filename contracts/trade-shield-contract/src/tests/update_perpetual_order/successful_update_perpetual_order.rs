@@ -0,0 +1,137 @@
+use cosmwasm_std::{Addr, Decimal, Int64, SignedDecimal, SignedDecimal256, Uint128};
+use elys_bindings::trade_shield::msg::query_resp::GetPerpetualOrderResp;
+use std::str::FromStr;
+
+use crate::tests::get_order_id_from_events::get_attr_from_events;
+
+use super::*;
+
+// This test case verifies that a pending "limit-open" order can be updated while keeping its id.
+// - The user opens a long BTC order with 30,000 USDC at 40,000.1 with a 2.15 leverage.
+// - The user moves the trigger price to 38,000, sets the leverage to 3 and lowers the collateral to 20,000 USDC.
+// - The user raises the collateral back to 25,000 USDC.
+#[test]
+fn successful_update_perpetual_order() {
+    let usdc = "ibc/2180E84E20F5679FCC760D8C165B60F42065DEF7F46A72B447CFF1B7DC6C0A65";
+    let wallet = vec![("user", coins(30000, usdc))];
+
+    let mut app = ElysApp::new_with_wallets(wallet);
+
+    let instantiate_msg = InstantiateMockMsg {
+        account_history_address: None,
+        spot_orders: vec![],
+        perpetual_orders: vec![],
+    };
+
+    let code = ContractWrapper::new(execute, instantiate, query).with_reply(reply);
+    let code_id = app.store_code(Box::new(code));
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg,
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let trigger_price = |rate: &str| OrderPrice {
+        base_denom: usdc.to_string(),
+        quote_denom: "btc".to_string(),
+        rate: Decimal::from_str(rate).unwrap(),
+    };
+
+    let resp = app
+        .execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::CreatePerpetualOrder {
+                position: Some(PerpetualPosition::Long),
+                leverage: Some(SignedDecimal::from_atomics(Int64::new(215), 2).unwrap()),
+                trading_asset: Some("btc".to_string()),
+                take_profit_price: Some(
+                    SignedDecimal256::from_atomics(Uint128::new(200), 2).unwrap(),
+                ),
+                order_type: PerpetualOrderType::LimitOpen,
+                trigger_price: Some(trigger_price("40000.1")),
+                position_id: None,
+                trailing_distance: None,
+                linked_order_id: None,
+                expiration: None,
+                close_size: None,
+                health_threshold: None,
+            },
+            &coins(30000, usdc),
+        )
+        .unwrap();
+
+    let order_id: u64 = get_attr_from_events(&resp.events, "perpetual_order_id")
+        .unwrap()
+        .parse()
+        .unwrap();
+
+    let get_order = |app: &ElysApp| -> PerpetualOrder {
+        let resp: GetPerpetualOrderResp = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetPerpetualOrder { id: order_id })
+            .unwrap();
+        resp.order
+    };
+
+    let usdc_balance = |app: &ElysApp, address: &str| -> u128 {
+        app.wrap()
+            .query_balance(address, usdc)
+            .unwrap()
+            .amount
+            .u128()
+    };
+
+    let resp = app
+        .execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::UpdatePerpetualOrder {
+                order_id,
+                trigger_price: Some(trigger_price("38000")),
+                collateral: Some(Uint128::new(20000)),
+                leverage: Some(SignedDecimal::from_str("3").unwrap()),
+                take_profit_price: None,
+                expiration: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    assert_eq!(
+        get_attr_from_events(&resp.events, "perpetual_order_id"),
+        Some(order_id.to_string())
+    );
+
+    let order = get_order(&app);
+    assert_eq!(order.trigger_price, Some(trigger_price("38000")));
+    assert_eq!(order.leverage, SignedDecimal::from_str("3").unwrap());
+    assert_eq!(order.collateral, coin(20000, usdc));
+    assert_eq!(usdc_balance(&app, "user"), 10000);
+    assert_eq!(usdc_balance(&app, addr.as_str()), 20000);
+
+    app.execute_contract(
+        Addr::unchecked("user"),
+        addr.clone(),
+        &ExecuteMsg::UpdatePerpetualOrder {
+            order_id,
+            trigger_price: None,
+            collateral: Some(Uint128::new(25000)),
+            leverage: None,
+            take_profit_price: None,
+            expiration: None,
+        },
+        &coins(5000, usdc),
+    )
+    .unwrap();
+
+    assert_eq!(get_order(&app).collateral, coin(25000, usdc));
+    assert_eq!(usdc_balance(&app, "user"), 5000);
+    assert_eq!(usdc_balance(&app, addr.as_str()), 25000);
+}
@@ -0,0 +1,194 @@
+use super::*;
+use cosmwasm_std::{Coin, StdError};
+use elys_bindings::trade_shield::msg::query_resp::GetSpotOrderResp;
+
+use crate::tests::get_order_id_from_events::get_order_id_from_events;
+
+// This test case verifies that a pending order can be updated while keeping its id.
+// - The user sells 2 BTC at 40,000 USDC.
+// - The user lowers the price to 35,000 USDC and adds 1 BTC to the order.
+// - The user reduces the order to 1 BTC and gets 2 BTC back.
+// - The BTC price reaches 36,000 USDC, the updated order is executed.
+#[test]
+fn successful_update_spot_order() {
+    let wallets: Vec<(&str, Vec<Coin>)> = vec![("user", coins(4, "btc"))];
+    let mut app = ElysApp::new_with_wallets(wallets);
+
+    let usdc = "ibc/2180E84E20F5679FCC760D8C165B60F42065DEF7F46A72B447CFF1B7DC6C0A65";
+    let prices = |btc_price: u128| {
+        vec![
+            Price::new(
+                "btc",
+                Decimal::from_atomics(Uint128::new(btc_price), 0).unwrap(),
+            ),
+            Price::new(usdc, Decimal::one()),
+        ]
+    };
+
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices(30000)))
+        .unwrap();
+
+    let code = ContractWrapper::new(execute, instantiate, query)
+        .with_reply(reply)
+        .with_sudo(sudo);
+    let code_id = app.store_code(Box::new(code));
+
+    let instantiate_msg = InstantiateMockMsg {
+        account_history_address: None,
+        spot_orders: vec![],
+        perpetual_orders: vec![],
+    };
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg,
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let order_price = |rate: u128| OrderPrice {
+        base_denom: "btc".to_string(),
+        quote_denom: usdc.to_string(),
+        rate: Decimal::from_atomics(Uint128::new(rate), 0).unwrap(),
+    };
+
+    let resp = app
+        .execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::CreateSpotOrder {
+                order_type: SpotOrderType::LimitSell,
+                order_source_denom: "btc".to_string(),
+                order_target_denom: usdc.to_string(),
+                order_price: Some(order_price(40000)),
+                trailing_distance: None,
+                linked_order_id: None,
+                expiration: None,
+                max_slippage: None,
+                min_out: None,
+            },
+            &coins(2, "btc"),
+        )
+        .unwrap();
+
+    let order_id = get_order_id_from_events(&resp.events).unwrap();
+
+    let get_order = |app: &ElysApp| -> SpotOrder {
+        let resp: GetSpotOrderResp = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetSpotOrder { order_id })
+            .unwrap();
+        resp.order
+    };
+
+    let btc_balance = |app: &ElysApp, address: &str| -> u128 {
+        app.wrap()
+            .query_balance(address, "btc")
+            .unwrap()
+            .amount
+            .u128()
+    };
+
+    // The funds sent should match the added amount.
+    let err = app
+        .execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::UpdateSpotOrder {
+                order_id,
+                order_price: Some(order_price(35000)),
+                order_amount: Some(Uint128::new(3)),
+                max_slippage: None,
+                expiration: None,
+            },
+            &coins(2, "btc"),
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        ContractError::StdError(StdError::generic_err(
+            "funds: The amount sent should be the difference with the current amount: 1btc"
+        )),
+        err.downcast().unwrap()
+    );
+
+    app.execute_contract(
+        Addr::unchecked("user"),
+        addr.clone(),
+        &ExecuteMsg::UpdateSpotOrder {
+            order_id,
+            order_price: Some(order_price(35000)),
+            order_amount: Some(Uint128::new(3)),
+            max_slippage: None,
+            expiration: None,
+        },
+        &coins(1, "btc"),
+    )
+    .unwrap();
+
+    let order = get_order(&app);
+    assert_eq!(order.order_price, order_price(35000));
+    assert_eq!(order.order_amount, coin(3, "btc"));
+    assert_eq!(btc_balance(&app, addr.as_str()), 3);
+
+    app.execute_contract(
+        Addr::unchecked("user"),
+        addr.clone(),
+        &ExecuteMsg::UpdateSpotOrder {
+            order_id,
+            order_price: None,
+            order_amount: Some(Uint128::new(1)),
+            max_slippage: None,
+            expiration: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(get_order(&app).order_amount, coin(1, "btc"));
+    assert_eq!(btc_balance(&app, addr.as_str()), 1);
+    assert_eq!(btc_balance(&app, "user"), 3);
+
+    app.init_modules(|router, _, store| router.custom.set_prices(store, &prices(36000)))
+        .unwrap();
+    app.wasm_sudo(addr.clone(), &SudoMsg::ClockEndBlock {})
+        .unwrap();
+
+    assert_eq!(get_order(&app).status, Status::Executed);
+    assert_eq!(
+        app.wrap()
+            .query_balance("user", usdc)
+            .unwrap()
+            .amount
+            .u128(),
+        36000
+    );
+
+    // An executed order cannot be updated anymore.
+    let err = app
+        .execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::UpdateSpotOrder {
+                order_id,
+                order_price: Some(order_price(37000)),
+                order_amount: None,
+                max_slippage: None,
+                expiration: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        ContractError::UpdateStatusError {
+            order_id,
+            status: Status::Executed
+        },
+        err.downcast().unwrap()
+    );
+}
@@ -0,0 +1,51 @@
+use super::*;
+
+// This test case verifies that only the owner of an order can update it.
+#[test]
+fn unauthorized() {
+    let mut app = ElysApp::new();
+
+    let instantiate_msg = InstantiateMockMsg {
+        account_history_address: None,
+        spot_orders: vec![SpotOrder::new_dummy()],
+        perpetual_orders: vec![],
+    };
+
+    let id = instantiate_msg.spot_orders[0].order_id;
+
+    let code = ContractWrapper::new(execute, instantiate, query);
+    let code_id = app.store_code(Box::new(code));
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &instantiate_msg,
+            &[],
+            "Contract",
+            None,
+        )
+        .unwrap();
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("not_user"),
+            addr,
+            &ExecuteMsg::UpdateSpotOrder {
+                order_id: id,
+                order_price: None,
+                order_amount: Some(Uint128::new(1)),
+                max_slippage: None,
+                expiration: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        ContractError::Unauthorized {
+            sender: Addr::unchecked("not_user")
+        },
+        err.downcast().unwrap()
+    );
+}